@@ -0,0 +1,187 @@
+//! Derive macros for lv2rs-atom.
+//!
+//! This crate contains the implementation of `#[derive(AtomObject)]`. You should not use it
+//! directly, since the macro is re-exported by lv2rs-atom together with the
+//! [`AtomObject`](https://docs.rs/lv2rs-atom/*/lv2rs_atom/object/trait.AtomObject.html) trait. The
+//! documentation of the trait also describes how to use the macro.
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, Attribute, Data, DeriveInput, Error, Fields, LitByteStr, LitStr};
+
+/// Derive the `AtomObject` and `PropertyValue` traits for a struct with named fields.
+///
+/// The struct needs the `#[atom_object(otype = "...")]` attribute with the URI of its class and
+/// every field needs the `#[property(key = "...")]` attribute with the URI of its property.
+#[proc_macro_derive(AtomObject, attributes(atom_object, property))]
+pub fn derive_atom_object(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// Parse the URI argument of an attribute like `#[name(argument = "uri")]`.
+///
+/// The URI is returned as a null-terminated byte string literal.
+fn parse_uri(
+    attrs: &[Attribute],
+    name: &str,
+    argument: &str,
+    span: Span,
+) -> syn::Result<LitByteStr> {
+    let attr = attrs
+        .iter()
+        .find(|attr| attr.path().is_ident(name))
+        .ok_or_else(|| {
+            Error::new(
+                span,
+                format!("missing `#[{}({} = \"...\")]` attribute", name, argument),
+            )
+        })?;
+
+    let mut uri: Option<LitStr> = None;
+    attr.parse_nested_meta(|meta| {
+        if meta.path.is_ident(argument) {
+            uri = Some(meta.value()?.parse()?);
+            Ok(())
+        } else {
+            Err(meta.error(format!("expected `{}`", argument)))
+        }
+    })?;
+    let uri = uri.ok_or_else(|| Error::new_spanned(attr, format!("missing `{}`", argument)))?;
+
+    let mut bytes = uri.value().into_bytes();
+    if bytes.contains(&0) {
+        return Err(Error::new_spanned(uri, "URIs must not contain null bytes"));
+    }
+    bytes.push(0);
+    Ok(LitByteStr::new(&bytes, uri.span()))
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(Error::new_spanned(
+                    &input.ident,
+                    "`AtomObject` can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "`AtomObject` can only be derived for structs",
+            ))
+        }
+    };
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let otype = parse_uri(&input.attrs, "atom_object", "otype", name.span())?;
+
+    let mut idents = Vec::new();
+    let mut types = Vec::new();
+    let mut keys = Vec::new();
+    for field in fields {
+        let ident = field.ident.as_ref().unwrap();
+        keys.push(parse_uri(&field.attrs, "property", "key", ident.span())?);
+        idents.push(ident);
+        types.push(&field.ty);
+    }
+    let values: Vec<_> = (0..idents.len())
+        .map(|index| format_ident!("__value_{}", index))
+        .collect();
+    let indices: Vec<_> = (0..idents.len()).collect();
+    let count = idents.len();
+
+    Ok(quote! {
+        impl #impl_generics ::lv2rs_atom::object::AtomObject for #name #ty_generics #where_clause {
+            fn object_type() -> &'static ::std::ffi::CStr {
+                unsafe { ::std::ffi::CStr::from_bytes_with_nul_unchecked(#otype) }
+            }
+
+            #[allow(unused_variables, unused_mut)]
+            fn read_object(
+                object: &::lv2rs_atom::object::Object,
+                urids: &mut ::lv2rs_atom::__private::CachedMap,
+            ) -> ::std::result::Result<Self, ::lv2rs_atom::object::GetPropertyError> {
+                let keys: [::lv2rs_atom::__private::URID; #count] = [#(
+                    urids.map(unsafe { ::std::ffi::CStr::from_bytes_with_nul_unchecked(#keys) })
+                ),*];
+                #(
+                    let mut #values: ::std::option::Option<#types> = ::std::option::Option::None;
+                )*
+                for (header, atom) in object.iter() {
+                    #(
+                        if header.key == keys[#indices] && #values.is_none() {
+                            #values = ::std::option::Option::Some(
+                                <#types as ::lv2rs_atom::object::PropertyValue>::read_property(
+                                    header.key, atom, urids,
+                                )?,
+                            );
+                        }
+                    )*
+                }
+                ::std::result::Result::Ok(Self {#(
+                    #idents: match #values {
+                        ::std::option::Option::Some(value) => value,
+                        ::std::option::Option::None => {
+                            <#types as ::lv2rs_atom::object::PropertyValue>::read_missing(
+                                keys[#indices],
+                            )?
+                        }
+                    },
+                )*})
+            }
+
+            fn write_properties<'a, W>(
+                &self,
+                frame: &mut W,
+                urids: &mut ::lv2rs_atom::__private::CachedMap,
+            ) -> ::std::result::Result<(), ::lv2rs_atom::object::WritePropertyError>
+            where
+                W: ::lv2rs_atom::frame::WritingFrame<'a>
+                    + ::lv2rs_atom::frame::WritingFrameExt<'a, ::lv2rs_atom::object::Object>,
+            {
+                #(
+                    let key = urids.map(unsafe {
+                        ::std::ffi::CStr::from_bytes_with_nul_unchecked(#keys)
+                    });
+                    <#types as ::lv2rs_atom::object::PropertyValue>::write_property(
+                        &self.#idents, frame, key, urids,
+                    )?;
+                )*
+                ::std::result::Result::Ok(())
+            }
+        }
+
+        impl #impl_generics ::lv2rs_atom::object::PropertyValue for #name #ty_generics #where_clause {
+            fn read_property(
+                key: ::lv2rs_atom::__private::URID,
+                atom: &::lv2rs_atom::Atom,
+                urids: &mut ::lv2rs_atom::__private::CachedMap,
+            ) -> ::std::result::Result<Self, ::lv2rs_atom::object::GetPropertyError> {
+                <Self as ::lv2rs_atom::object::AtomObject>::read_nested(key, atom, urids)
+            }
+
+            fn write_property<'a, W>(
+                &self,
+                frame: &mut W,
+                key: ::lv2rs_atom::__private::URID,
+                urids: &mut ::lv2rs_atom::__private::CachedMap,
+            ) -> ::std::result::Result<(), ::lv2rs_atom::object::WritePropertyError>
+            where
+                W: ::lv2rs_atom::frame::WritingFrame<'a>
+                    + ::lv2rs_atom::frame::WritingFrameExt<'a, ::lv2rs_atom::object::Object>,
+            {
+                <Self as ::lv2rs_atom::object::AtomObject>::write_nested(self, frame, key, urids)
+            }
+        }
+    })
+}
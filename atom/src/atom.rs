@@ -77,7 +77,7 @@ impl Atom {
         &self,
         urids: &mut urid::CachedMap,
     ) -> Result<&A, GetBodyError> {
-        if self.atom_type != urids.map(A::get_uri()) {
+        if !A::accepts_type(self.atom_type, urids) {
             return Err(GetBodyError::WrongURID);
         }
         let raw_body = self.get_raw_body();
@@ -122,6 +122,14 @@ pub trait AtomBody {
     /// Return the URI of the atom type.
     fn get_uri() -> &'static CStr;
 
+    /// Return `true` if atoms with the given type URID contain bodies of this type.
+    ///
+    /// By default, only the URID of [`get_uri`](#tymethod.get_uri) is accepted. Bodies that may
+    /// also be sent with other, for example deprecated, types override this method.
+    fn accepts_type(atom_type: URID, urids: &mut urid::CachedMap) -> bool {
+        atom_type == urids.map(Self::get_uri())
+    }
+
     /// Write out a basic but valid atom body.
     ///
     /// Implementors should use the writing frame to write out general information about the atom,
@@ -0,0 +1,145 @@
+//! Raw binary data.
+//!
+//! A [chunk](type.Chunk.html) is the standard container for blobs of raw bytes, whose meaning is
+//! not known to the atom type system. Hosts also use chunks to describe the free space of output
+//! ports: An empty chunk with a size of N bytes tells the plugin that it may write an atom with a
+//! body of up to N bytes. The [`AtomOutputPort`](../ports/struct.AtomOutputPort.html) understands
+//! this and uses the whole space.
+//!
+//! When initialized, a chunk contains the bytes it was initialized with. More bytes can be appended
+//! using the [`ChunkWritingFrame`](trait.ChunkWritingFrame.html) trait. Every writing frame
+//! implements this trait via a blanket implementation and the trait is included in the crate's
+//! prelude. You can, therefore, act as if the extended methods were normal methods of a writing
+//! frame.
+//!
+//! An example:
+//!
+//!     extern crate lv2rs_atom as atom;
+//!     extern crate lv2rs_urid as urid;
+//!
+//!     use atom::prelude::*;
+//!     use atom::ports::*;
+//!     use urid::{CachedMap, debug::DebugMap};
+//!
+//!     pub struct Plugin {
+//!         in_port: AtomInputPort<Chunk>,
+//!         out_port: AtomOutputPort<Chunk>,
+//!         urids: CachedMap,
+//!     }
+//!
+//!     impl Plugin {
+//!         /// Simulated `run` method.
+//!         fn run(&mut self) {
+//!             // Writing
+//!             {
+//!                 let mut frame =
+//!                     unsafe { self.out_port.write_atom_body(b"\x01\x02", &mut self.urids) }
+//!                         .unwrap();
+//!                 frame.append(b"\x03\x04").unwrap();
+//!             }
+//!
+//!             // Reading.
+//!             let chunk = unsafe { self.in_port.get_atom_body(&mut self.urids) }.unwrap();
+//!             assert_eq!(b"\x01\x02\x03\x04", chunk.as_slice());
+//!         }
+//!     }
+//!
+//!     // Getting a debug URID map.
+//!     let mut debug_map = DebugMap::new();
+//!     let mut urids = unsafe {debug_map.create_cached_map()};
+//!
+//!     // Creating the plugin.
+//!     let mut plugin = Plugin {
+//!         in_port: AtomInputPort::new(),
+//!         out_port: AtomOutputPort::new(),
+//!         urids: urids,
+//!     };
+//!
+//!     // Creating the atom space.
+//!     let mut atom_space = vec![0u8; 256];
+//!     let atom = unsafe { (atom_space.as_mut_ptr() as *mut Atom).as_mut() }.unwrap();
+//!     *(atom.mut_size()) = 256 - 8;
+//!
+//!     // Connecting the ports.
+//!     plugin.in_port.connect_port(atom as &Atom);
+//!     plugin.out_port.connect_port(atom);
+//!
+//!     // Calling `run`.
+//!     plugin.run();
+use crate::atom::{array::*, *};
+use crate::frame::{WritingFrame, WritingFrameExt};
+use crate::ports::WriteAtomError;
+use crate::uris;
+use std::ffi::CStr;
+
+/// Header of a chunk.
+///
+/// Chunks have no header, but since the raw representation of a chunk is the same as the one of a
+/// [tuple](../tuple/type.Tuple.html), this empty struct is used to tell them apart.
+#[repr(C)]
+pub struct ChunkHeader;
+
+impl ArrayAtomHeader for ChunkHeader {
+    type InitializationParameter = ();
+
+    unsafe fn initialize<'a, W, T>(_: &mut W, _: &(), _: &mut urid::CachedMap) -> Result<(), ()>
+    where
+        T: 'static + Sized + Copy,
+        ArrayAtomBody<Self, T>: AtomBody,
+        W: WritingFrame<'a> + WritingFrameExt<'a, ArrayAtomBody<Self, T>>,
+    {
+        Ok(())
+    }
+}
+
+/// Raw binary data.
+///
+/// See the [module documentation](index.html) for more information.
+pub type Chunk = ArrayAtomBody<ChunkHeader, u8>;
+
+impl AtomBody for Chunk {
+    type InitializationParameter = [u8];
+
+    fn get_uri() -> &'static CStr {
+        unsafe { CStr::from_bytes_with_nul_unchecked(uris::CHUNK_TYPE_URI) }
+    }
+
+    unsafe fn initialize_body<'a, W>(
+        writer: &mut W,
+        data: &[u8],
+        urids: &mut urid::CachedMap,
+    ) -> Result<(), ()>
+    where
+        W: WritingFrame<'a> + WritingFrameExt<'a, Self>,
+    {
+        Self::__initialize_body(writer, &(), urids)?;
+        Self::append(writer, data)
+    }
+
+    fn create_ref(raw_data: &[u8]) -> Result<&Self, ()> {
+        Self::__create_ref(raw_data)
+    }
+}
+
+impl Chunk {
+    /// Return the bytes of the chunk.
+    pub fn as_slice(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+/// Extension for [`WritingFrame`](../frame/trait.WritingFrame.html) and
+/// [`WritingFrameExt`](../frame/trait.WritingFrameExt.html) for chunks.
+///
+/// See the [module documentation](index.html) for more information.
+pub trait ChunkWritingFrame<'a>: WritingFrame<'a> + WritingFrameExt<'a, Chunk> {
+    /// Append raw bytes to the end of the chunk.
+    ///
+    /// If the bytes don't fit into the remaining space, nothing is written and an error is
+    /// returned.
+    fn append(&mut self, data: &[u8]) -> Result<(), WriteAtomError> {
+        unsafe { Chunk::append(self, data) }.map_err(|_| WriteAtomError::InsufficientSpace)
+    }
+}
+
+impl<'a, W> ChunkWritingFrame<'a> for W where W: WritingFrame<'a> + WritingFrameExt<'a, Chunk> {}
@@ -0,0 +1,353 @@
+//! Compatibility layer for the deprecated event ports.
+//!
+//! Before atom sequences were introduced, events were transported in `ev:EventPort` buffers,
+//! defined by the [event extension](http://lv2plug.in/ns/ext/event). Some older hosts still only
+//! support these ports. The wrappers of this module present the contents of event buffers as
+//! atoms with time stamps, just like [`Sequence::iter`](../sequence/type.Sequence.html#method.iter)
+//! does, and write atoms to event buffers.
+//!
+//! The types of events are identified by IDs that are mapped with the deprecated
+//! [uri-map](../../lv2rs_urid/uri_map/index.html) feature in the context of the event extension.
+//! These IDs differ from the URIDs of the atom types and therefore, a plugin has to declare the
+//! types it wants to read and write with [`EventTypes`](struct.EventTypes.html). Events of other
+//! types are skipped.
+//!
+//!     extern crate lv2rs_atom as atom;
+//!     extern crate lv2rs_urid as urid;
+//!
+//!     use atom::event::*;
+//!     use atom::prelude::*;
+//!     use std::ffi::CStr;
+//!
+//!     /// Simulated `run` function of a plugin that counts the MIDI events it receives.
+//!     fn run(
+//!         input: &mut EventInputPort,
+//!         output: &mut EventOutputPort,
+//!         urids: &mut urid::CachedMap,
+//!     ) -> usize {
+//!         let midi_event = CStr::from_bytes_with_nul(b"http://lv2plug.in/ns/ext/midi#MidiEvent\0")
+//!             .unwrap();
+//!         let midi_urid = urids.map(midi_event);
+//!
+//!         unsafe { output.clear() }.unwrap();
+//!         let mut count = 0;
+//!         for (time, atom) in unsafe { input.iter() }.unwrap() {
+//!             if atom.atom_type() == midi_urid {
+//!                 count += 1;
+//!                 // Passing the event through.
+//!                 unsafe { output.push_event(time, atom) }.unwrap();
+//!             }
+//!         }
+//!         count
+//!     }
+use crate::atom::Atom;
+use crate::sequence::TimeStamp;
+use std::ffi::CStr;
+use std::mem::size_of;
+use std::os::raw::c_int;
+use urid::uri_map::UriMap;
+use urid::{CachedMap, URID};
+
+pub const URI: &[u8] = b"http://lv2plug.in/ns/ext/event\0";
+pub const EVENT_PORT_URI: &[u8] = b"http://lv2plug.in/ns/ext/event#EventPort\0";
+pub const SUPPORTS_EVENT_URI: &[u8] = b"http://lv2plug.in/ns/ext/event#supportsEvent\0";
+
+/// The stamp type of buffers whose events are stamped with frames and subframes.
+pub const AUDIO_STAMP: u16 = 0;
+
+/// The header of an event in an event buffer.
+///
+/// The data of the event follows directly after the header and every event is padded to 64 bits.
+///
+/// This type is `repr(C)` and is used to interpret raw data.
+#[repr(C)]
+pub struct EventHeader {
+    /// The time stamp of the event in frames.
+    pub frames: u32,
+    /// The fractional part of the time stamp, in 1/2^32 frames.
+    pub subframes: u32,
+    /// The uri-map ID of the event type.
+    ///
+    /// The type zero is reserved for events that are managed by the host and can't be copied.
+    pub event_type: u16,
+    /// The size of the data in bytes.
+    pub size: u16,
+}
+
+/// An event buffer, as passed to an `ev:EventPort` by the host.
+///
+/// This type is `repr(C)` and is used to interpret raw data.
+#[repr(C)]
+pub struct EventBuffer {
+    /// The events of the buffer.
+    pub data: *mut u8,
+    /// The size of this header in bytes.
+    pub header_size: u16,
+    /// The type of the time stamps, which is [`AUDIO_STAMP`](constant.AUDIO_STAMP.html) for all
+    /// known hosts.
+    pub stamp_type: u16,
+    /// The number of events in the buffer.
+    pub event_count: u32,
+    /// The size of the `data` memory in bytes.
+    pub capacity: u32,
+    /// The number of bytes of `data` that are used by events.
+    pub size: u32,
+}
+
+/// Errors that may occur when reading or writing event buffers.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EventError {
+    /// The internal pointer points to zero.
+    ///
+    /// Maybe `connect_port` is not implemented correctly?
+    NullPointer,
+    /// The buffer uses time stamps other than frames.
+    UnsupportedStampType,
+    /// An event doesn't fit into the buffer.
+    ///
+    /// When reading, this means that the buffer is malformed.
+    InsufficientSpace,
+    /// The type of the event wasn't declared in the [`EventTypes`](struct.EventTypes.html).
+    UnknownType,
+    /// The time stamp is given in beats, which isn't supported by event buffers.
+    WrongTimeUnit,
+}
+
+/// The event types a plugin reads or writes, with their uri-map IDs and their URIDs.
+pub struct EventTypes {
+    types: Vec<(u16, URID)>,
+}
+
+impl EventTypes {
+    /// Map the given type URIs with both the uri-map feature and the URID map.
+    ///
+    /// Types that can't be mapped by the uri-map feature, or whose IDs don't fit into an event
+    /// header, are left out.
+    pub fn new(uri_map: &mut UriMap, urids: &mut CachedMap, uris: &[&'static CStr]) -> Self {
+        let context = unsafe { CStr::from_bytes_with_nul_unchecked(URI) };
+        let types = uris
+            .iter()
+            .filter_map(|uri| {
+                let id = uri_map.uri_to_id(Some(context), uri);
+                if id == 0 || id > u32::from(u16::MAX) {
+                    None
+                } else {
+                    Some((id as u16, urids.map(uri)))
+                }
+            })
+            .collect();
+        Self { types }
+    }
+
+    /// Return the URID of the type with the given uri-map ID.
+    pub fn urid(&self, id: u16) -> Option<URID> {
+        self.types
+            .iter()
+            .find(|(other, _)| *other == id)
+            .map(|(_, urid)| *urid)
+    }
+
+    /// Return the uri-map ID of the type with the given URID.
+    pub fn id(&self, urid: URID) -> Option<u16> {
+        self.types
+            .iter()
+            .find(|(_, other)| *other == urid)
+            .map(|(id, _)| *id)
+    }
+}
+
+/// Round the size up to the next multiple of 64 bits.
+fn pad_size(size: usize) -> usize {
+    (size + 7) & !7
+}
+
+/// Wrapper for reading event buffers.
+///
+/// Since the header of an event differs from the header of an atom, the events are copied into
+/// atoms first. The memory for these copies is allocated when the port is created; If the events
+/// don't fit into it, it's grown, which is not real-time safe. Therefore, the capacity should be
+/// at least as large as the buffers of the host.
+pub struct EventInputPort {
+    buffer: *const EventBuffer,
+    types: EventTypes,
+    storage: Vec<u64>,
+    events: Vec<(i64, usize)>,
+}
+
+impl EventInputPort {
+    /// Create a new port for the given types, with memory for `capacity` bytes of events.
+    pub fn new(types: EventTypes, capacity: usize) -> Self {
+        Self {
+            buffer: std::ptr::null(),
+            types,
+            storage: Vec::with_capacity(capacity / size_of::<u64>()),
+            events: Vec::new(),
+        }
+    }
+
+    /// Set the internal buffer pointer.
+    ///
+    /// This function should only be called by a plugin's `connect_port` function.
+    pub fn connect_port(&mut self, buffer: *const EventBuffer) {
+        self.buffer = buffer;
+    }
+
+    /// Return an iterator over the events of the buffer, as atoms with time stamps.
+    ///
+    /// The time stamps are given in frames; Subframes are dropped. Events with unknown types are
+    /// skipped.
+    ///
+    /// # Safety
+    ///
+    /// This method dereferences the raw, internal pointer. Make sure that your plugin's
+    /// `connect_port` method calls this port's [`connect_port`](#method.connect_port) method
+    /// correctly!
+    pub unsafe fn iter(&mut self) -> Result<impl Iterator<Item = (TimeStamp, &Atom)>, EventError> {
+        let buffer = self.buffer.as_ref().ok_or(EventError::NullPointer)?;
+        if buffer.stamp_type != AUDIO_STAMP {
+            return Err(EventError::UnsupportedStampType);
+        }
+        let data = if buffer.data.is_null() {
+            &[][..]
+        } else {
+            std::slice::from_raw_parts(buffer.data as *const u8, buffer.size as usize)
+        };
+
+        self.storage.clear();
+        self.events.clear();
+        let mut offset = 0;
+        for _ in 0..buffer.event_count {
+            if offset + size_of::<EventHeader>() > data.len() {
+                return Err(EventError::InsufficientSpace);
+            }
+            let header = std::ptr::read_unaligned(data[offset..].as_ptr() as *const EventHeader);
+            let body_start = offset + size_of::<EventHeader>();
+            let body_end = body_start + header.size as usize;
+            if body_end > data.len() {
+                return Err(EventError::InsufficientSpace);
+            }
+            offset = pad_size(body_end);
+
+            let atom_type = match self.types.urid(header.event_type) {
+                Some(atom_type) => atom_type,
+                None => continue,
+            };
+            let atom_offset = self.storage.len() * size_of::<u64>();
+            let atom_size = size_of::<Atom>() + header.size as usize;
+            self.storage.resize(
+                self.storage.len() + pad_size(atom_size) / size_of::<u64>(),
+                0,
+            );
+            let atom_ptr = (self.storage.as_mut_ptr() as *mut u8).add(atom_offset);
+            let atom = (atom_ptr as *mut Atom).as_mut().unwrap();
+            *atom.mut_size() = c_int::from(header.size);
+            *atom.mut_atom_type() = atom_type;
+            std::ptr::copy_nonoverlapping(
+                data[body_start..].as_ptr(),
+                atom_ptr.add(size_of::<Atom>()),
+                header.size as usize,
+            );
+            self.events.push((i64::from(header.frames), atom_offset));
+        }
+
+        let storage = self.storage.as_ptr() as *const u8;
+        Ok(self.events.iter().map(move |(frames, offset)| {
+            let atom = (storage.add(*offset) as *const Atom).as_ref().unwrap();
+            (TimeStamp::Frames(*frames), atom)
+        }))
+    }
+}
+
+/// Wrapper for writing event buffers.
+pub struct EventOutputPort {
+    buffer: *mut EventBuffer,
+    types: EventTypes,
+}
+
+impl EventOutputPort {
+    /// Create a new port for the given types.
+    pub fn new(types: EventTypes) -> Self {
+        Self {
+            buffer: std::ptr::null_mut(),
+            types,
+        }
+    }
+
+    /// Set the internal buffer pointer.
+    ///
+    /// This function should only be called by a plugin's `connect_port` function.
+    pub fn connect_port(&mut self, buffer: *mut EventBuffer) {
+        self.buffer = buffer;
+    }
+
+    /// Remove all events from the buffer.
+    ///
+    /// The buffer is not cleared by the host and therefore, this method has to be called in every
+    /// `run` call before events are pushed.
+    ///
+    /// # Safety
+    ///
+    /// This method dereferences the raw, internal pointer. Make sure that your plugin's
+    /// `connect_port` method calls this port's [`connect_port`](#method.connect_port) method
+    /// correctly!
+    pub unsafe fn clear(&mut self) -> Result<(), EventError> {
+        let buffer = self.buffer.as_mut().ok_or(EventError::NullPointer)?;
+        buffer.event_count = 0;
+        buffer.size = 0;
+        Ok(())
+    }
+
+    /// Append an atom to the buffer as an event.
+    ///
+    /// The time stamp has to be given in frames and must not be before the time stamp of the
+    /// previous event.
+    ///
+    /// # Safety
+    ///
+    /// This method has the same requirements as [`clear`](#method.clear).
+    pub unsafe fn push_event(&mut self, time: TimeStamp, atom: &Atom) -> Result<(), EventError> {
+        let buffer = self.buffer.as_mut().ok_or(EventError::NullPointer)?;
+        if buffer.stamp_type != AUDIO_STAMP {
+            return Err(EventError::UnsupportedStampType);
+        }
+        let frames = match time {
+            TimeStamp::Frames(frames) => frames as u32,
+            TimeStamp::Beats(_) => return Err(EventError::WrongTimeUnit),
+        };
+        let event_type = self
+            .types
+            .id(atom.atom_type())
+            .ok_or(EventError::UnknownType)?;
+        let body = atom.get_raw_body();
+        if body.len() > usize::from(u16::MAX) {
+            return Err(EventError::InsufficientSpace);
+        }
+
+        let offset = buffer.size as usize;
+        let event_size = size_of::<EventHeader>() + body.len();
+        if buffer.data.is_null() || offset + pad_size(event_size) > buffer.capacity as usize {
+            return Err(EventError::InsufficientSpace);
+        }
+        let header = EventHeader {
+            frames,
+            subframes: 0,
+            event_type,
+            size: body.len() as u16,
+        };
+        let event_ptr = buffer.data.add(offset);
+        std::ptr::write_unaligned(event_ptr as *mut EventHeader, header);
+        std::ptr::copy_nonoverlapping(
+            body.as_ptr(),
+            event_ptr.add(size_of::<EventHeader>()),
+            body.len(),
+        );
+        std::ptr::write_bytes(
+            event_ptr.add(event_size),
+            0,
+            pad_size(event_size) - event_size,
+        );
+        buffer.size += pad_size(event_size) as u32;
+        buffer.event_count += 1;
+        Ok(())
+    }
+}
@@ -32,6 +32,22 @@ pub trait WritingFrame<'a> {
 
     /// Return an immutable reference to the managed atom header.
     fn get_atom(&self) -> &Atom;
+
+    /// Return a mutable reference to the managed atom header, if the frame grants access to it.
+    ///
+    /// The frames of this crate always return `Some`. The default implementation returns `None`, so
+    /// that other frames don't have to implement this method. Extension methods that have to change
+    /// the header, like
+    /// [`ObjectWritingFrame::set_object_type`](../object/trait.ObjectWritingFrame.html#method.set_object_type),
+    /// fail with `WriteAtomError::HeaderUnavailable` on such frames.
+    ///
+    /// # Safety
+    ///
+    /// Changing the header may break the atom structure. The size is managed by the frame and must
+    /// not be altered and the type must stay compatible with the written body.
+    unsafe fn get_atom_mut(&mut self) -> Option<&mut Atom> {
+        None
+    }
 }
 
 /// Extended functionality for writing frames.
@@ -157,6 +173,10 @@ impl<'a, A: AtomBody + ?Sized> WritingFrame<'a> for RootFrame<'a, A> {
     fn get_atom(&self) -> &Atom {
         self.atom
     }
+
+    unsafe fn get_atom_mut(&mut self) -> Option<&mut Atom> {
+        Some(self.atom)
+    }
 }
 
 impl<'a, A: AtomBody + ?Sized> WritingFrameExt<'a, A> for RootFrame<'a, A> {}
@@ -212,6 +232,10 @@ where
     fn get_atom(&self) -> &Atom {
         self.atom
     }
+
+    unsafe fn get_atom_mut(&mut self) -> Option<&mut Atom> {
+        Some(self.atom)
+    }
 }
 
 impl<'a, 'b, A: AtomBody + ?Sized> WritingFrameExt<'b, A> for NestedFrame<'a, 'b, A> {}
@@ -1,7 +1,7 @@
 //! The purpose of this crate is to provide safe, idiomatic and easy-to-use means to use the type
 //! system introduced by the LV2 atom library. This type system is (relatively) portable and can be
 //! used to exchange information of arbitrary type among LV2 plugins.
-//! 
+//!
 //! This is a frozen prototype and therefore, development of this crate will not continue here. Further
 //! development continues as [rust-lv2](https://github.com/rust-dsp/rust-lv2).
 //!
@@ -23,11 +23,15 @@
 //!     * `i64`
 //!     * `bool`
 //!     * `URID`
+//! * [`Chunk`](chunk/index.html): Raw binary data.
 //! * [`Literal`](literal/index.html): A proper UTF-8 string.
 //! * [`Object`](object/index.html): Compound type similar to tuples that maps URIDs to atoms.
+//! Structs can be stored as objects by deriving [`AtomObject`](object/trait.AtomObject.html).
 //! * [`Sequence`](sequence/index.html): Tuple with additional time stamps for every atom.
 //! Usually used for frame-perfect, event-based data.
-//! * [`AtomString`](string/index.html): An old-school ASCII string, also used for URIs.
+//! * [`Sound`](sound/index.html): A vector of audio samples.
+//! * [`AtomString`](string/index.html): An old-school ASCII string.
+//! * [`AtomURI` and `AtomPath`](uri/index.html): UTF-8 encoded URIs and file paths.
 //! * [`Tuple`](tuple/index.html): Heterogenous array of atoms, including dynamically sized
 //! ones.
 //! * [`Vector`](vector/index.html): Homogenous array of sized atoms, like numbers.
@@ -58,22 +62,37 @@
 //! for meaningfulness. Instead, you should use the safe methods provided by the writing frame
 //! extensions, which are tailored for specific atoms and guarantee the consistency of the resulting
 //! output. You can read more about them in their specific module descriptions.
+extern crate lv2rs_core as core;
 extern crate lv2rs_urid as urid;
 
 mod atom;
+pub mod chunk;
+pub mod event;
 pub mod frame;
 pub mod literal;
+pub mod morph;
 pub mod object;
 pub mod ports;
 pub mod scalar;
 pub mod sequence;
+pub mod sound;
 pub mod string;
 pub mod tuple;
+pub mod uri;
 pub mod uris;
 pub mod vector;
 
 pub use atom::*;
 
+/// Items used by the code generated by `#[derive(AtomObject)]`.
+///
+/// This way, users of the derive macro don't need to depend on lv2rs-urid themselves. This module
+/// is not part of the public API.
+#[doc(hidden)]
+pub mod __private {
+    pub use urid::{CachedMap, URID};
+}
+
 /// Re-exportation module that contains all traits necessary to use lv2rs-atom.
 pub mod prelude {
     pub use crate::frame::{WritingFrame, WritingFrameExt};
@@ -82,13 +101,14 @@ pub mod prelude {
     // Atom bodies.
     pub use crate::atom::{Atom, AtomBody};
     pub use crate::{
-        literal::Literal, object::Object, sequence::Sequence, string::AtomString, tuple::Tuple,
-        vector::Vector,
+        chunk::Chunk, literal::Literal, object::Object, sequence::Sequence, sound::Sound,
+        string::AtomString, tuple::Tuple, uri::AtomPath, uri::AtomURI, vector::Vector,
     };
 
     // Writing frame extensions
     pub use crate::{
-        literal::LiteralWritingFrame, object::ObjectWritingFrame, sequence::SequenceWritingFrame,
-        tuple::TupleWritingFrame, vector::VectorWritingFrame,
+        chunk::ChunkWritingFrame, literal::LiteralWritingFrame, object::ObjectWritingFrame,
+        sequence::SequenceWritingFrame, sound::SoundWritingFrame, tuple::TupleWritingFrame,
+        vector::VectorWritingFrame,
     };
 }
@@ -0,0 +1,482 @@
+//! Ports whose type changes at runtime.
+//!
+//! Generic plugins like mixers or routers can process any kind of signal, as long as all of their
+//! ports have the same type. With the [morph extension](http://lv2plug.in/ns/ext/morph), such
+//! plugins declare ports whose type can be changed by the host:
+//!
+//! * A `morph:MorphPort` has a default type, which is its class in the plugin's description, and
+//!   may be changed to one of the types declared with `morph:supportsType` by the host.
+//! * A `morph:AutoMorphPort` changes its type automatically when the type of other ports changes.
+//!   The host asks the plugin for the current type of these ports.
+//!
+//! The ports are wrapped by a [`MorphInputPort`](struct.MorphInputPort.html) or a
+//! [`MorphOutputPort`](struct.MorphOutputPort.html), which contain an audio, CV or atom port
+//! depending on the current type. When the type changes, the old port is replaced by a new,
+//! unconnected port of the new type. Therefore, a pointer to a buffer of the old type can never be
+//! read as the new type; The host has to connect the port again before the next `run` call.
+//!
+//! Plugins implement the [`Morph`](trait.Morph.html) trait and return the
+//! [`MorphInterface`](struct.MorphInterface.html) in their `extension_data` function.
+use crate::atom::AtomBody;
+use crate::ports::*;
+use crate::uris as atom_uris;
+use core::ports::{AudioInputPort, AudioOutputPort};
+use core::{ttl, uris, ExtensionData, Handle, Plugin};
+use std::ffi::CStr;
+use std::os::raw::*;
+use urid::{CachedMap, URID};
+
+pub const URI: &[u8] = b"http://lv2plug.in/ns/ext/morph\0";
+pub const AUTO_MORPH_PORT_URI: &[u8] = b"http://lv2plug.in/ns/ext/morph#AutoMorphPort\0";
+pub const MORPH_PORT_URI: &[u8] = b"http://lv2plug.in/ns/ext/morph#MorphPort\0";
+pub const CURRENT_TYPE_URI: &[u8] = b"http://lv2plug.in/ns/ext/morph#currentType\0";
+pub const INTERFACE_URI: &[u8] = b"http://lv2plug.in/ns/ext/morph#interface\0";
+pub const SUPPORTS_TYPE_URI: &[u8] = b"http://lv2plug.in/ns/ext/morph#supportsType\0";
+
+/// The type of a morph port.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PortType {
+    /// `lv2:AudioPort`: A buffer of audio samples.
+    Audio,
+    /// `lv2:CVPort`: A buffer of control values with the sample rate of audio.
+    CV,
+    /// `atom:AtomPort`: An atom.
+    Atom,
+}
+
+impl PortType {
+    /// Return the URI of the type.
+    pub fn get_uri(&self) -> &'static CStr {
+        let uri = match self {
+            PortType::Audio => uris::AUDIO_PORT_URI,
+            PortType::CV => uris::CV_PORT_URI,
+            PortType::Atom => atom_uris::ATOM_PORT_TYPE_URI,
+        };
+        unsafe { CStr::from_bytes_with_nul_unchecked(uri) }
+    }
+
+    /// Return the type as a prefixed name, e.g. `lv2:AudioPort`.
+    ///
+    /// The name uses the prefixes of
+    /// [`ttl::PREFIXES`](../../lv2rs_core/ttl/constant.PREFIXES.html).
+    pub fn prefixed_name(&self) -> &'static str {
+        match self {
+            PortType::Audio => "lv2:AudioPort",
+            PortType::CV => "lv2:CVPort",
+            PortType::Atom => "atom:AtomPort",
+        }
+    }
+
+    /// Try to get the type with the given URID.
+    pub fn from_urid(urid: URID, urids: &mut CachedMap) -> Option<Self> {
+        [PortType::Audio, PortType::CV, PortType::Atom]
+            .iter()
+            .find(|port_type| urids.map(port_type.get_uri()) == urid)
+            .cloned()
+    }
+}
+
+/// An entry of an options array, as defined by the options extension.
+///
+/// The host may pass options with a morph request, for example the new buffer size. The array is
+/// terminated by an entry whose key is zero and whose value is null.
+#[repr(C)]
+pub struct OptionsOption {
+    /// The context of the option, e.g. the instance or a port.
+    pub context: u32,
+    /// The subject of the option, e.g. the index of a port.
+    pub subject: u32,
+    /// The URID of the option.
+    pub key: URID,
+    /// The size of the value in bytes.
+    pub size: u32,
+    /// The URID of the value's type.
+    pub value_type: URID,
+    /// A pointer to the value.
+    pub value: *const c_void,
+}
+
+/// The current port of a [`MorphInputPort`](struct.MorphInputPort.html).
+pub enum MorphedInput<A: AtomBody + ?Sized> {
+    Audio(AudioInputPort),
+    CV(AudioInputPort),
+    Atom(AtomInputPort<A>),
+}
+
+impl<A: AtomBody + ?Sized> MorphedInput<A> {
+    fn new(port_type: PortType) -> Self {
+        match port_type {
+            PortType::Audio => MorphedInput::Audio(AudioInputPort::new()),
+            PortType::CV => MorphedInput::CV(AudioInputPort::new()),
+            PortType::Atom => MorphedInput::Atom(AtomInputPort::new()),
+        }
+    }
+
+    /// Return the type of the port.
+    pub fn port_type(&self) -> PortType {
+        match self {
+            MorphedInput::Audio(_) => PortType::Audio,
+            MorphedInput::CV(_) => PortType::CV,
+            MorphedInput::Atom(_) => PortType::Atom,
+        }
+    }
+}
+
+/// The current port of a [`MorphOutputPort`](struct.MorphOutputPort.html).
+pub enum MorphedOutput<A: AtomBody + ?Sized> {
+    Audio(AudioOutputPort),
+    CV(AudioOutputPort),
+    Atom(AtomOutputPort<A>),
+}
+
+impl<A: AtomBody + ?Sized> MorphedOutput<A> {
+    fn new(port_type: PortType) -> Self {
+        match port_type {
+            PortType::Audio => MorphedOutput::Audio(AudioOutputPort::new()),
+            PortType::CV => MorphedOutput::CV(AudioOutputPort::new()),
+            PortType::Atom => MorphedOutput::Atom(AtomOutputPort::new()),
+        }
+    }
+
+    /// Return the type of the port.
+    pub fn port_type(&self) -> PortType {
+        match self {
+            MorphedOutput::Audio(_) => PortType::Audio,
+            MorphedOutput::CV(_) => PortType::CV,
+            MorphedOutput::Atom(_) => PortType::Atom,
+        }
+    }
+}
+
+/// Add the morph statements of a port to its description.
+fn add_morph_statements(
+    node: &mut ttl::Node,
+    automatic: bool,
+    default: PortType,
+    supported: &[PortType],
+) {
+    node.add("a", default.prefixed_name());
+    if automatic {
+        node.add("a", "morph:AutoMorphPort");
+    } else {
+        node.add("a", "morph:MorphPort");
+    }
+    for port_type in supported.iter().filter(|port_type| **port_type != default) {
+        node.add("morph:supportsType", port_type.prefixed_name());
+    }
+}
+
+/// An input port whose type changes at runtime.
+///
+///     extern crate lv2rs_atom as atom;
+///     use atom::morph::*;
+///
+///     let mut port: MorphInputPort<f32> =
+///         MorphInputPort::new(PortType::Audio, &[PortType::CV, PortType::Atom]);
+///
+///     // Changing the type, usually on request of the host.
+///     assert!(port.morph(PortType::Atom));
+///     match port.port() {
+///         MorphedInput::Atom(_) => (),
+///         _ => panic!("The port should be an atom port now!"),
+///     }
+pub struct MorphInputPort<A: AtomBody + ?Sized> {
+    port: MorphedInput<A>,
+    default: PortType,
+    supported: Vec<PortType>,
+    automatic: bool,
+}
+
+impl<A: AtomBody + ?Sized> MorphInputPort<A> {
+    /// Create a new, unconnected port with the given default type and the types it supports
+    /// additionally.
+    pub fn new(default: PortType, supported: &[PortType]) -> Self {
+        let mut types = vec![default];
+        types.extend(supported.iter().filter(|port_type| **port_type != default));
+        Self {
+            port: MorphedInput::new(default),
+            default,
+            supported: types,
+            automatic: false,
+        }
+    }
+
+    /// Declare the port as a `morph:AutoMorphPort`, whose type is determined by the plugin.
+    pub fn with_auto_morph(mut self) -> Self {
+        self.automatic = true;
+        self
+    }
+
+    /// Return `true` if the port is a `morph:AutoMorphPort`.
+    pub fn is_auto_morph(&self) -> bool {
+        self.automatic
+    }
+
+    /// Return the current type of the port.
+    pub fn port_type(&self) -> PortType {
+        self.port.port_type()
+    }
+
+    /// Return `true` if the port may have the given type.
+    pub fn supports(&self, port_type: PortType) -> bool {
+        self.supported.contains(&port_type)
+    }
+
+    /// Change the type of the port.
+    ///
+    /// If the type is supported, the current port is replaced by an unconnected port of the new
+    /// type and `true` is returned. If the port already has the type, it's left untouched.
+    pub fn morph(&mut self, port_type: PortType) -> bool {
+        if !self.supports(port_type) {
+            return false;
+        }
+        if self.port_type() != port_type {
+            self.port = MorphedInput::new(port_type);
+        }
+        true
+    }
+
+    /// Set the internal data pointer of the current port.
+    ///
+    /// This function should only be called by a plugin's `connect_port` function.
+    pub fn connect(&mut self, data: *mut ()) {
+        match &mut self.port {
+            MorphedInput::Audio(port) | MorphedInput::CV(port) => port.connect(data as *const f32),
+            MorphedInput::Atom(port) => port.connect_port(data as *const crate::Atom),
+        }
+    }
+
+    /// Return the current port.
+    pub fn port(&self) -> &MorphedInput<A> {
+        &self.port
+    }
+
+    /// Add the type and the morph statements of the port to its description.
+    ///
+    /// The node should be created with the [`PortMetadata`](../../lv2rs_core/ports/struct.PortMetadata.html)
+    /// of the port and the `lv2:InputPort` class.
+    pub fn add_to_node(&self, node: &mut ttl::Node) {
+        add_morph_statements(node, self.automatic, self.default, &self.supported);
+    }
+}
+
+/// An output port whose type changes at runtime.
+///
+/// It works just like the [`MorphInputPort`](struct.MorphInputPort.html).
+pub struct MorphOutputPort<A: AtomBody + ?Sized> {
+    port: MorphedOutput<A>,
+    default: PortType,
+    supported: Vec<PortType>,
+    automatic: bool,
+}
+
+impl<A: AtomBody + ?Sized> MorphOutputPort<A> {
+    /// Create a new, unconnected port with the given default type and the types it supports
+    /// additionally.
+    pub fn new(default: PortType, supported: &[PortType]) -> Self {
+        let mut types = vec![default];
+        types.extend(supported.iter().filter(|port_type| **port_type != default));
+        Self {
+            port: MorphedOutput::new(default),
+            default,
+            supported: types,
+            automatic: false,
+        }
+    }
+
+    /// Declare the port as a `morph:AutoMorphPort`, whose type is determined by the plugin.
+    pub fn with_auto_morph(mut self) -> Self {
+        self.automatic = true;
+        self
+    }
+
+    /// Return `true` if the port is a `morph:AutoMorphPort`.
+    pub fn is_auto_morph(&self) -> bool {
+        self.automatic
+    }
+
+    /// Return the current type of the port.
+    pub fn port_type(&self) -> PortType {
+        self.port.port_type()
+    }
+
+    /// Return `true` if the port may have the given type.
+    pub fn supports(&self, port_type: PortType) -> bool {
+        self.supported.contains(&port_type)
+    }
+
+    /// Change the type of the port.
+    ///
+    /// If the type is supported, the current port is replaced by an unconnected port of the new
+    /// type and `true` is returned. If the port already has the type, it's left untouched.
+    pub fn morph(&mut self, port_type: PortType) -> bool {
+        if !self.supports(port_type) {
+            return false;
+        }
+        if self.port_type() != port_type {
+            self.port = MorphedOutput::new(port_type);
+        }
+        true
+    }
+
+    /// Set the internal data pointer of the current port.
+    ///
+    /// This function should only be called by a plugin's `connect_port` function.
+    pub fn connect(&mut self, data: *mut ()) {
+        match &mut self.port {
+            MorphedOutput::Audio(port) | MorphedOutput::CV(port) => port.connect(data as *mut f32),
+            MorphedOutput::Atom(port) => port.connect_port(data as *mut crate::Atom),
+        }
+    }
+
+    /// Return the current port.
+    pub fn port(&mut self) -> &mut MorphedOutput<A> {
+        &mut self.port
+    }
+
+    /// Add the type and the morph statements of the port to its description.
+    ///
+    /// The node should be created with the [`PortMetadata`](../../lv2rs_core/ports/struct.PortMetadata.html)
+    /// of the port and the `lv2:OutputPort` class.
+    pub fn add_to_node(&self, node: &mut ttl::Node) {
+        add_morph_statements(node, self.automatic, self.default, &self.supported);
+    }
+}
+
+/// A plugin with ports whose type changes at runtime.
+///
+/// The host calls these methods in the instantiation threading class, i.e. never while the plugin
+/// runs. After the type of a port has changed, the host connects it again.
+pub trait Morph: Plugin {
+    /// Return the URID map of the plugin, which is used to translate the types.
+    fn urids(&mut self) -> &mut CachedMap;
+
+    /// Change the type of a `morph:MorphPort`.
+    ///
+    /// Return `true` if the type was changed and `false` if the port doesn't support the type. If
+    /// the types of auto-morph ports depend on the port, they should be updated here too.
+    fn morph_port(&mut self, port: u32, port_type: PortType, options: &[OptionsOption]) -> bool;
+
+    /// Return the current type of a `morph:AutoMorphPort`.
+    ///
+    /// `None` should be returned if the port isn't an auto-morph port.
+    fn port_type(&mut self, port: u32, options: &[OptionsOption]) -> Option<PortType>;
+}
+
+/// Raw representation of the morph interface.
+///
+/// A plugin returns a static reference to this struct when the host asks for the
+/// [`INTERFACE_URI`](constant.INTERFACE_URI.html):
+///
+///     # extern crate lv2rs_atom as atom;
+///     # extern crate lv2rs_core as core;
+///     # extern crate lv2rs_urid as urid;
+///     # use atom::morph::*;
+///     # use std::ffi::CStr;
+///     # struct Router {
+///     #     urids: urid::CachedMap,
+///     #     input: MorphInputPort<f32>,
+///     #     output: MorphOutputPort<f32>,
+///     # }
+///     # impl core::Plugin for Router {
+///     #     fn instantiate(
+///     #         _: &core::Descriptor,
+///     #         _: f64,
+///     #         _: &CStr,
+///     #         _: Option<&core::FeaturesList>,
+///     #     ) -> Option<Self> {
+///     #         None
+///     #     }
+///     #     fn connect_port(&mut self, _port: u32, _data: *mut ()) {}
+///     #     fn run(&mut self, _n_samples: u32) {}
+///     fn extension_data(uri: &CStr) -> Option<&'static dyn core::ExtensionData> {
+///         static MORPH: MorphInterface = MorphInterface::new::<Router>();
+///         if uri.to_bytes_with_nul() == INTERFACE_URI {
+///             Some(&MORPH)
+///         } else {
+///             None
+///         }
+///     }
+///     # }
+///     # impl Morph for Router {
+///     #     fn urids(&mut self) -> &mut urid::CachedMap {
+///     #         &mut self.urids
+///     #     }
+///     #     fn morph_port(&mut self, _: u32, port_type: PortType, _: &[OptionsOption]) -> bool {
+///     #         self.input.morph(port_type) && self.output.morph(port_type)
+///     #     }
+///     #     fn port_type(&mut self, _: u32, _: &[OptionsOption]) -> Option<PortType> {
+///     #         Some(self.output.port_type())
+///     #     }
+///     # }
+#[repr(C)]
+pub struct MorphInterface {
+    /// Change the type of a port. Returns the URID of the new type or zero on failure.
+    pub morph_port: unsafe extern "C" fn(
+        instance: Handle,
+        port: u32,
+        port_type: URID,
+        options: *const OptionsOption,
+    ) -> URID,
+    /// Return the URID of the current type of an auto-morph port or zero.
+    pub port_type:
+        unsafe extern "C" fn(instance: Handle, port: u32, options: *const OptionsOption) -> URID,
+}
+
+impl MorphInterface {
+    /// Create the interface for the given plugin type.
+    pub const fn new<P: Morph>() -> Self {
+        Self {
+            morph_port: morph_port::<P>,
+            port_type: port_type::<P>,
+        }
+    }
+}
+
+impl ExtensionData for MorphInterface {}
+
+/// Create a slice from a null-terminated options array.
+unsafe fn options_slice<'a>(options: *const OptionsOption) -> &'a [OptionsOption] {
+    if options.is_null() {
+        return &[];
+    }
+    let mut length = 0;
+    loop {
+        let option = &*options.add(length);
+        if option.key == 0 && option.value.is_null() {
+            break;
+        }
+        length += 1;
+    }
+    std::slice::from_raw_parts(options, length)
+}
+
+unsafe extern "C" fn morph_port<P: Morph>(
+    instance: Handle,
+    port: u32,
+    port_type: URID,
+    options: *const OptionsOption,
+) -> URID {
+    let instance = (instance as *mut P).as_mut().unwrap();
+    let port_type = match PortType::from_urid(port_type, instance.urids()) {
+        Some(port_type) => port_type,
+        None => return 0,
+    };
+    if instance.morph_port(port, port_type, options_slice(options)) {
+        instance.urids().map(port_type.get_uri())
+    } else {
+        0
+    }
+}
+
+unsafe extern "C" fn port_type<P: Morph>(
+    instance: Handle,
+    port: u32,
+    options: *const OptionsOption,
+) -> URID {
+    let instance = (instance as *mut P).as_mut().unwrap();
+    match instance.port_type(port, options_slice(options)) {
+        Some(port_type) => instance.urids().map(port_type.get_uri()),
+        None => 0,
+    }
+}
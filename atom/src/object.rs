@@ -11,7 +11,16 @@
 //! writing frame.
 //!
 //! Reading an object is accomplished by creating an iterator over the properties with the
-//! [`iter`](type.Object.html#method.iter) method.
+//! [`iter`](type.Object.html#method.iter) method. Single properties are looked up by their key
+//! with the [`get`](type.Object.html#method.get) method and several properties at once with the
+//! [`query`](type.Object.html#method.query) method.
+//!
+//! Structs with a fixed set of properties may also derive the
+//! [`AtomObject`](trait.AtomObject.html) trait, which reads and writes all fields at once.
+//!
+//! Objects sent with the deprecated types `atom:Blank` and `atom:Resource` are read like any other
+//! object. The [`ObjectType`](enum.ObjectType.html) enum describes these types and can be used to
+//! write them for old hosts.
 //!
 //! An example:
 //!
@@ -103,10 +112,15 @@
 //!     plugin.run();
 use crate::atom::{array::*, *};
 use crate::frame::{NestedFrame, WritingFrame, WritingFrameExt};
+use crate::ports::WriteAtomError;
+use crate::scalar::ScalarAtomBody;
 use crate::uris;
+use crate::vector::{Vector, VectorWritingFrame};
 use std::ffi::CStr;
 use urid::URID;
 
+pub use lv2rs_atom_derive::AtomObject;
+
 /// The header of an object's property.
 ///
 /// In original LV2, a property is a standalone atom, but since it is only useful within objects,
@@ -157,6 +171,46 @@ impl ArrayAtomHeader for ObjectHeader {
     }
 }
 
+/// The atom type of an object.
+///
+/// Old versions of LV2 had two object types: `atom:Resource` for objects with an id and
+/// `atom:Blank` for objects without one. Both are deprecated in favour of `atom:Object`, but many
+/// hosts and plugins still use them. Objects of all three types are read as
+/// [`Object`s](type.Object.html) and the type of written objects can be changed with
+/// [`set_object_type`](trait.ObjectWritingFrame.html#method.set_object_type).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ObjectType {
+    /// `atom:Object`, the default type.
+    Object,
+    /// `atom:Blank`, the deprecated type for objects without an id.
+    Blank,
+    /// `atom:Resource`, the deprecated type for objects with an id.
+    Resource,
+}
+
+impl ObjectType {
+    /// Return the deprecated type for an object with the given id.
+    ///
+    /// Objects with an id of zero are blank, all others are resources.
+    pub fn legacy(id: URID) -> Self {
+        if id == 0 {
+            ObjectType::Blank
+        } else {
+            ObjectType::Resource
+        }
+    }
+
+    /// Return the URI of the type.
+    pub fn get_uri(self) -> &'static CStr {
+        let uri = match self {
+            ObjectType::Object => uris::OBJECT_TYPE_URI,
+            ObjectType::Blank => uris::BLANK_TYPE_URI,
+            ObjectType::Resource => uris::RESOURCE_TYPE_URI,
+        };
+        unsafe { CStr::from_bytes_with_nul_unchecked(uri) }
+    }
+}
+
 /// Object- or Map-style atom container.
 ///
 /// See the [module documentation](index.html) for more information.
@@ -166,7 +220,13 @@ impl AtomBody for Object {
     type InitializationParameter = (URID, URID);
 
     fn get_uri() -> &'static CStr {
-        unsafe { CStr::from_bytes_with_nul_unchecked(uris::OBJECT_TYPE_URI) }
+        ObjectType::Object.get_uri()
+    }
+
+    fn accepts_type(atom_type: URID, urids: &mut urid::CachedMap) -> bool {
+        [ObjectType::Object, ObjectType::Blank, ObjectType::Resource]
+            .iter()
+            .any(|object_type| atom_type == urids.map(object_type.get_uri()))
     }
 
     unsafe fn initialize_body<'a, W>(
@@ -185,6 +245,75 @@ impl AtomBody for Object {
     }
 }
 
+/// Errors that may occur when looking up properties of an object.
+#[derive(Debug, PartialEq)]
+pub enum GetPropertyError {
+    /// The object has no property with the given key.
+    MissingProperty(URID),
+    /// The body of the property with the given key has another type.
+    WrongURID(URID),
+    /// The body of the property with the given key is malformed.
+    MalformedAtom(URID),
+}
+
+impl GetPropertyError {
+    fn from_body_error(key: URID, error: GetBodyError) -> Self {
+        match error {
+            GetBodyError::WrongURID => GetPropertyError::WrongURID(key),
+            GetBodyError::MalformedAtom => GetPropertyError::MalformedAtom(key),
+        }
+    }
+}
+
+/// A slot for a property that is filled by [`Object::query`](type.Object.html#method.query).
+pub trait QuerySlot<'a> {
+    /// Return the key of the property.
+    fn key(&self) -> URID;
+
+    /// Return `true` if the slot has already been filled.
+    fn is_filled(&self) -> bool;
+
+    /// Fill the slot with the body of the property's atom.
+    fn fill(&mut self, atom: &'a Atom, urids: &mut urid::CachedMap) -> Result<(), GetBodyError>;
+}
+
+/// A typed slot for the body of a property.
+///
+/// The slot is created with the key of the property, passed to
+/// [`Object::query`](type.Object.html#method.query) and contains the body of the property
+/// afterwards.
+pub struct PropertySlot<'a, A: AtomBody + ?Sized> {
+    key: URID,
+    body: Option<&'a A>,
+}
+
+impl<'a, A: AtomBody + ?Sized> PropertySlot<'a, A> {
+    /// Create an empty slot for the property with the given key.
+    pub fn new(key: URID) -> Self {
+        Self { key, body: None }
+    }
+
+    /// Return the body of the property, if the slot was filled.
+    pub fn get(&self) -> Option<&'a A> {
+        self.body
+    }
+}
+
+impl<'a, A: AtomBody + ?Sized> QuerySlot<'a> for PropertySlot<'a, A> {
+    fn key(&self) -> URID {
+        self.key
+    }
+
+    fn is_filled(&self) -> bool {
+        self.body.is_some()
+    }
+
+    fn fill(&mut self, atom: &'a Atom, urids: &mut urid::CachedMap) -> Result<(), GetBodyError> {
+        self.body = Some(atom.get_body(urids)?);
+        Ok(())
+    }
+}
+
 impl Object {
     /// Create an iterator over all properties of the object.
     ///
@@ -192,6 +321,66 @@ impl Object {
     pub fn iter<'a>(&'a self) -> impl Iterator<Item = (&'a PropertyHeader, &'a Atom)> {
         AtomIterator::<PropertyHeader>::new(&self.data)
     }
+
+    /// Return the body of the property with the given key.
+    ///
+    /// If the object contains the key more than once, the first property is used.
+    pub fn get<A: AtomBody + ?Sized>(
+        &self,
+        key: URID,
+        urids: &mut urid::CachedMap,
+    ) -> Result<&A, GetPropertyError> {
+        let (_, atom) = self
+            .iter()
+            .find(|(header, _)| header.key == key)
+            .ok_or(GetPropertyError::MissingProperty(key))?;
+        atom.get_body(urids)
+            .map_err(|error| GetPropertyError::from_body_error(key, error))
+    }
+
+    /// Fill the slots with the bodies of their properties, in one pass over the object.
+    ///
+    /// If the object contains a key more than once, the first property is used. An error is
+    /// returned if a property is missing or has the wrong type; In this case, some slots may
+    /// already be filled.
+    ///
+    ///     extern crate lv2rs_atom as atom;
+    ///     extern crate lv2rs_urid as urid;
+    ///
+    ///     use atom::object::PropertySlot;
+    ///     use atom::prelude::*;
+    ///     use std::ffi::CStr;
+    ///
+    ///     /// Read the note and velocity properties of an object.
+    ///     fn read_note(object: &Object, urids: &mut urid::CachedMap) -> Option<(i32, f32)> {
+    ///         let note = urids.map(CStr::from_bytes_with_nul(b"https://example.org#note\0").unwrap());
+    ///         let velocity =
+    ///             urids.map(CStr::from_bytes_with_nul(b"https://example.org#velocity\0").unwrap());
+    ///
+    ///         let mut note = PropertySlot::<i32>::new(note);
+    ///         let mut velocity = PropertySlot::<f32>::new(velocity);
+    ///         object.query(&mut [&mut note, &mut velocity], urids).ok()?;
+    ///         Some((*note.get()?, *velocity.get()?))
+    ///     }
+    pub fn query<'a>(
+        &'a self,
+        slots: &mut [&mut dyn QuerySlot<'a>],
+        urids: &mut urid::CachedMap,
+    ) -> Result<(), GetPropertyError> {
+        for (header, atom) in self.iter() {
+            for slot in slots
+                .iter_mut()
+                .filter(|slot| slot.key() == header.key && !slot.is_filled())
+            {
+                slot.fill(atom, urids)
+                    .map_err(|error| GetPropertyError::from_body_error(header.key, error))?;
+            }
+        }
+        match slots.iter().find(|slot| !slot.is_filled()) {
+            Some(slot) => Err(GetPropertyError::MissingProperty(slot.key())),
+            None => Ok(()),
+        }
+    }
 }
 
 /// Extension for [`WritingFrame`](../frame/trait.WritingFrame.html) and
@@ -222,6 +411,265 @@ pub trait ObjectWritingFrame<'a>: WritingFrame<'a> + WritingFrameExt<'a, Object>
             Ok(frame)
         }
     }
+
+    /// Change the atom type of the object.
+    ///
+    /// Objects are written as `atom:Object`s by default, but old hosts may only understand the
+    /// deprecated types. For them, the type returned by
+    /// [`ObjectType::legacy`](enum.ObjectType.html#method.legacy) should be used.
+    ///
+    /// If the frame doesn't grant access to its atom header, the type isn't changed and
+    /// `WriteAtomError::HeaderUnavailable` is returned. This never happens with the frames of this
+    /// crate.
+    fn set_object_type(
+        &mut self,
+        object_type: ObjectType,
+        urids: &mut urid::CachedMap,
+    ) -> Result<(), WriteAtomError> {
+        let atom_type = urids.map(object_type.get_uri());
+        let atom = unsafe { self.get_atom_mut() }.ok_or(WriteAtomError::HeaderUnavailable)?;
+        *(atom.mut_atom_type()) = atom_type;
+        Ok(())
+    }
 }
 
 impl<'a, W> ObjectWritingFrame<'a> for W where W: WritingFrame<'a> + WritingFrameExt<'a, Object> {}
+
+/// Errors that may occur when writing properties to an object.
+#[derive(Debug, PartialEq)]
+pub enum WritePropertyError {
+    /// The property with the given key does not fit into the atom space.
+    InsufficientSpace(URID),
+}
+
+/// A value that can be stored in a property of an object.
+///
+/// This trait is implemented for scalars, for `Vec`s of scalars, which are stored as
+/// [`Vector`s](../vector/type.Vector.html), and for `Option`s of values, which are stored as
+/// optional properties. Types deriving [`AtomObject`](trait.AtomObject.html) also implement it and
+/// are stored as nested objects.
+pub trait PropertyValue: Sized {
+    /// Read the value from the atom of the property with the given key.
+    fn read_property(
+        key: URID,
+        atom: &Atom,
+        urids: &mut urid::CachedMap,
+    ) -> Result<Self, GetPropertyError>;
+
+    /// Return the value of a property that is missing in the object.
+    ///
+    /// By default, a missing property is an error.
+    fn read_missing(key: URID) -> Result<Self, GetPropertyError> {
+        Err(GetPropertyError::MissingProperty(key))
+    }
+
+    /// Write the value as a property with the given key.
+    fn write_property<'a, W>(
+        &self,
+        frame: &mut W,
+        key: URID,
+        urids: &mut urid::CachedMap,
+    ) -> Result<(), WritePropertyError>
+    where
+        W: WritingFrame<'a> + WritingFrameExt<'a, Object>;
+}
+
+impl<T> PropertyValue for T
+where
+    T: 'static + ScalarAtomBody + Copy,
+{
+    fn read_property(
+        key: URID,
+        atom: &Atom,
+        urids: &mut urid::CachedMap,
+    ) -> Result<Self, GetPropertyError> {
+        atom.get_body::<T>(urids)
+            .copied()
+            .map_err(|error| GetPropertyError::from_body_error(key, error))
+    }
+
+    fn write_property<'a, W>(
+        &self,
+        frame: &mut W,
+        key: URID,
+        urids: &mut urid::CachedMap,
+    ) -> Result<(), WritePropertyError>
+    where
+        W: WritingFrame<'a> + WritingFrameExt<'a, Object>,
+    {
+        frame
+            .push_property::<T>(key, 0, self, urids)
+            .map(|_| ())
+            .map_err(|_| WritePropertyError::InsufficientSpace(key))
+    }
+}
+
+impl<T> PropertyValue for Vec<T>
+where
+    T: 'static + ScalarAtomBody + Copy,
+{
+    fn read_property(
+        key: URID,
+        atom: &Atom,
+        urids: &mut urid::CachedMap,
+    ) -> Result<Self, GetPropertyError> {
+        atom.get_body::<Vector<T>>(urids)
+            .map(|vector| vector.as_slice().to_vec())
+            .map_err(|error| GetPropertyError::from_body_error(key, error))
+    }
+
+    fn write_property<'a, W>(
+        &self,
+        frame: &mut W,
+        key: URID,
+        urids: &mut urid::CachedMap,
+    ) -> Result<(), WritePropertyError>
+    where
+        W: WritingFrame<'a> + WritingFrameExt<'a, Object>,
+    {
+        let mut frame = frame
+            .push_property::<Vector<T>>(key, 0, &(), urids)
+            .map_err(|_| WritePropertyError::InsufficientSpace(key))?;
+        frame
+            .append(self.as_slice())
+            .map_err(|_| WritePropertyError::InsufficientSpace(key))
+    }
+}
+
+impl<T: PropertyValue> PropertyValue for Option<T> {
+    fn read_property(
+        key: URID,
+        atom: &Atom,
+        urids: &mut urid::CachedMap,
+    ) -> Result<Self, GetPropertyError> {
+        T::read_property(key, atom, urids).map(Some)
+    }
+
+    fn read_missing(_key: URID) -> Result<Self, GetPropertyError> {
+        Ok(None)
+    }
+
+    fn write_property<'a, W>(
+        &self,
+        frame: &mut W,
+        key: URID,
+        urids: &mut urid::CachedMap,
+    ) -> Result<(), WritePropertyError>
+    where
+        W: WritingFrame<'a> + WritingFrameExt<'a, Object>,
+    {
+        match self {
+            Some(value) => value.write_property(frame, key, urids),
+            None => Ok(()),
+        }
+    }
+}
+
+/// A struct that is stored as an object, with one property per field.
+///
+/// This trait should be derived: Every field of the struct is annotated with the URI of its
+/// property and its type has to implement [`PropertyValue`](trait.PropertyValue.html). The struct
+/// itself is annotated with the URI of its class, which is used as the `otype` of nested objects:
+///
+///     extern crate lv2rs_atom as atom;
+///     extern crate lv2rs_urid as urid;
+///
+///     use atom::object::AtomObject;
+///     use atom::prelude::*;
+///     use atom::ports::*;
+///     use urid::debug::DebugMap;
+///
+///     #[derive(AtomObject, Debug, PartialEq)]
+///     #[atom_object(otype = "https://example.org#Envelope")]
+///     struct Envelope {
+///         #[property(key = "https://example.org#attack")]
+///         attack: f32,
+///         #[property(key = "https://example.org#release")]
+///         release: f32,
+///         #[property(key = "https://example.org#curve")]
+///         curve: Vec<f32>,
+///         #[property(key = "https://example.org#loop")]
+///         loop_point: Option<i32>,
+///     }
+///
+///     let mut debug_map = DebugMap::new();
+///     let mut urids = unsafe { debug_map.create_cached_map() };
+///
+///     let mut atom_space = vec![0u8; 256];
+///     let atom = unsafe { (atom_space.as_mut_ptr() as *mut Atom).as_mut() }.unwrap();
+///     *(atom.mut_size()) = 256 - 8;
+///
+///     let envelope = Envelope {
+///         attack: 0.1,
+///         release: 0.5,
+///         curve: vec![0.0, 0.8, 1.0],
+///         loop_point: None,
+///     };
+///
+///     // Writing the envelope.
+///     let mut out_port = AtomOutputPort::<Object>::new();
+///     out_port.connect_port(atom);
+///     let otype = urids.map(Envelope::object_type());
+///     let mut frame = unsafe { out_port.write_atom_body(&(0, otype), &mut urids) }.unwrap();
+///     envelope.write_properties(&mut frame, &mut urids).unwrap();
+///     drop(frame);
+///
+///     // Reading it again.
+///     let mut in_port = AtomInputPort::<Object>::new();
+///     in_port.connect_port(atom);
+///     let object = unsafe { in_port.get_atom_body(&mut urids) }.unwrap();
+///     assert_eq!(envelope, Envelope::read_object(object, &mut urids).unwrap());
+pub trait AtomObject: Sized {
+    /// Return the URI of the class of the object.
+    fn object_type() -> &'static CStr;
+
+    /// Read the struct from the properties of the object.
+    ///
+    /// If the object contains a key more than once, the first property is used. Properties with
+    /// unknown keys are ignored.
+    fn read_object(object: &Object, urids: &mut urid::CachedMap) -> Result<Self, GetPropertyError>;
+
+    /// Write the fields of the struct as properties to the object frame.
+    fn write_properties<'a, W>(
+        &self,
+        frame: &mut W,
+        urids: &mut urid::CachedMap,
+    ) -> Result<(), WritePropertyError>
+    where
+        W: WritingFrame<'a> + WritingFrameExt<'a, Object>;
+
+    /// Read the struct from the nested object of the property with the given key.
+    ///
+    /// This method is used by the derived implementation of
+    /// [`PropertyValue`](trait.PropertyValue.html).
+    fn read_nested(
+        key: URID,
+        atom: &Atom,
+        urids: &mut urid::CachedMap,
+    ) -> Result<Self, GetPropertyError> {
+        let object: &Object = atom
+            .get_body(urids)
+            .map_err(|error| GetPropertyError::from_body_error(key, error))?;
+        Self::read_object(object, urids)
+    }
+
+    /// Write the struct as a nested object to the property with the given key.
+    ///
+    /// This method is used by the derived implementation of
+    /// [`PropertyValue`](trait.PropertyValue.html).
+    fn write_nested<'a, W>(
+        &self,
+        frame: &mut W,
+        key: URID,
+        urids: &mut urid::CachedMap,
+    ) -> Result<(), WritePropertyError>
+    where
+        W: WritingFrame<'a> + WritingFrameExt<'a, Object>,
+    {
+        let otype = urids.map(Self::object_type());
+        let mut frame = frame
+            .push_property::<Object>(key, 0, &(0, otype), urids)
+            .map_err(|_| WritePropertyError::InsufficientSpace(key))?;
+        self.write_properties(&mut frame, urids)
+    }
+}
@@ -2,6 +2,7 @@
 //!
 //! The wrappers provided by this module increase the safety and usability of atom IO.
 use crate::atom::*;
+use crate::chunk::Chunk;
 use crate::frame::RootFrame;
 use std::marker::PhantomData;
 use std::ptr::{null, null_mut};
@@ -22,6 +23,10 @@ pub enum WriteAtomError {
     NullPointer,
     /// The host hasn't allocated enough memory to initialize the atom.
     InsufficientSpace,
+    /// The writing frame doesn't grant access to its atom header, which would have to be changed.
+    ///
+    /// See [`WritingFrame::get_atom_mut`](../frame/trait.WritingFrame.html#method.get_atom_mut).
+    HeaderUnavailable,
 }
 
 #[derive(Debug)]
@@ -61,6 +66,10 @@ impl<A: AtomBody + ?Sized> AtomOutputPort<A> {
     /// body. For [scalar atoms](../scalar/index.html), this is all you can and need to
     /// do. For all other atoms, you can write additional data using the `RootFrame`.
     ///
+    /// If the host passed an empty [chunk](../chunk/index.html), its size is the capacity of the
+    /// written atom's body. Otherwise, the size of the passed atom is the capacity of the whole
+    /// atom, including its header.
+    ///
     /// This method is unsafe since it dereferences the raw, internal pointer and therefore could
     /// yield undefined behaviour. Make sure that your plugin's `connect_port` method calls this
     /// port's [`connect_port`](#method.connect_port) method correctly!
@@ -73,7 +82,13 @@ impl<A: AtomBody + ?Sized> AtomOutputPort<A> {
             Some(header) => header,
             None => return Err(WriteAtomError::NullPointer),
         };
-        let data = std::slice::from_raw_parts_mut(self.atom as *mut u8, header.size() as usize);
+        // Hosts describe the free space as an empty chunk, whose size is the capacity of the body.
+        let size = if header.atom_type() == urids.map(Chunk::get_uri()) {
+            header.size() + std::mem::size_of::<Atom>()
+        } else {
+            header.size()
+        };
+        let data = std::slice::from_raw_parts_mut(self.atom as *mut u8, size);
         let mut frame =
             RootFrame::new(data, urids).map_err(|_| WriteAtomError::InsufficientSpace)?;
         A::initialize_body(&mut frame, parameter, urids)
@@ -0,0 +1,193 @@
+//! Audio snippets.
+//!
+//! A [sound](type.Sound.html) is a vector of `f32` samples that is tagged as audio. It can be used
+//! to send audio through atom ports, for example to show the waveform of the input in a UI.
+//!
+//! When initialized, a sound does not contain any samples. They have to be appended using the
+//! [`SoundWritingFrame`](trait.SoundWritingFrame.html) trait, either from a slice or directly from
+//! an [`AudioInputPort`](../../lv2rs_core/ports/struct.AudioInputPort.html). Every writing frame
+//! implements this trait via a blanket implementation and the trait is included in the crate's
+//! prelude. You can, therefore, act as if the extended methods were normal methods of a writing
+//! frame.
+//!
+//! An example:
+//!
+//!     extern crate lv2rs_atom as atom;
+//!     extern crate lv2rs_core as core;
+//!     extern crate lv2rs_urid as urid;
+//!
+//!     use atom::prelude::*;
+//!     use atom::ports::*;
+//!     use core::ports::AudioInputPort;
+//!     use urid::{CachedMap, debug::DebugMap};
+//!
+//!     pub struct Plugin {
+//!         audio_in: AudioInputPort,
+//!         waveform_out: AtomOutputPort<Sound>,
+//!         waveform_in: AtomInputPort<Sound>,
+//!         urids: CachedMap,
+//!     }
+//!
+//!     impl Plugin {
+//!         /// Simulated `run` method.
+//!         fn run(&mut self, n_samples: u32) {
+//!             // Writing
+//!             {
+//!                 let mut frame =
+//!                     unsafe { self.waveform_out.write_atom_body(&(), &mut self.urids) }.unwrap();
+//!                 unsafe { frame.append_port(&self.audio_in, n_samples) }.unwrap();
+//!             }
+//!
+//!             // Reading.
+//!             let sound = unsafe { self.waveform_in.get_atom_body(&mut self.urids) }.unwrap();
+//!             assert_eq!(&[0.0, 0.25, 0.5, 0.75], sound.as_slice());
+//!         }
+//!     }
+//!
+//!     // Getting a debug URID map.
+//!     let mut debug_map = DebugMap::new();
+//!     let mut urids = unsafe {debug_map.create_cached_map()};
+//!
+//!     // Creating the plugin.
+//!     let mut plugin = Plugin {
+//!         audio_in: AudioInputPort::new(),
+//!         waveform_out: AtomOutputPort::new(),
+//!         waveform_in: AtomInputPort::new(),
+//!         urids: urids,
+//!     };
+//!
+//!     // Creating the audio buffer and the atom space.
+//!     let audio = [0.0, 0.25, 0.5, 0.75];
+//!     let mut atom_space = vec![0u8; 256];
+//!     let atom = unsafe { (atom_space.as_mut_ptr() as *mut Atom).as_mut() }.unwrap();
+//!     *(atom.mut_size()) = 256 - 8;
+//!
+//!     // Connecting the ports.
+//!     plugin.audio_in.connect(audio.as_ptr());
+//!     plugin.waveform_in.connect_port(atom as &Atom);
+//!     plugin.waveform_out.connect_port(atom);
+//!
+//!     // Calling `run`.
+//!     plugin.run(4);
+use crate::atom::{array::*, *};
+use crate::frame::{WritingFrame, WritingFrameExt};
+use crate::ports::WriteAtomError;
+use crate::uris;
+use core::ports::AudioInputPort;
+use std::ffi::CStr;
+use std::mem::size_of;
+use std::os::raw::c_uint;
+use urid::URID;
+
+/// The body header of a sound.
+///
+/// Sounds are vectors and therefore, this header has the same layout as the
+/// [`VectorHeader`](../vector/struct.VectorHeader.html): It contains the size and the type of the
+/// samples, which are always `atom:Float`s. It is also `repr(C)` and is used to interpret raw
+/// atoms.
+#[repr(C)]
+pub struct SoundHeader {
+    pub child_size: c_uint,
+    pub child_type: c_uint,
+}
+
+/// An audio snippet.
+///
+/// See the [module documentation](index.html) for more information.
+pub type Sound = ArrayAtomBody<SoundHeader, f32>;
+
+impl ArrayAtomHeader for SoundHeader {
+    type InitializationParameter = URID;
+
+    unsafe fn initialize<'a, W, T>(
+        writer: &mut W,
+        child_type: &URID,
+        _urids: &mut urid::CachedMap,
+    ) -> Result<(), ()>
+    where
+        T: 'static + Sized + Copy,
+        ArrayAtomBody<Self, T>: AtomBody,
+        W: WritingFrame<'a> + WritingFrameExt<'a, ArrayAtomBody<Self, T>>,
+    {
+        let header = SoundHeader {
+            child_size: size_of::<T>() as u32,
+            child_type: *child_type,
+        };
+        writer.write_sized(&header)?;
+        Ok(())
+    }
+}
+
+impl AtomBody for Sound {
+    type InitializationParameter = ();
+
+    fn get_uri() -> &'static CStr {
+        unsafe { CStr::from_bytes_with_nul_unchecked(uris::SOUND_TYPE_URI) }
+    }
+
+    unsafe fn initialize_body<'a, W>(
+        writer: &mut W,
+        _: &(),
+        urids: &mut urid::CachedMap,
+    ) -> Result<(), ()>
+    where
+        W: WritingFrame<'a> + WritingFrameExt<'a, Self>,
+    {
+        Self::__initialize_body(writer, &urids.map(<f32 as AtomBody>::get_uri()), urids)
+    }
+
+    fn create_ref(raw_data: &[u8]) -> Result<&Self, ()> {
+        let sound = Self::__create_ref(raw_data)?;
+        if sound.header.child_size as usize != size_of::<f32>() {
+            return Err(());
+        }
+        Ok(sound)
+    }
+}
+
+impl Sound {
+    /// Return a slice containing all samples of the sound.
+    ///
+    /// No allocation is done; This method simply borrows the data of the sound.
+    pub fn as_slice(&self) -> &[f32] {
+        &self.data
+    }
+}
+
+/// Extension for [`WritingFrame`](../frame/trait.WritingFrame.html) and
+/// [`WritingFrameExt`](../frame/trait.WritingFrameExt.html) for sounds.
+///
+/// See the [module documentation](index.html) for more information.
+pub trait SoundWritingFrame<'a>: WritingFrame<'a> + WritingFrameExt<'a, Sound> {
+    /// Append a slice of samples to the end of the sound.
+    ///
+    /// If the samples don't fit into the remaining space, nothing is written and an error is
+    /// returned.
+    fn append(&mut self, samples: &[f32]) -> Result<(), WriteAtomError> {
+        unsafe { Sound::append(self, samples) }.map_err(|_| WriteAtomError::InsufficientSpace)
+    }
+
+    /// Append the samples of an audio input port to the end of the sound.
+    ///
+    /// The samples are copied directly from the buffer of the port into the atom space. If the
+    /// port is not connected, `WriteAtomError::NullPointer` is returned.
+    ///
+    /// # Safety
+    ///
+    /// This method is unsafe for the same reasons as
+    /// [`AudioInputPort::as_slice`](../../lv2rs_core/ports/struct.AudioInputPort.html#method.as_slice):
+    /// Only a plugin's `run` function should use it and must pass the sample count it received
+    /// from the host.
+    unsafe fn append_port(
+        &mut self,
+        port: &AudioInputPort,
+        n_samples: u32,
+    ) -> Result<(), WriteAtomError> {
+        let samples = port
+            .as_slice(n_samples)
+            .ok_or(WriteAtomError::NullPointer)?;
+        self.append(samples)
+    }
+}
+
+impl<'a, W> SoundWritingFrame<'a> for W where W: WritingFrame<'a> + WritingFrameExt<'a, Sound> {}
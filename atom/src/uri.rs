@@ -0,0 +1,232 @@
+//! URIs and paths.
+//!
+//! This module contains the [`AtomURI`](type.AtomURI.html) and the
+//! [`AtomPath`](type.AtomPath.html), two null-terminated, UTF-8 encoded strings. URIs name
+//! resources, for example in `patch:Get` messages, and paths point to files, like samples. Paths
+//! are especially important for the host, since it has to map them when saving or restoring the
+//! state of a plugin.
+//!
+//! Like atom strings, URIs and paths can only be written once: The `write_atom_body` call expects
+//! a `str` or a `Path` from which it can copy the data and after that call, the atom can't be
+//! modified.
+//!
+//! An example:
+//!
+//!     extern crate lv2rs_atom as atom;
+//!     extern crate lv2rs_urid as urid;
+//!
+//!     use atom::prelude::*;
+//!     use atom::ports::*;
+//!     use urid::{CachedMap, debug::DebugMap};
+//!     use std::path::Path;
+//!
+//!     pub struct Plugin {
+//!         in_port: AtomInputPort<AtomPath>,
+//!         out_port: AtomOutputPort<AtomPath>,
+//!         urids: CachedMap,
+//!     }
+//!
+//!     impl Plugin {
+//!         /// Simulated `run` method.
+//!         fn run(&mut self) {
+//!             let sample = Path::new("/usr/share/samples/kick.wav");
+//!
+//!             // Writing.
+//!             unsafe { self.out_port.write_atom_body(sample, &mut self.urids).unwrap() };
+//!
+//!             // Reading.
+//!             let path = unsafe { self.in_port.get_atom_body(&mut self.urids) }.unwrap();
+//!             assert_eq!(sample, path.as_path().unwrap());
+//!         }
+//!     }
+//!
+//!     // Getting a debug URID map.
+//!     let mut debug_map = DebugMap::new();
+//!     let mut urids = unsafe {debug_map.create_cached_map()};
+//!
+//!     // Creating the plugin.
+//!     let mut plugin = Plugin {
+//!         in_port: AtomInputPort::new(),
+//!         out_port: AtomOutputPort::new(),
+//!         urids: urids,
+//!     };
+//!
+//!     // Creating the atom space.
+//!     let mut atom_space = vec![0u8; 256];
+//!     let atom = unsafe { (atom_space.as_mut_ptr() as *mut Atom).as_mut() }.unwrap();
+//!     *(atom.mut_size()) = 256 - 8;
+//!
+//!     // Connecting the ports.
+//!     plugin.in_port.connect_port(atom as &Atom);
+//!     plugin.out_port.connect_port(atom);
+//!
+//!     // Calling `run`.
+//!     plugin.run();
+use crate::atom::{array::*, *};
+use crate::frame::{WritingFrame, WritingFrameExt};
+use crate::uris;
+use std::ffi::{CStr, FromBytesWithNulError};
+use std::path::Path;
+use std::str::Utf8Error;
+
+/// Header of a URI.
+///
+/// URIs have no header, but since their raw representation is the same as the one of other
+/// atoms, this empty struct is used to tell them apart.
+#[repr(C)]
+pub struct URIHeader;
+
+/// Header of a path.
+///
+/// Paths have no header, but since their raw representation is the same as the one of other
+/// atoms, this empty struct is used to tell them apart.
+#[repr(C)]
+pub struct PathHeader;
+
+impl ArrayAtomHeader for URIHeader {
+    type InitializationParameter = ();
+
+    unsafe fn initialize<'a, W, T>(_: &mut W, _: &(), _: &mut urid::CachedMap) -> Result<(), ()>
+    where
+        T: 'static + Sized + Copy,
+        ArrayAtomBody<Self, T>: AtomBody,
+        W: WritingFrame<'a> + WritingFrameExt<'a, ArrayAtomBody<Self, T>>,
+    {
+        Ok(())
+    }
+}
+
+impl ArrayAtomHeader for PathHeader {
+    type InitializationParameter = ();
+
+    unsafe fn initialize<'a, W, T>(_: &mut W, _: &(), _: &mut urid::CachedMap) -> Result<(), ()>
+    where
+        T: 'static + Sized + Copy,
+        ArrayAtomBody<Self, T>: AtomBody,
+        W: WritingFrame<'a> + WritingFrameExt<'a, ArrayAtomBody<Self, T>>,
+    {
+        Ok(())
+    }
+}
+
+/// A URI, encoded as a null-terminated UTF-8 string.
+///
+/// See the [module documentation](index.html) for more information.
+pub type AtomURI = ArrayAtomBody<URIHeader, u8>;
+
+/// A file path, encoded as a null-terminated UTF-8 string.
+///
+/// See the [module documentation](index.html) for more information.
+pub type AtomPath = ArrayAtomBody<PathHeader, u8>;
+
+/// Write a string and its null terminator.
+///
+/// Strings containing null bytes can't be written and an `Err` is returned.
+unsafe fn write_str<'a, W: WritingFrame<'a>>(writer: &mut W, string: &str) -> Result<(), ()> {
+    if string.as_bytes().contains(&0) {
+        return Err(());
+    }
+    writer.write_raw(string.as_bytes())?;
+    writer.write_raw(&[0])?;
+    Ok(())
+}
+
+/// Parse the data of a URI or a path, without its null terminator.
+fn read_str(data: &[u8]) -> Result<&str, Utf8Error> {
+    let data = match data.split_last() {
+        Some((0, data)) => data,
+        _ => data,
+    };
+    std::str::from_utf8(data)
+}
+
+impl AtomBody for AtomURI {
+    type InitializationParameter = str;
+
+    fn get_uri() -> &'static CStr {
+        unsafe { CStr::from_bytes_with_nul_unchecked(uris::URI_TYPE_URI) }
+    }
+
+    unsafe fn initialize_body<'a, W>(
+        writer: &mut W,
+        uri: &str,
+        urids: &mut urid::CachedMap,
+    ) -> Result<(), ()>
+    where
+        W: WritingFrame<'a> + WritingFrameExt<'a, Self>,
+    {
+        Self::__initialize_body(writer, &(), urids)?;
+        write_str(writer, uri)
+    }
+
+    fn create_ref(raw_data: &[u8]) -> Result<&Self, ()> {
+        Self::__create_ref(raw_data)
+    }
+}
+
+impl AtomURI {
+    /// Try to parse the URI as a `&str`.
+    ///
+    /// Parsing errors are forwarded.
+    pub fn as_str(&self) -> Result<&str, Utf8Error> {
+        read_str(&self.data)
+    }
+
+    /// Try to wrap the URI into a `CStr` reference.
+    ///
+    /// This function returns an error if the internal conversion fails.
+    pub fn as_cstr(&self) -> Result<&CStr, FromBytesWithNulError> {
+        CStr::from_bytes_with_nul(&self.data)
+    }
+}
+
+impl AtomBody for AtomPath {
+    type InitializationParameter = Path;
+
+    fn get_uri() -> &'static CStr {
+        unsafe { CStr::from_bytes_with_nul_unchecked(uris::PATH_TYPE_URI) }
+    }
+
+    /// Write the path.
+    ///
+    /// Paths that are not valid UTF-8 can't be written and an `Err` is returned.
+    unsafe fn initialize_body<'a, W>(
+        writer: &mut W,
+        path: &Path,
+        urids: &mut urid::CachedMap,
+    ) -> Result<(), ()>
+    where
+        W: WritingFrame<'a> + WritingFrameExt<'a, Self>,
+    {
+        let path = path.to_str().ok_or(())?;
+        Self::__initialize_body(writer, &(), urids)?;
+        write_str(writer, path)
+    }
+
+    fn create_ref(raw_data: &[u8]) -> Result<&Self, ()> {
+        Self::__create_ref(raw_data)
+    }
+}
+
+impl AtomPath {
+    /// Try to parse the path as a `&str`.
+    ///
+    /// Parsing errors are forwarded.
+    pub fn as_str(&self) -> Result<&str, Utf8Error> {
+        read_str(&self.data)
+    }
+
+    /// Try to parse the path as a `&Path`.
+    ///
+    /// Parsing errors are forwarded.
+    pub fn as_path(&self) -> Result<&Path, Utf8Error> {
+        self.as_str().map(Path::new)
+    }
+
+    /// Try to wrap the path into a `CStr` reference.
+    ///
+    /// This function returns an error if the internal conversion fails.
+    pub fn as_cstr(&self) -> Result<&CStr, FromBytesWithNulError> {
+        CStr::from_bytes_with_nul(&self.data)
+    }
+}
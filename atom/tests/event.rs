@@ -0,0 +1,114 @@
+extern crate lv2rs_atom as atom;
+extern crate lv2rs_urid as urid;
+
+use atom::event::*;
+use atom::sequence::TimeStamp;
+use std::ffi::CStr;
+use std::mem::size_of;
+use std::os::raw::*;
+use urid::debug::DebugMap;
+use urid::uri_map::UriMap;
+
+const MIDI_EVENT_URI: &[u8] = b"http://lv2plug.in/ns/ext/midi#MidiEvent\0";
+
+/// A uri-map implementation of a host that only knows MIDI events.
+extern "C" fn uri_to_id(_data: *mut c_void, map: *const c_char, uri: *const c_char) -> u32 {
+    let map = unsafe { CStr::from_ptr(map) };
+    let uri = unsafe { CStr::from_ptr(uri) };
+    if map.to_bytes_with_nul() == URI && uri.to_bytes_with_nul() == MIDI_EVENT_URI {
+        3
+    } else {
+        0
+    }
+}
+
+/// Write an event to the raw event memory, the way a host does.
+fn write_event(data: &mut Vec<u8>, frames: u32, event_type: u16, body: &[u8]) {
+    data.extend_from_slice(&frames.to_ne_bytes());
+    data.extend_from_slice(&0u32.to_ne_bytes());
+    data.extend_from_slice(&event_type.to_ne_bytes());
+    data.extend_from_slice(&(body.len() as u16).to_ne_bytes());
+    data.extend_from_slice(body);
+    while data.len() % 8 != 0 {
+        data.push(0);
+    }
+}
+
+#[test]
+fn test_event_ports() {
+    let mut debug_map = DebugMap::new();
+    let mut urids = unsafe { debug_map.create_cached_map() };
+    let midi_event = CStr::from_bytes_with_nul(MIDI_EVENT_URI).unwrap();
+    let midi_urid = urids.map(midi_event);
+
+    let mut uri_map = UriMap {
+        callback_data: std::ptr::null_mut(),
+        uri_to_id,
+    };
+    let mut input = EventInputPort::new(
+        EventTypes::new(&mut uri_map, &mut urids, &[midi_event]),
+        256,
+    );
+    let mut output = EventOutputPort::new(EventTypes::new(&mut uri_map, &mut urids, &[midi_event]));
+
+    // Filling the input buffer with a MIDI event and an event of an unknown type.
+    let mut input_data: Vec<u8> = Vec::new();
+    write_event(&mut input_data, 4, 3, &[0x90, 60, 100]);
+    write_event(&mut input_data, 8, 7, &[1, 2, 3, 4, 5, 6, 7, 8, 9]);
+    assert_eq!(16 + 24, input_data.len());
+    let input_buffer = EventBuffer {
+        data: input_data.as_mut_ptr(),
+        header_size: size_of::<EventBuffer>() as u16,
+        stamp_type: AUDIO_STAMP,
+        event_count: 2,
+        capacity: input_data.len() as u32,
+        size: input_data.len() as u32,
+    };
+    input.connect_port(&input_buffer);
+
+    let mut output_data: Vec<u64> = vec![0; 4];
+    let mut output_buffer = EventBuffer {
+        data: output_data.as_mut_ptr() as *mut u8,
+        header_size: size_of::<EventBuffer>() as u16,
+        stamp_type: AUDIO_STAMP,
+        event_count: 5,
+        capacity: 24,
+        size: 24,
+    };
+    output.connect_port(&mut output_buffer);
+
+    // Reading the input and passing it through.
+    unsafe { output.clear() }.unwrap();
+    let mut count = 0;
+    for (time, atom) in unsafe { input.iter() }.unwrap() {
+        assert_eq!(TimeStamp::Frames(4), time);
+        assert_eq!(midi_urid, atom.atom_type());
+        assert_eq!(&[0x90, 60, 100], atom.get_raw_body());
+        unsafe { output.push_event(time, atom) }.unwrap();
+        count += 1;
+    }
+    assert_eq!(1, count);
+
+    // Only the MIDI event may be written and it has to be stamped with frames.
+    let atom = unsafe { input.iter() }.unwrap().next().unwrap().1;
+    assert_eq!(Err(EventError::WrongTimeUnit), unsafe {
+        output.push_event(TimeStamp::Beats(1.0), atom)
+    });
+    assert_eq!(Err(EventError::InsufficientSpace), unsafe {
+        output.push_event(TimeStamp::Frames(5), atom)
+    });
+
+    assert_eq!(1, output_buffer.event_count);
+    assert_eq!(16, output_buffer.size);
+    let output_bytes = unsafe { std::slice::from_raw_parts(output_data.as_ptr() as *const u8, 16) };
+    assert_eq!(&4u32.to_ne_bytes(), &output_bytes[0..4]);
+    assert_eq!(&3u16.to_ne_bytes(), &output_bytes[8..10]);
+    assert_eq!(&3u16.to_ne_bytes(), &output_bytes[10..12]);
+    assert_eq!(&[0x90, 60, 100, 0], &output_bytes[12..16]);
+
+    // A disconnected port.
+    let mut disconnected = EventOutputPort::new(EventTypes::new(&mut uri_map, &mut urids, &[]));
+    assert_eq!(Err(EventError::NullPointer), unsafe {
+        disconnected.clear()
+    });
+}
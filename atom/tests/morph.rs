@@ -0,0 +1,168 @@
+extern crate lv2rs_atom as atom;
+extern crate lv2rs_core as core;
+extern crate lv2rs_urid as urid;
+
+use atom::morph::*;
+use atom::Atom;
+use core::ports::PortMetadata;
+use std::ffi::CStr;
+use std::ptr::null;
+use urid::debug::DebugMap;
+
+/// A router that passes its input to its output, whatever type they have.
+struct Router {
+    urids: urid::CachedMap,
+    input: MorphInputPort<f32>,
+    output: MorphOutputPort<f32>,
+}
+
+impl core::Plugin for Router {
+    fn instantiate(
+        _descriptor: &core::Descriptor,
+        _rate: f64,
+        _bundle_path: &CStr,
+        features: Option<&core::FeaturesList>,
+    ) -> Option<Self> {
+        Some(Self {
+            urids: urid::CachedMap::try_from_features(features?)?,
+            input: MorphInputPort::new(PortType::Audio, &[PortType::CV, PortType::Atom]),
+            output: MorphOutputPort::new(PortType::Audio, &[PortType::CV, PortType::Atom])
+                .with_auto_morph(),
+        })
+    }
+
+    fn connect_port(&mut self, port: u32, data: *mut ()) {
+        match port {
+            0 => self.input.connect(data),
+            1 => self.output.connect(data),
+            _ => (),
+        }
+    }
+
+    fn run(&mut self, n_samples: u32) {
+        match (self.input.port(), self.output.port()) {
+            (MorphedInput::Audio(input), MorphedOutput::Audio(output))
+            | (MorphedInput::CV(input), MorphedOutput::CV(output)) => {
+                let input = unsafe { input.as_slice(n_samples) }.unwrap();
+                let output = unsafe { output.as_slice(n_samples) }.unwrap();
+                output.copy_from_slice(input);
+            }
+            (MorphedInput::Atom(input), MorphedOutput::Atom(output)) => {
+                let value = *unsafe { input.get_atom_body(&mut self.urids) }.unwrap();
+                unsafe { output.write_atom_body(&value, &mut self.urids) }.unwrap();
+            }
+            _ => panic!("The ports have different types!"),
+        }
+    }
+}
+
+impl Morph for Router {
+    fn urids(&mut self) -> &mut urid::CachedMap {
+        &mut self.urids
+    }
+
+    fn morph_port(&mut self, port: u32, port_type: PortType, _options: &[OptionsOption]) -> bool {
+        // The output follows the input.
+        port == 0 && self.input.morph(port_type) && self.output.morph(port_type)
+    }
+
+    fn port_type(&mut self, port: u32, _options: &[OptionsOption]) -> Option<PortType> {
+        if port == 1 {
+            Some(self.output.port_type())
+        } else {
+            None
+        }
+    }
+}
+
+#[test]
+fn test_morph() {
+    let mut debug_map = DebugMap::new();
+    let mut urids = unsafe { debug_map.create_cached_map() };
+    let map_feature = core::Feature::new(
+        CStr::from_bytes_with_nul(urid::uris::MAP_URI).unwrap(),
+        debug_map.get_map_mut() as *mut urid::Map as *mut std::os::raw::c_void,
+    );
+    let features: [&'static core::Feature; 1] =
+        [unsafe { &*(&map_feature as *const core::Feature) }];
+    let bundle_path = CStr::from_bytes_with_nul(b"/\0").unwrap();
+    let descriptor = core::Descriptor::new::<Router>(bundle_path);
+    let mut router: Router =
+        core::Plugin::instantiate(&descriptor, 44100.0, bundle_path, Some(&features)).unwrap();
+    let handle = &mut router as *mut Router as core::Handle;
+    let interface = MorphInterface::new::<Router>();
+
+    // Description.
+    let mut node = PortMetadata::new(1, "out", "Output").to_node(&["lv2:OutputPort"]);
+    router.output.add_to_node(&mut node);
+    assert_eq!(
+        "[
+    a lv2:OutputPort ;
+    lv2:index 1 ;
+    lv2:symbol \"out\" ;
+    lv2:name \"Output\" ;
+    a lv2:AudioPort ;
+    a morph:AutoMorphPort ;
+    morph:supportsType lv2:CVPort ;
+    morph:supportsType atom:AtomPort
+]",
+        node.to_string()
+    );
+    assert!(!router.input.is_auto_morph());
+
+    // Audio.
+    let mut audio_in = [1.0f32, 2.0];
+    let mut audio_out = [0.0f32; 2];
+    core::Plugin::connect_port(&mut router, 0, audio_in.as_mut_ptr() as *mut ());
+    core::Plugin::connect_port(&mut router, 1, audio_out.as_mut_ptr() as *mut ());
+    core::Plugin::run(&mut router, 2);
+    assert_eq!(audio_in, audio_out);
+
+    // Morphing to atoms.
+    let atom_port_urid = urids.map(PortType::Atom.get_uri());
+    let midi_urid = urids.map(CStr::from_bytes_with_nul(b"http://example.org/MIDI\0").unwrap());
+    unsafe {
+        assert_eq!(0, (interface.morph_port)(handle, 0, midi_urid, null()));
+        assert_eq!(0, (interface.morph_port)(handle, 1, atom_port_urid, null()));
+        assert_eq!(
+            urids.map(PortType::Audio.get_uri()),
+            (interface.port_type)(handle, 1, null())
+        );
+        assert_eq!(
+            atom_port_urid,
+            (interface.morph_port)(handle, 0, atom_port_urid, null())
+        );
+        assert_eq!(atom_port_urid, (interface.port_type)(handle, 1, null()));
+        assert_eq!(0, (interface.port_type)(handle, 0, null()));
+    }
+    assert_eq!(PortType::Atom, router.input.port_type());
+    assert_eq!(
+        Some(PortType::Atom),
+        PortType::from_urid(atom_port_urid, &mut urids)
+    );
+
+    // The ports have been disconnected.
+    match router.input.port() {
+        MorphedInput::Atom(port) => assert!(unsafe { port.get_atom_body(&mut urids) }.is_err()),
+        _ => panic!("The input should be an atom port!"),
+    }
+
+    // Atoms.
+    let mut atom_in = vec![0u8; 64];
+    let mut atom_out = vec![0u8; 64];
+    unsafe {
+        let atom = (atom_in.as_mut_ptr() as *mut Atom).as_mut().unwrap();
+        *(atom.mut_size()) = 64 - 8;
+        let mut writer: atom::ports::AtomOutputPort<f32> = atom::ports::AtomOutputPort::new();
+        writer.connect_port(atom);
+        writer.write_atom_body(&42.0, &mut urids).unwrap();
+        let atom = (atom_out.as_mut_ptr() as *mut Atom).as_mut().unwrap();
+        *(atom.mut_size()) = 64 - 8;
+    }
+    core::Plugin::connect_port(&mut router, 0, atom_in.as_mut_ptr() as *mut ());
+    core::Plugin::connect_port(&mut router, 1, atom_out.as_mut_ptr() as *mut ());
+    core::Plugin::run(&mut router, 2);
+    let mut reader: atom::ports::AtomInputPort<f32> = atom::ports::AtomInputPort::new();
+    reader.connect_port(atom_out.as_ptr() as *const Atom);
+    assert_eq!(42.0, *unsafe { reader.get_atom_body(&mut urids) }.unwrap());
+}
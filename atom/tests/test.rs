@@ -309,3 +309,451 @@ fn test_sequence() {
         assert_eq!(2, *integer);
     }
 }
+
+#[test]
+fn test_object() {
+    use atom::object::{GetPropertyError, PropertySlot};
+    let mut debug_map = DebugMap::new();
+    let mut urids = unsafe { debug_map.create_cached_map() };
+    let class = urids.map(CStr::from_bytes_with_nul(b"https://example.org#Note\0").unwrap());
+    let note = urids.map(CStr::from_bytes_with_nul(b"https://example.org#note\0").unwrap());
+    let velocity = urids.map(CStr::from_bytes_with_nul(b"https://example.org#velocity\0").unwrap());
+    let name = urids.map(CStr::from_bytes_with_nul(b"https://example.org#name\0").unwrap());
+    let missing = urids.map(CStr::from_bytes_with_nul(b"https://example.org#missing\0").unwrap());
+
+    // Creating the atom space.
+    let mut atom_space = vec![0u8; 256];
+    let atom = unsafe { (atom_space.as_mut_ptr() as *mut Atom).as_mut() }.unwrap();
+    *(atom.mut_size()) = 256 - 8;
+
+    // Creating the ports and connecting them.
+    let mut out_port: AtomOutputPort<Object> = AtomOutputPort::new();
+    out_port.connect_port(atom);
+    let mut in_port: AtomInputPort<Object> = AtomInputPort::new();
+    in_port.connect_port(atom as &Atom);
+
+    // Writing.
+    {
+        let mut frame = unsafe { out_port.write_atom_body(&(0, class), &mut urids) }.unwrap();
+        frame
+            .push_property::<i32>(note, 0, &60, &mut urids)
+            .unwrap();
+        frame
+            .push_property::<f32>(velocity, 0, &0.5, &mut urids)
+            .unwrap();
+        frame
+            .push_property::<Literal>(name, 0, &0, &mut urids)
+            .unwrap()
+            .append_string("C4")
+            .unwrap();
+        frame
+            .push_property::<i32>(note, 0, &61, &mut urids)
+            .unwrap();
+    }
+
+    // Reading single properties.
+    let object = unsafe { in_port.get_atom_body(&mut urids) }.unwrap();
+    assert_eq!(60, *object.get::<i32>(note, &mut urids).unwrap());
+    assert_eq!(0.5, *object.get::<f32>(velocity, &mut urids).unwrap());
+    assert_eq!(
+        "C4",
+        object
+            .get::<Literal>(name, &mut urids)
+            .unwrap()
+            .as_str()
+            .unwrap()
+    );
+    assert_eq!(
+        GetPropertyError::MissingProperty(missing),
+        object.get::<i32>(missing, &mut urids).unwrap_err()
+    );
+    assert_eq!(
+        GetPropertyError::WrongURID(velocity),
+        object.get::<i32>(velocity, &mut urids).unwrap_err()
+    );
+
+    // Reading several properties at once.
+    let mut note_slot = PropertySlot::<i32>::new(note);
+    let mut velocity_slot = PropertySlot::<f32>::new(velocity);
+    let mut name_slot = PropertySlot::<Literal>::new(name);
+    assert!(note_slot.get().is_none());
+    object
+        .query(
+            &mut [&mut velocity_slot, &mut note_slot, &mut name_slot],
+            &mut urids,
+        )
+        .unwrap();
+    assert_eq!(60, *note_slot.get().unwrap());
+    assert_eq!(0.5, *velocity_slot.get().unwrap());
+    assert_eq!("C4", name_slot.get().unwrap().as_str().unwrap());
+
+    let mut note_slot = PropertySlot::<i32>::new(note);
+    let mut missing_slot = PropertySlot::<i32>::new(missing);
+    assert_eq!(
+        Err(GetPropertyError::MissingProperty(missing)),
+        object.query(&mut [&mut note_slot, &mut missing_slot], &mut urids)
+    );
+
+    let mut name_slot = PropertySlot::<f32>::new(name);
+    assert_eq!(
+        Err(GetPropertyError::WrongURID(name)),
+        object.query(&mut [&mut name_slot], &mut urids)
+    );
+}
+
+#[test]
+fn test_atom_object() {
+    use atom::object::{AtomObject, GetPropertyError};
+
+    #[derive(AtomObject, Debug, PartialEq)]
+    #[atom_object(otype = "https://example.org#Envelope")]
+    struct Envelope {
+        #[property(key = "https://example.org#attack")]
+        attack: f32,
+        #[property(key = "https://example.org#curve")]
+        curve: Vec<f32>,
+    }
+
+    #[derive(AtomObject, Debug, PartialEq)]
+    #[atom_object(otype = "https://example.org#Voice")]
+    struct Voice {
+        #[property(key = "https://example.org#note")]
+        note: i32,
+        #[property(key = "https://example.org#active")]
+        active: bool,
+        #[property(key = "https://example.org#envelope")]
+        envelope: Envelope,
+        #[property(key = "https://example.org#filter")]
+        filter: Option<Envelope>,
+        #[property(key = "https://example.org#detune")]
+        detune: Option<f64>,
+    }
+
+    let mut debug_map = DebugMap::new();
+    let mut urids = unsafe { debug_map.create_cached_map() };
+    let voice_class = urids.map(Voice::object_type());
+    let note = urids.map(CStr::from_bytes_with_nul(b"https://example.org#note\0").unwrap());
+
+    // Creating the atom space.
+    let mut atom_space = vec![0u8; 512];
+    let atom = unsafe { (atom_space.as_mut_ptr() as *mut Atom).as_mut() }.unwrap();
+    *(atom.mut_size()) = 512 - 8;
+
+    // Creating the ports and connecting them.
+    let mut out_port: AtomOutputPort<Object> = AtomOutputPort::new();
+    out_port.connect_port(atom);
+    let mut in_port: AtomInputPort<Object> = AtomInputPort::new();
+    in_port.connect_port(atom as &Atom);
+
+    let voice = Voice {
+        note: 64,
+        active: true,
+        envelope: Envelope {
+            attack: 0.25,
+            curve: vec![0.0, 0.5, 1.0],
+        },
+        filter: None,
+        detune: Some(-0.5),
+    };
+
+    // Writing and reading the struct.
+    {
+        let mut frame = unsafe { out_port.write_atom_body(&(0, voice_class), &mut urids) }.unwrap();
+        voice.write_properties(&mut frame, &mut urids).unwrap();
+    }
+    {
+        let object = unsafe { in_port.get_atom_body(&mut urids) }.unwrap();
+        assert_eq!(voice_class, object.header.otype);
+        // The optional property that is `None` was not written.
+        assert_eq!(4, object.iter().count());
+        assert_eq!(voice, Voice::read_object(object, &mut urids).unwrap());
+        let envelope: &Object = object
+            .get(
+                urids.map(CStr::from_bytes_with_nul(b"https://example.org#envelope\0").unwrap()),
+                &mut urids,
+            )
+            .unwrap();
+        assert_eq!(urids.map(Envelope::object_type()), envelope.header.otype);
+    }
+
+    // Reading an object with a missing property and a property of the wrong type.
+    {
+        let mut frame = unsafe { out_port.write_atom_body(&(0, voice_class), &mut urids) }.unwrap();
+        frame
+            .push_property::<f32>(note, 0, &64.0, &mut urids)
+            .unwrap();
+    }
+    {
+        let object = unsafe { in_port.get_atom_body(&mut urids) }.unwrap();
+        assert_eq!(
+            GetPropertyError::WrongURID(note),
+            Voice::read_object(object, &mut urids).unwrap_err()
+        );
+        assert_eq!(
+            GetPropertyError::MissingProperty(
+                urids.map(CStr::from_bytes_with_nul(b"https://example.org#attack\0").unwrap())
+            ),
+            Envelope::read_object(object, &mut urids).unwrap_err()
+        );
+    }
+}
+
+#[test]
+fn test_chunk() {
+    let mut debug_map = DebugMap::new();
+    let mut urids = unsafe { debug_map.create_cached_map() };
+    let chunk_type = urids.map(Chunk::get_uri());
+
+    // Creating the atom space. Like a host, we describe it as an empty chunk.
+    let mut atom_space = vec![0u8; 64];
+    let atom = unsafe { (atom_space.as_mut_ptr() as *mut Atom).as_mut() }.unwrap();
+    *(atom.mut_atom_type()) = chunk_type;
+    *(atom.mut_size()) = 64 - 8;
+    let atom = atom as *mut Atom;
+
+    // Creating the ports and connecting them.
+    let mut out_port: AtomOutputPort<Chunk> = AtomOutputPort::new();
+    out_port.connect_port(atom);
+    let mut in_port: AtomInputPort<Chunk> = AtomInputPort::new();
+    in_port.connect_port(atom as *const Atom);
+
+    // The whole body of the chunk is available.
+    {
+        let mut frame = unsafe { out_port.write_atom_body(&[1, 2, 3, 4], &mut urids) }.unwrap();
+        frame.append(&[5; 52]).unwrap();
+        assert!(frame.append(&[6]).is_err());
+    }
+    {
+        let chunk = unsafe { in_port.get_atom_body(&mut urids) }.unwrap();
+        assert_eq!(56, chunk.as_slice().len());
+        assert_eq!(&[1, 2, 3, 4, 5], &chunk.as_slice()[..5]);
+        assert_eq!(5, chunk.as_slice()[55]);
+    }
+
+    // Other atoms may use the space of the chunk too.
+    unsafe {
+        *((*atom).mut_atom_type()) = chunk_type;
+        *((*atom).mut_size()) = 64 - 8;
+    }
+    {
+        let mut out_port: AtomOutputPort<Vector<f32>> = AtomOutputPort::new();
+        out_port.connect_port(atom);
+        let mut frame = unsafe { out_port.write_atom_body(&(), &mut urids) }.unwrap();
+        frame.append(&[0.5; 12]).unwrap();
+        assert!(frame.push(1.0).is_err());
+    }
+
+    // Without a chunk, the size of the atom includes the header.
+    unsafe {
+        *((*atom).mut_atom_type()) = urids.map(<i32 as AtomBody>::get_uri());
+        *((*atom).mut_size()) = 64 - 8;
+    }
+    {
+        let mut frame = unsafe { out_port.write_atom_body(&[], &mut urids) }.unwrap();
+        assert!(frame.append(&[0; 49]).is_err());
+        frame.append(&[0; 48]).unwrap();
+    }
+}
+
+#[test]
+fn test_uri_and_path() {
+    use std::path::Path;
+
+    let mut debug_map = DebugMap::new();
+    let mut urids = unsafe { debug_map.create_cached_map() };
+
+    // Creating the atom space.
+    let mut atom_space = vec![0u8; 256];
+    let atom = unsafe { (atom_space.as_mut_ptr() as *mut Atom).as_mut() }.unwrap();
+    *(atom.mut_size()) = 256 - 8;
+    let atom = atom as *mut Atom;
+    // Hosts reset the size of the atom space before every run.
+    let reset = || unsafe { *((*atom).mut_size()) = 256 - 8 };
+
+    // URIs.
+    {
+        let mut out_port: AtomOutputPort<AtomURI> = AtomOutputPort::new();
+        out_port.connect_port(atom);
+        let mut in_port: AtomInputPort<AtomURI> = AtomInputPort::new();
+        in_port.connect_port(atom as *const Atom);
+
+        unsafe { out_port.write_atom_body("https://example.org#kick", &mut urids) }.unwrap();
+        let uri = unsafe { in_port.get_atom_body(&mut urids) }.unwrap();
+        assert_eq!("https://example.org#kick", uri.as_str().unwrap());
+        assert_eq!(
+            CStr::from_bytes_with_nul(b"https://example.org#kick\0").unwrap(),
+            uri.as_cstr().unwrap()
+        );
+        assert!(unsafe { in_port.get_atom_body(&mut urids) }.is_ok());
+
+        // Strings with null bytes can't be written.
+        reset();
+        assert!(unsafe { out_port.write_atom_body("https://example.org#\0", &mut urids) }.is_err());
+    }
+
+    // Paths.
+    {
+        let mut out_port: AtomOutputPort<AtomPath> = AtomOutputPort::new();
+        out_port.connect_port(atom);
+        let mut in_port: AtomInputPort<AtomPath> = AtomInputPort::new();
+        in_port.connect_port(atom as *const Atom);
+
+        let sample = Path::new("/usr/share/samples/kick.wav");
+        reset();
+        unsafe { out_port.write_atom_body(sample, &mut urids) }.unwrap();
+        let path = unsafe { in_port.get_atom_body(&mut urids) }.unwrap();
+        assert_eq!(sample, path.as_path().unwrap());
+        assert_eq!("/usr/share/samples/kick.wav", path.as_str().unwrap());
+
+        // A path is not a URI.
+        let mut uri_port: AtomInputPort<AtomURI> = AtomInputPort::new();
+        uri_port.connect_port(atom as *const Atom);
+        assert!(unsafe { uri_port.get_atom_body(&mut urids) }.is_err());
+    }
+}
+
+#[test]
+fn test_sound() {
+    extern crate lv2rs_core as core;
+    use atom::ports::WriteAtomError;
+    use core::ports::AudioInputPort;
+
+    let mut debug_map = DebugMap::new();
+    let mut urids = unsafe { debug_map.create_cached_map() };
+
+    // Creating the atom space, described as an empty chunk.
+    let mut atom_space = vec![0u8; 64];
+    let atom = unsafe { (atom_space.as_mut_ptr() as *mut Atom).as_mut() }.unwrap();
+    *(atom.mut_atom_type()) = urids.map(Chunk::get_uri());
+    *(atom.mut_size()) = 64 - 8;
+    let atom = atom as *mut Atom;
+
+    // Creating the ports and connecting them.
+    let audio: Vec<f32> = (0..16).map(|i| i as f32 / 16.0).collect();
+    let mut audio_port = AudioInputPort::new();
+    let mut out_port: AtomOutputPort<Sound> = AtomOutputPort::new();
+    out_port.connect_port(atom);
+    let mut in_port: AtomInputPort<Sound> = AtomInputPort::new();
+    in_port.connect_port(atom as *const Atom);
+
+    // Writing.
+    {
+        let mut frame = unsafe { out_port.write_atom_body(&(), &mut urids) }.unwrap();
+        match unsafe { frame.append_port(&audio_port, 4) } {
+            Err(WriteAtomError::NullPointer) => (),
+            _ => panic!("Appended samples from an unconnected port!"),
+        }
+        audio_port.connect(audio.as_ptr());
+        unsafe { frame.append_port(&audio_port, 8) }.unwrap();
+        frame.append(&[1.0; 4]).unwrap();
+        // The header of the sound takes 8 bytes, leaving space for 12 samples.
+        match frame.append(&[1.0]) {
+            Err(WriteAtomError::InsufficientSpace) => (),
+            _ => panic!("Appended samples beyond the end of the atom space!"),
+        }
+    }
+
+    // Reading.
+    {
+        let sound = unsafe { in_port.get_atom_body(&mut urids) }.unwrap();
+        assert_eq!(&audio[..8], &sound.as_slice()[..8]);
+        assert_eq!(&[1.0; 4], &sound.as_slice()[8..]);
+        assert_eq!(
+            urids.map(<f32 as AtomBody>::get_uri()),
+            sound.header.child_type
+        );
+
+        // Sounds are not read as vectors.
+        let mut vector_port: AtomInputPort<Vector<f32>> = AtomInputPort::new();
+        vector_port.connect_port(atom as *const Atom);
+        assert!(unsafe { vector_port.get_atom_body(&mut urids) }.is_err());
+    }
+}
+
+#[test]
+fn test_legacy_object() {
+    use atom::object::ObjectType;
+
+    let mut debug_map = DebugMap::new();
+    let mut urids = unsafe { debug_map.create_cached_map() };
+    let class = urids.map(CStr::from_bytes_with_nul(b"https://example.org#Note\0").unwrap());
+    let note = urids.map(CStr::from_bytes_with_nul(b"https://example.org#note\0").unwrap());
+    let instance = urids.map(CStr::from_bytes_with_nul(b"https://example.org#C4\0").unwrap());
+
+    // Creating the atom space.
+    let mut atom_space = vec![0u8; 256];
+    let atom = unsafe { (atom_space.as_mut_ptr() as *mut Atom).as_mut() }.unwrap();
+    *(atom.mut_size()) = 256 - 8;
+    let atom = atom as *mut Atom;
+
+    // Creating the ports and connecting them.
+    let mut out_port: AtomOutputPort<Object> = AtomOutputPort::new();
+    out_port.connect_port(atom);
+    let mut in_port: AtomInputPort<Object> = AtomInputPort::new();
+    in_port.connect_port(atom as *const Atom);
+
+    assert_eq!(ObjectType::Blank, ObjectType::legacy(0));
+    assert_eq!(ObjectType::Resource, ObjectType::legacy(instance));
+
+    for (id, object_type) in [
+        (0, ObjectType::Object),
+        (0, ObjectType::Blank),
+        (instance, ObjectType::Resource),
+    ]
+    .iter()
+    {
+        // Writing an object with the given type.
+        unsafe { *((*atom).mut_size()) = 256 - 8 };
+        {
+            let mut frame = unsafe { out_port.write_atom_body(&(*id, class), &mut urids) }.unwrap();
+            frame.set_object_type(*object_type, &mut urids).unwrap();
+            frame
+                .push_property::<i32>(note, 0, &60, &mut urids)
+                .unwrap();
+        }
+        assert_eq!(urids.map(object_type.get_uri()), unsafe {
+            (*atom).atom_type()
+        });
+
+        // All object types are read as objects.
+        let object = unsafe { in_port.get_atom_body(&mut urids) }.unwrap();
+        assert_eq!(*id, object.header.id);
+        assert_eq!(class, object.header.otype);
+        assert_eq!(60, *object.get::<i32>(note, &mut urids).unwrap());
+    }
+
+    // Other atoms are still rejected.
+    unsafe { *((*atom).mut_size()) = 256 - 8 };
+    let mut tuple_port: AtomOutputPort<Tuple> = AtomOutputPort::new();
+    tuple_port.connect_port(atom);
+    unsafe { tuple_port.write_atom_body(&(), &mut urids) }.unwrap();
+    assert!(unsafe { in_port.get_atom_body(&mut urids) }.is_err());
+
+    // Frames without access to their header keep the default type.
+    struct HiddenHeader<F>(F);
+
+    impl<'a, F: WritingFrame<'a>> WritingFrame<'a> for HiddenHeader<F> {
+        unsafe fn write_raw(&mut self, data: &[u8]) -> Result<&'a mut [u8], ()> {
+            self.0.write_raw(data)
+        }
+
+        fn get_atom(&self) -> &Atom {
+            self.0.get_atom()
+        }
+    }
+
+    impl<'a, F: WritingFrame<'a>> WritingFrameExt<'a, Object> for HiddenHeader<F> {}
+
+    unsafe { *((*atom).mut_size()) = 256 - 8 };
+    {
+        let frame = unsafe { out_port.write_atom_body(&(0, class), &mut urids) }.unwrap();
+        let mut frame = HiddenHeader(frame);
+        match frame.set_object_type(ObjectType::Blank, &mut urids) {
+            Err(WriteAtomError::HeaderUnavailable) => (),
+            _ => panic!("Changed the type without access to the header!"),
+        }
+    }
+    assert_eq!(urids.map(ObjectType::Object.get_uri()), unsafe {
+        (*atom).atom_type()
+    });
+}
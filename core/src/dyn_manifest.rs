@@ -0,0 +1,202 @@
+//! Generating plugin descriptions at runtime.
+//!
+//! Usually, the plugins of a library are described by the Turtle files of its bundle. Some
+//! libraries don't know their plugins in advance, for example because they create one plugin for
+//! every script they find. Such libraries implement the
+//! [dynamic manifest extension](http://lv2plug.in/ns/ext/dynmanifest): The host opens the
+//! manifest generator of the library, asks it for the list of plugins and then for the
+//! description of every plugin.
+//!
+//! A manifest generator implements the [`DynManifest`](trait.DynManifest.html) trait and is
+//! exported with the [`lv2_dyn_manifest`](../macro.lv2_dyn_manifest.html) macro. Since the plugins
+//! are only known at runtime, their descriptors are stored in a
+//! [`DescriptorTable`](../struct.DescriptorTable.html) that is filled by the generator. The bundle
+//! of the library only has to declare the generator:
+//!
+//! ```text
+//! @prefix dman: <http://lv2plug.in/ns/ext/dynmanifest#> .
+//! @prefix lv2: <http://lv2plug.in/ns/lv2core#> .
+//!
+//! <http://example.org/scripts/generator>
+//!     a dman:DynManifest ;
+//!     lv2:binary <scripts.so> .
+//! ```
+use crate::plugin::DescriptorTable;
+use crate::ttl;
+use crate::{Feature, FeaturesList};
+use std::ffi::CStr;
+use std::os::raw::*;
+
+pub const URI: &[u8] = b"http://lv2plug.in/ns/ext/dynmanifest\0";
+pub const DYN_MANIFEST_URI: &[u8] = b"http://lv2plug.in/ns/ext/dynmanifest#DynManifest\0";
+
+/// Handle of an opened manifest generator.
+pub type DynManifestHandle = *mut c_void;
+
+/// The `FILE` stream type of the C standard library.
+///
+/// The host passes the stream the generated Turtle data is written to.
+#[repr(C)]
+pub struct File {
+    _private: [u8; 0],
+}
+
+extern "C" {
+    fn fwrite(ptr: *const c_void, size: usize, count: usize, stream: *mut File) -> usize;
+}
+
+/// A generator for the descriptions of plugins that are only known at runtime.
+///
+/// The descriptions are written as [`ttl::Node`s](../ttl/struct.Node.html), which may use the
+/// prefixes of [`ttl::PREFIXES`](../ttl/constant.PREFIXES.html).
+pub trait DynManifest {
+    /// Register the descriptors of all plugins of the library.
+    ///
+    /// This function is called once, before the host requests the first descriptor or opens the
+    /// generator. The URIs of the descriptors have to match the subjects of the generator.
+    fn register_plugins(descriptors: &DescriptorTable);
+
+    /// Open the generator.
+    ///
+    /// The generator is created every time the host wants to read the descriptions and it is
+    /// dropped when the host is done. If the generator can't be created, `None` should be
+    /// returned.
+    fn open(features: Option<&FeaturesList>) -> Option<Self>
+    where
+        Self: Sized;
+
+    /// Return the subjects the generator describes, together with their types.
+    ///
+    /// For plugins, these are nodes like `<https://example.org/plugin> a lv2:Plugin .`
+    fn subjects(&mut self) -> Vec<ttl::Node>;
+
+    /// Return the description of the subject with the given URI.
+    ///
+    /// If the URI isn't one of the subjects, `None` should be returned.
+    fn data(&mut self, uri: &CStr) -> Option<Vec<ttl::Node>>;
+}
+
+/// Write a document with the given nodes to the stream.
+///
+/// This function returns zero if the document was written and a non-zero value otherwise.
+unsafe fn write_document(nodes: &[ttl::Node], stream: *mut File) -> c_int {
+    if stream.is_null() {
+        return 1;
+    }
+    let mut document = String::from(ttl::PREFIXES);
+    for node in nodes {
+        document.push('\n');
+        document.push_str(&node.to_string());
+    }
+    let written = fwrite(
+        document.as_ptr() as *const c_void,
+        1,
+        document.len(),
+        stream,
+    );
+    if written == document.len() {
+        0
+    } else {
+        1
+    }
+}
+
+/// Helper function for the `lv2_dyn_manifest_open` call.
+///
+/// This function takes the raw parameters provided by the C API and turns them into safe Rust data
+/// types. Only functions generated by the `lv2_dyn_manifest` macro should call this function, any
+/// other must not.
+///
+/// # Safety
+///
+/// `handle` has to point to writable memory or be null and `features` has to be a null-terminated
+/// array of valid features or null.
+pub unsafe fn open<M: DynManifest>(
+    handle: *mut DynManifestHandle,
+    features: *const *const Feature,
+) -> c_int {
+    let handle = match handle.as_mut() {
+        Some(handle) => handle,
+        None => return 1,
+    };
+    let features = if features.is_null() {
+        None
+    } else {
+        let mut length = 0;
+        while !(*features.add(length)).is_null() {
+            length += 1;
+        }
+        Some(std::slice::from_raw_parts(
+            features as *const &'static Feature,
+            length,
+        ))
+    };
+    match M::open(features) {
+        Some(generator) => {
+            *handle = Box::leak(Box::new(generator)) as *mut M as DynManifestHandle;
+            0
+        }
+        None => 1,
+    }
+}
+
+/// Helper function for the `lv2_dyn_manifest_get_subjects` call.
+///
+/// This function takes the raw parameters provided by the C API and turns them into safe Rust data
+/// types. Only functions generated by the `lv2_dyn_manifest` macro should call this function, any
+/// other must not.
+///
+/// # Safety
+///
+/// `handle` has to be a handle created by [`open`](fn.open.html) for the same type `M` and
+/// `stream` has to be an open stream or null.
+pub unsafe fn get_subjects<M: DynManifest>(handle: DynManifestHandle, stream: *mut File) -> c_int {
+    match (handle as *mut M).as_mut() {
+        Some(generator) => write_document(&generator.subjects(), stream),
+        None => 1,
+    }
+}
+
+/// Helper function for the `lv2_dyn_manifest_get_data` call.
+///
+/// This function takes the raw parameters provided by the C API and turns them into safe Rust data
+/// types. Only functions generated by the `lv2_dyn_manifest` macro should call this function, any
+/// other must not.
+///
+/// # Safety
+///
+/// `handle` has to be a handle created by [`open`](fn.open.html) for the same type `M`, `stream`
+/// has to be an open stream or null and `uri` has to be a null-terminated string or null.
+pub unsafe fn get_data<M: DynManifest>(
+    handle: DynManifestHandle,
+    stream: *mut File,
+    uri: *const c_char,
+) -> c_int {
+    let generator = match (handle as *mut M).as_mut() {
+        Some(generator) => generator,
+        None => return 1,
+    };
+    if uri.is_null() {
+        return 1;
+    }
+    match generator.data(CStr::from_ptr(uri)) {
+        Some(nodes) => write_document(&nodes, stream),
+        None => 1,
+    }
+}
+
+/// Helper function for the `lv2_dyn_manifest_close` call.
+///
+/// This function takes the raw parameters provided by the C API and turns them into safe Rust data
+/// types. Only functions generated by the `lv2_dyn_manifest` macro should call this function, any
+/// other must not.
+///
+/// # Safety
+///
+/// `handle` has to be a handle created by [`open`](fn.open.html) for the same type `M`, which
+/// must not be used afterwards.
+pub unsafe fn close<M: DynManifest>(handle: DynManifestHandle) {
+    if !handle.is_null() {
+        drop(Box::from_raw(handle as *mut M));
+    }
+}
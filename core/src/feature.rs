@@ -31,6 +31,17 @@ pub struct Feature {
 pub type FeaturesList = [&'static Feature];
 
 impl Feature {
+    /// Create a new feature.
+    ///
+    /// Features are usually created by hosts, but creating them is also useful when testing
+    /// plugins without a host.
+    pub fn new(uri: &'static CStr, data: *mut c_void) -> Self {
+        Self {
+            uri: uri.as_ptr(),
+            data,
+        }
+    }
+
     /// Try to get the URI of the feature.
     ///
     /// None if the URI is pointing to null.
@@ -0,0 +1,401 @@
+//! Small visualizations in the mixer strip of the host.
+//!
+//! Some hosts, for example Ardour, show a small image in the mixer strip of a plugin, like the
+//! curve of an equalizer or the level of a meter. This image is drawn by the plugin via the
+//! [inline display extension](http://harrisonconsoles.com/lv2/inlinedisplay): The host calls the
+//! [`InlineDisplay::render`](trait.InlineDisplay.html#tymethod.render) method of the plugin's
+//! display state, which draws the image into a [`Surface`](struct.Surface.html) in software.
+//!
+//! Drawing happens in a non-realtime thread of the host, but concurrently to `run`. Therefore, the
+//! plugin itself is never touched by the render thread. Instead, the data the image is drawn from
+//! lives in a separate display state, which is shared by the plugin and the render thread via an
+//! `Arc` and uses atomics or lock-free structures. The plugin hands the state out with
+//! [`Plugin::inline_display`](../trait.Plugin.html#method.inline_display) and when the data has
+//! changed, it asks the host to draw the image again with the [`QueueDraw`](struct.QueueDraw.html)
+//! feature.
+//!
+//!     extern crate lv2rs_core as core;
+//!     use core::inline_display::*;
+//!
+//!     /// Draw a horizontal level bar of the given level, between 0.0 and 1.0.
+//!     fn draw_level(level: f32, width: u32) -> Surface {
+//!         let mut surface = Surface::new(width, 8);
+//!         surface.fill(argb(0xff, 0x20, 0x20, 0x20));
+//!         let bar_width = (level.max(0.0).min(1.0) * width as f32) as u32;
+//!         surface.fill_rect(0, 2, bar_width, 4, argb(0xff, 0x00, 0xc0, 0x00));
+//!         surface
+//!     }
+//!
+//!     let surface = draw_level(0.5, 10);
+//!     assert_eq!(argb(0xff, 0x00, 0xc0, 0x00), surface.pixel(4, 2).unwrap());
+//!     assert_eq!(argb(0xff, 0x20, 0x20, 0x20), surface.pixel(5, 2).unwrap());
+use crate::feature::{Feature, FeaturesList};
+use crate::plugin::{ExtensionData, Handle};
+use std::ffi::CStr;
+use std::os::raw::*;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+pub const URI: &[u8] = b"http://harrisonconsoles.com/lv2/inlinedisplay\0";
+pub const INTERFACE_URI: &[u8] = b"http://harrisonconsoles.com/lv2/inlinedisplay#interface\0";
+pub const QUEUE_DRAW_URI: &[u8] = b"http://harrisonconsoles.com/lv2/inlinedisplay#queue_draw\0";
+pub const IN_GUI_URI: &[u8] = b"http://harrisonconsoles.com/lv2/inlinedisplay#in_gui\0";
+
+/// Create an ARGB32 pixel from the given color components.
+///
+/// The color components are premultiplied with the alpha value, as required by the image format of
+/// the host.
+pub fn argb(alpha: u8, red: u8, green: u8, blue: u8) -> u32 {
+    let premultiply = |component: u8| (u32::from(component) * u32::from(alpha) + 127) / 255;
+    (u32::from(alpha) << 24)
+        | (premultiply(red) << 16)
+        | (premultiply(green) << 8)
+        | premultiply(blue)
+}
+
+/// An image with ARGB32 pixels.
+///
+/// Every pixel is a native-endian `u32` with the alpha value in the highest and the blue value in
+/// the lowest byte, just like Cairo's `ARGB32` format. The color components have to be
+/// premultiplied with the alpha value, which is done by [`argb`](fn.argb.html). A new surface is
+/// fully transparent.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Surface {
+    width: u32,
+    height: u32,
+    pixels: Vec<u32>,
+}
+
+impl Surface {
+    /// Create a new, transparent surface with the given size.
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![0; width as usize * height as usize],
+        }
+    }
+
+    /// Return the width of the surface in pixels.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Return the height of the surface in pixels.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Return the pixels of the surface, row by row.
+    pub fn pixels(&self) -> &[u32] {
+        &self.pixels
+    }
+
+    /// Return the pixels of the surface mutably, row by row.
+    pub fn pixels_mut(&mut self) -> &mut [u32] {
+        &mut self.pixels
+    }
+
+    /// Return the pixel at the given position or `None` if it's outside of the surface.
+    pub fn pixel(&self, x: u32, y: u32) -> Option<u32> {
+        if x < self.width && y < self.height {
+            Some(self.pixels[(y * self.width + x) as usize])
+        } else {
+            None
+        }
+    }
+
+    /// Set the pixel at the given position.
+    ///
+    /// Pixels outside of the surface are ignored.
+    pub fn set_pixel(&mut self, x: u32, y: u32, pixel: u32) {
+        if x < self.width && y < self.height {
+            self.pixels[(y * self.width + x) as usize] = pixel;
+        }
+    }
+
+    /// Set all pixels of the surface.
+    pub fn fill(&mut self, pixel: u32) {
+        for target in self.pixels.iter_mut() {
+            *target = pixel;
+        }
+    }
+
+    /// Set all pixels of the given rectangle.
+    ///
+    /// The parts of the rectangle that are outside of the surface are ignored.
+    pub fn fill_rect(&mut self, x: u32, y: u32, width: u32, height: u32, pixel: u32) {
+        let x_end = x.saturating_add(width).min(self.width);
+        let y_end = y.saturating_add(height).min(self.height);
+        for row in y.min(y_end)..y_end {
+            let start = (row * self.width) as usize;
+            for target in &mut self.pixels[start + x.min(x_end) as usize..start + x_end as usize] {
+                *target = pixel;
+            }
+        }
+    }
+}
+
+/// Raw representation of a surface, as it's passed to the host.
+///
+/// This type is `repr(C)` and is used to interpret raw data.
+#[repr(C)]
+pub struct RawSurface {
+    /// The pixel data.
+    pub data: *mut c_uchar,
+    /// The width of the image in pixels.
+    pub width: c_int,
+    /// The height of the image in pixels.
+    pub height: c_int,
+    /// The size of a row in bytes.
+    pub stride: c_int,
+}
+
+/// Storage for the last rendered surface.
+///
+/// The host reads the surface after `render` has returned and therefore, the plugin has to keep it
+/// until the next `render` call. This storage is part of the display state and is handed to the
+/// interface by [`InlineDisplay::display_storage`](trait.InlineDisplay.html#tymethod.display_storage).
+///
+/// The display state is shared and therefore, the storage is only accessible through a shared
+/// reference and guards its content with a mutex. The mutex is only locked by the render thread
+/// and by [`surface`](#method.surface), and never by `run`.
+pub struct DisplayStorage {
+    content: Mutex<StorageContent>,
+}
+
+struct StorageContent {
+    surface: Option<Surface>,
+    raw: RawSurface,
+}
+
+// The raw surface only points into the surface that is owned by the same content.
+unsafe impl Send for StorageContent {}
+
+impl DisplayStorage {
+    /// Create an empty storage.
+    pub fn new() -> Self {
+        Self {
+            content: Mutex::new(StorageContent {
+                surface: None,
+                raw: RawSurface {
+                    data: std::ptr::null_mut(),
+                    width: 0,
+                    height: 0,
+                    stride: 0,
+                },
+            }),
+        }
+    }
+
+    /// Return a copy of the last rendered surface, if there is one.
+    pub fn surface(&self) -> Option<Surface> {
+        self.lock().surface.clone()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, StorageContent> {
+        self.content
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Drop the last rendered surface.
+    fn clear(&self) {
+        self.lock().surface = None;
+    }
+
+    /// Store the surface and return its raw representation.
+    ///
+    /// The returned pointer stays valid until the storage is modified again, which only happens in
+    /// the next `render` call.
+    fn store(&self, surface: Surface) -> *mut RawSurface {
+        let mut content = self.lock();
+        let content = &mut *content;
+        content.surface = Some(surface);
+        let surface = content.surface.as_mut().unwrap();
+        content.raw = RawSurface {
+            data: surface.pixels.as_mut_ptr() as *mut c_uchar,
+            width: surface.width as c_int,
+            height: surface.height as c_int,
+            stride: (surface.width * 4) as c_int,
+        };
+        &mut content.raw
+    }
+}
+
+impl Default for DisplayStorage {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The state an inline display is drawn from.
+///
+/// The state is created by the plugin and shared with the render thread of the host via an `Arc`,
+/// which is returned by [`Plugin::inline_display`](../trait.Plugin.html#method.inline_display).
+/// Both methods are called from the render thread while `run` may be executing in another thread
+/// and therefore, the state has to be `Send` and `Sync`.
+pub trait InlineDisplay: Send + Sync {
+    /// Return the storage for the rendered surface.
+    fn display_storage(&self) -> &DisplayStorage;
+
+    /// Draw the inline display.
+    ///
+    /// The surface should be `width` pixels wide and must not be higher than `max_height`. The
+    /// plugin may choose a smaller width if it has a fixed aspect ratio. If nothing should be
+    /// displayed, `None` is returned. Surfaces that don't fit are discarded.
+    fn render(&self, width: u32, max_height: u32) -> Option<Surface>;
+}
+
+/// Raw representation of the inline display interface.
+///
+/// A plugin returns a static reference to this struct when the host asks for the
+/// [`INTERFACE_URI`](constant.INTERFACE_URI.html). The interface draws the display state that the
+/// plugin returned from [`Plugin::inline_display`](../trait.Plugin.html#method.inline_display)
+/// when it was instantiated:
+///
+///     # extern crate lv2rs_core as core;
+///     # use core::inline_display::*;
+///     # use std::ffi::CStr;
+///     # use std::sync::Arc;
+///     struct MeterDisplay {
+///         storage: DisplayStorage,
+///     }
+///
+///     impl InlineDisplay for MeterDisplay {
+///         fn display_storage(&self) -> &DisplayStorage {
+///             &self.storage
+///         }
+///
+///         fn render(&self, width: u32, _max_height: u32) -> Option<Surface> {
+///             Some(Surface::new(width, width / 4))
+///         }
+///     }
+///
+///     struct Meter {
+///         display: Arc<MeterDisplay>,
+///     }
+///
+///     impl core::Plugin for Meter {
+///     #     fn instantiate(
+///     #         _: &core::Descriptor,
+///     #         _: f64,
+///     #         _: &CStr,
+///     #         _: Option<&core::FeaturesList>,
+///     #     ) -> Option<Self> {
+///     #         None
+///     #     }
+///     #     fn connect_port(&mut self, _port: u32, _data: *mut ()) {}
+///     #     fn run(&mut self, _n_samples: u32) {}
+///         // ...
+///
+///         fn inline_display(&self) -> Option<Arc<dyn InlineDisplay>> {
+///             Some(self.display.clone())
+///         }
+///
+///         fn extension_data(uri: &CStr) -> Option<&'static dyn core::ExtensionData> {
+///             static DISPLAY: InlineDisplayInterface = InlineDisplayInterface::new();
+///             if uri.to_bytes_with_nul() == INTERFACE_URI {
+///                 Some(&DISPLAY)
+///             } else {
+///                 None
+///             }
+///         }
+///     }
+#[repr(C)]
+pub struct InlineDisplayInterface {
+    /// Draw the display and return the surface, which stays valid until the next call, or null.
+    pub render:
+        unsafe extern "C" fn(instance: Handle, width: u32, max_height: u32) -> *mut RawSurface,
+}
+
+impl InlineDisplayInterface {
+    /// Create the interface.
+    pub const fn new() -> Self {
+        Self { render }
+    }
+}
+
+impl Default for InlineDisplayInterface {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ExtensionData for InlineDisplayInterface {}
+
+/// The display states of all plugin instances that have one, by their handle.
+///
+/// The render thread only has the handle of an instance and must not create a reference to the
+/// plugin, since `run` may be executing at the same time. Therefore, the states are registered
+/// when an instance is created and looked up by the handle.
+static DISPLAYS: Mutex<Vec<(usize, Arc<dyn InlineDisplay>)>> = Mutex::new(Vec::new());
+
+fn displays() -> MutexGuard<'static, Vec<(usize, Arc<dyn InlineDisplay>)>> {
+    DISPLAYS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Register the display state of a new plugin instance.
+pub(crate) fn register(instance: Handle, display: Arc<dyn InlineDisplay>) {
+    displays().push((instance as usize, display));
+}
+
+/// Remove the display state of a plugin instance that is cleaned up.
+pub(crate) fn unregister(instance: Handle) {
+    displays().retain(|(handle, _)| *handle != instance as usize);
+}
+
+unsafe extern "C" fn render(instance: Handle, width: u32, max_height: u32) -> *mut RawSurface {
+    let display = match displays()
+        .iter()
+        .find(|(handle, _)| *handle == instance as usize)
+    {
+        Some((_, display)) => display.clone(),
+        None => return std::ptr::null_mut(),
+    };
+    let surface = match display.render(width, max_height) {
+        Some(surface) => surface,
+        None => return std::ptr::null_mut(),
+    };
+    let storage = display.display_storage();
+    storage.clear();
+    if surface.width > width || surface.height > max_height || surface.pixels.is_empty() {
+        return std::ptr::null_mut();
+    }
+    storage.store(surface)
+}
+
+/// The `queue_draw` feature.
+///
+/// Hosts providing this feature redraw the inline display when the plugin asks for it.
+#[repr(C)]
+pub struct QueueDraw {
+    /// Opaque data of the host, which must be passed to `queue_draw`.
+    pub handle: *mut c_void,
+    /// Ask the host to draw the display again.
+    pub queue_draw: unsafe extern "C" fn(handle: *mut c_void),
+}
+
+impl QueueDraw {
+    /// Try to find the queue-draw feature of the host in the features list.
+    ///
+    /// This function returns `None` if the host does not support inline displays.
+    pub fn try_from_features(features: &FeaturesList) -> Option<&'static mut Self> {
+        unsafe {
+            Feature::get_feature::<Self>(
+                features,
+                CStr::from_bytes_with_nul_unchecked(QUEUE_DRAW_URI),
+            )
+        }
+    }
+
+    /// Ask the host to draw the display again.
+    ///
+    /// This method may be called in the plugin's `run` function. The host then calls the
+    /// [`render`](struct.InlineDisplayInterface.html#structfield.render) function later, in another
+    /// thread.
+    pub fn queue_draw(&mut self) {
+        unsafe { (self.queue_draw)(self.handle) }
+    }
+}
@@ -0,0 +1,86 @@
+//! Sharing data between a plugin instance and its UI.
+//!
+//! Usually, plugins and UIs only communicate via ports, which is also possible if they run in
+//! different processes. Some UIs, for example spectrum displays, need more data than this and
+//! therefore, hosts may give UIs direct access to the plugin they control:
+//!
+//! * The `instance-access` feature passes the plugin instance to the UI.
+//! * The `data-access` feature passes the plugin's [`extension_data`](../trait.Plugin.html#method.extension_data)
+//!   function to the UI.
+//!
+//! UIs use these features with the wrappers of the `lv2rs-ui` crate. A plugin that wants to share
+//! data with its UI via instance access implements the [`SharedData`](trait.SharedData.html) trait,
+//! which hands out a reference-counted pointer to the shared data.
+//!
+//! ## Threading rules
+//!
+//! The plugin runs in the host's audio thread, while the UI runs in the host's GUI thread. This
+//! has some consequences:
+//!
+//! * The shared data is accessed from both threads at the same time and therefore, it has to be
+//!   `Send` and `Sync`. Since the audio thread must not block, the data should only consist of
+//!   atomics or lock-free structures, like ring buffers.
+//! * The plugin instance itself is mutably borrowed by the audio thread during `run`. Therefore,
+//!   the UI must not keep a reference to the instance; It should get the shared data once while
+//!   it's instantiated and only use this afterwards.
+//! * The UI is destroyed before the plugin instance, but the shared data is reference-counted and
+//!   therefore, it stays valid as long as one of them holds it.
+//! * Both features are only available if the plugin and the UI run in the same process. A UI
+//!   should therefore fall back to port communication if they are missing.
+use crate::plugin::Plugin;
+use std::sync::Arc;
+
+pub const INSTANCE_ACCESS_URI: &[u8] = b"http://lv2plug.in/ns/ext/instance-access\0";
+pub const DATA_ACCESS_URI: &[u8] = b"http://lv2plug.in/ns/ext/data-access\0";
+
+/// A plugin that shares read-only data with its UI.
+///
+///     extern crate lv2rs_core as core;
+///     use core::instance_access::SharedData;
+///     use std::ffi::CStr;
+///     use std::sync::atomic::{AtomicU32, Ordering};
+///     use std::sync::Arc;
+///
+///     /// The peak level, as the bits of a float.
+///     struct Meter {
+///         peak: Arc<AtomicU32>,
+///     }
+///
+///     impl core::Plugin for Meter {
+///         fn instantiate(
+///             _descriptor: &core::Descriptor,
+///             _rate: f64,
+///             _bundle_path: &CStr,
+///             _features: Option<&core::FeaturesList>,
+///         ) -> Option<Self> {
+///             Some(Self { peak: Arc::new(AtomicU32::new(0)) })
+///         }
+///
+///         fn connect_port(&mut self, _port: u32, _data: *mut ()) {}
+///
+///         fn run(&mut self, _n_samples: u32) {
+///             self.peak.store(0.5f32.to_bits(), Ordering::Relaxed);
+///         }
+///     }
+///
+///     impl SharedData for Meter {
+///         const URI: &'static [u8] = b"http://example.org/Meter\0";
+///         type Data = AtomicU32;
+///
+///         fn shared_data(&self) -> Arc<AtomicU32> {
+///             self.peak.clone()
+///         }
+///     }
+pub trait SharedData: Plugin {
+    /// The URI of the plugin, as a null-terminated bytes-array.
+    ///
+    /// UIs compare this URI with the URI of the plugin they were created for before they access
+    /// the instance.
+    const URI: &'static [u8];
+
+    /// The type of the shared data.
+    type Data: Send + Sync + 'static;
+
+    /// Return a pointer to the shared data.
+    fn shared_data(&self) -> Arc<Self::Data>;
+}
@@ -4,9 +4,19 @@
 //! 
 //! This is a frozen prototype and therefore, development of this crate will not continue here. Further
 //! development continues as [rust-lv2](https://github.com/rust-dsp/rust-lv2).
+pub mod dyn_manifest;
 mod feature;
+pub mod inline_display;
+pub mod instance_access;
+pub mod library;
 mod plugin;
+pub mod port_groups;
+pub mod port_props;
 pub mod ports;
+pub mod presets;
+pub mod resize_port;
+pub mod ttl;
+pub mod units;
 pub mod uris;
 
 pub use feature::{Feature, FeaturesList};
@@ -111,3 +121,196 @@ macro_rules! lv2_main {
         }
     };
 }
+
+/// Create lv2 export functions for a dynamic manifest.
+///
+/// This macro takes a struct that implements
+/// [`DynManifest`](dyn_manifest/trait.DynManifest.html) and creates the
+/// `lv2_dyn_manifest_open`, `lv2_dyn_manifest_get_subjects`, `lv2_dyn_manifest_get_data` and
+/// `lv2_dyn_manifest_close` functions as well as the `lv2_descriptor` function. The descriptors
+/// are stored in a static [`DescriptorTable`](struct.DescriptorTable.html) named `DESCRIPTORS`,
+/// which is filled by [`register_plugins`](dyn_manifest/trait.DynManifest.html#tymethod.register_plugins).
+/// Therefore, this macro can't be used together with [`lv2_main`](macro.lv2_main.html); Plugins
+/// that are known at compile time should be registered by the generator too.
+///
+/// It needs two arguments:
+/// * The namespace of the `lv2rs-core` crate.
+/// * The struct type that should be used as the manifest generator.
+///
+///       extern crate lv2rs_core as lv2core;
+///       use lv2core::dyn_manifest::DynManifest;
+///       use lv2core::{ttl, DescriptorTable};
+///       use std::ffi::{CStr, CString};
+///
+///       struct Script {}
+///
+///       impl lv2core::Plugin for Script {
+///           fn instantiate(
+///               _descriptor: &lv2core::Descriptor,
+///               _rate: f64,
+///               _bundle_path: &CStr,
+///               _features: Option<&lv2core::FeaturesList>
+///           ) -> Option<Self> {
+///               Some(Self {})
+///           }
+///
+///           fn connect_port(&mut self, _port: u32, _data: *mut ()) {}
+///
+///           fn run(&mut self, _n_samples: u32) {}
+///       }
+///
+///       const SCRIPT_URI: &str = "http://example.org/scripts/gain";
+///
+///       struct Scripts {}
+///
+///       impl DynManifest for Scripts {
+///           fn register_plugins(descriptors: &DescriptorTable) {
+///               descriptors.register::<Script>(&CString::new(SCRIPT_URI).unwrap());
+///           }
+///
+///           fn open(_features: Option<&lv2core::FeaturesList>) -> Option<Self> {
+///               Some(Self {})
+///           }
+///
+///           fn subjects(&mut self) -> Vec<ttl::Node> {
+///               let mut node = ttl::Node::new(ttl::uri(SCRIPT_URI));
+///               node.add("a", "lv2:Plugin");
+///               vec![node]
+///           }
+///
+///           fn data(&mut self, uri: &CStr) -> Option<Vec<ttl::Node>> {
+///               if uri.to_bytes() != SCRIPT_URI.as_bytes() {
+///                   return None;
+///               }
+///               let mut node = ttl::Node::new(ttl::uri(SCRIPT_URI));
+///               node.add("a", "lv2:Plugin").add("lv2:name", ttl::string("Gain"));
+///               Some(vec![node])
+///           }
+///       }
+///
+///       lv2core::lv2_dyn_manifest!(lv2core, Scripts);
+///
+#[macro_export]
+macro_rules! lv2_dyn_manifest {
+    ($c:ident, $s:ty) => {
+        static DESCRIPTORS: $c::DescriptorTable = $c::DescriptorTable::new();
+
+        #[no_mangle]
+        pub unsafe extern "C" fn lv2_descriptor(index: u32) -> *const $c::Descriptor {
+            DESCRIPTORS.initialize(<$s as $c::dyn_manifest::DynManifest>::register_plugins);
+            match DESCRIPTORS.get(index) {
+                Some(descriptor) => descriptor,
+                None => std::ptr::null(),
+            }
+        }
+
+        #[no_mangle]
+        pub unsafe extern "C" fn lv2_dyn_manifest_open(
+            handle: *mut $c::dyn_manifest::DynManifestHandle,
+            features: *const *const $c::Feature,
+        ) -> std::os::raw::c_int {
+            DESCRIPTORS.initialize(<$s as $c::dyn_manifest::DynManifest>::register_plugins);
+            $c::dyn_manifest::open::<$s>(handle, features)
+        }
+
+        #[no_mangle]
+        pub unsafe extern "C" fn lv2_dyn_manifest_get_subjects(
+            handle: $c::dyn_manifest::DynManifestHandle,
+            stream: *mut $c::dyn_manifest::File,
+        ) -> std::os::raw::c_int {
+            $c::dyn_manifest::get_subjects::<$s>(handle, stream)
+        }
+
+        #[no_mangle]
+        pub unsafe extern "C" fn lv2_dyn_manifest_get_data(
+            handle: $c::dyn_manifest::DynManifestHandle,
+            stream: *mut $c::dyn_manifest::File,
+            uri: *const std::os::raw::c_char,
+        ) -> std::os::raw::c_int {
+            $c::dyn_manifest::get_data::<$s>(handle, stream, uri)
+        }
+
+        #[no_mangle]
+        pub unsafe extern "C" fn lv2_dyn_manifest_close(
+            handle: $c::dyn_manifest::DynManifestHandle,
+        ) {
+            $c::dyn_manifest::close::<$s>(handle);
+        }
+    };
+}
+
+/// Create lv2 export functions for a library with shared setup.
+///
+/// This macro takes a struct that implements [`Library`](library/trait.Library.html) and creates
+/// the `lv2_lib_descriptor` function. The library is stored in a static
+/// [`LibraryContext`](library/struct.LibraryContext.html) named `LIBRARY`, from which the plugins
+/// get the library in their `instantiate` function. The plugins are registered by
+/// [`register_plugins`](library/trait.Library.html#tymethod.register_plugins). Hosts that support
+/// `lv2_lib_descriptor` don't look for `lv2_descriptor` and therefore, this macro can't be used
+/// together with [`lv2_main`](macro.lv2_main.html).
+///
+/// It needs two arguments:
+/// * The namespace of the `lv2rs-core` crate.
+/// * The struct type that should be used as the library.
+///
+///       extern crate lv2rs_core as lv2core;
+///       use lv2core::library::Library;
+///       use lv2core::DescriptorTable;
+///       use std::ffi::CStr;
+///       use std::sync::Arc;
+///
+///       /// Wavetables that are loaded once and shared by all oscillators.
+///       struct Wavetables {
+///           tables: Vec<Vec<f32>>,
+///       }
+///
+///       impl Library for Wavetables {
+///           fn instantiate(
+///               _bundle_path: &CStr,
+///               _features: Option<&lv2core::FeaturesList>,
+///           ) -> Option<Self> {
+///               // A real library would load the tables from the bundle.
+///               Some(Self { tables: vec![vec![0.0; 2048]; 16] })
+///           }
+///
+///           fn register_plugins(&self, descriptors: &DescriptorTable) {
+///               let uri = CStr::from_bytes_with_nul(b"http://example.org/oscillator\0").unwrap();
+///               descriptors.register::<Oscillator>(uri);
+///           }
+///       }
+///
+///       struct Oscillator {
+///           wavetables: Arc<Wavetables>,
+///       }
+///
+///       impl lv2core::Plugin for Oscillator {
+///           fn instantiate(
+///               _descriptor: &lv2core::Descriptor,
+///               _rate: f64,
+///               _bundle_path: &CStr,
+///               _features: Option<&lv2core::FeaturesList>
+///           ) -> Option<Self> {
+///               Some(Self { wavetables: LIBRARY.get()? })
+///           }
+///
+///           fn connect_port(&mut self, _port: u32, _data: *mut ()) {}
+///
+///           fn run(&mut self, _n_samples: u32) {}
+///       }
+///
+///       lv2core::lv2_lib_main!(lv2core, Wavetables);
+///
+#[macro_export]
+macro_rules! lv2_lib_main {
+    ($c:ident, $l:ty) => {
+        static LIBRARY: $c::library::LibraryContext<$l> = $c::library::LibraryContext::new();
+
+        #[no_mangle]
+        pub unsafe extern "C" fn lv2_lib_descriptor(
+            bundle_path: *const std::os::raw::c_char,
+            features: *const *const $c::Feature,
+        ) -> *const $c::library::LibDescriptor {
+            $c::library::lib_descriptor::<$l>(&LIBRARY, bundle_path, features)
+        }
+    };
+}
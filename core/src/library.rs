@@ -0,0 +1,200 @@
+//! Library-wide setup that is shared by all plugins.
+//!
+//! Usually, a library exports its plugins with the `lv2_descriptor` function and every plugin
+//! instance does its setup on its own. Some libraries have a lot of setup that is the same for all
+//! plugins, for example loading a large wavetable bank. These libraries export the
+//! `lv2_lib_descriptor` function instead, which is called once per bundle with the bundle path and
+//! the features of the host, before any plugin is listed.
+//!
+//! The setup is done by a type implementing the [`Library`](trait.Library.html) trait. The
+//! [`lv2_lib_main`](../macro.lv2_lib_main.html) macro stores it in a static
+//! [`LibraryContext`](struct.LibraryContext.html) named `LIBRARY`, from which the plugins
+//! retrieve a reference-counted pointer to it in their `instantiate` function. The library is
+//! released when the host cleans up the last library descriptor and no plugin instance uses it
+//! anymore.
+use crate::feature::Feature;
+use crate::plugin::{Descriptor, DescriptorTable};
+use crate::FeaturesList;
+use std::ffi::CStr;
+use std::os::raw::*;
+use std::sync::{Arc, Mutex};
+
+/// Handle of a library descriptor.
+pub type LibHandle = *mut c_void;
+
+/// Raw representation of a library descriptor.
+///
+/// This type is `repr(C)` and is used to interpret raw data.
+#[repr(C)]
+pub struct LibDescriptor {
+    /// Opaque data of the library, which has to be passed to the functions of the descriptor.
+    pub handle: LibHandle,
+    /// The size of this struct in bytes.
+    pub size: u32,
+    /// Release the library descriptor. It must not be used afterwards.
+    pub cleanup: unsafe extern "C" fn(handle: LibHandle),
+    /// Return the descriptor of the plugin with the given index, or null if there is none.
+    pub get_plugin: unsafe extern "C" fn(handle: LibHandle, index: u32) -> *const Descriptor,
+}
+
+/// The shared setup of a library.
+///
+/// The library is shared by all plugin instances, which may run in different threads, and
+/// therefore it has to be `Send` and `Sync`.
+pub trait Library: Sized + Send + Sync + 'static {
+    /// Create the library for the bundle at the given path.
+    ///
+    /// If the library can't be created, `None` should be returned.
+    fn instantiate(bundle_path: &CStr, features: Option<&FeaturesList>) -> Option<Self>;
+
+    /// Register the descriptors of all plugins of the library.
+    ///
+    /// This method is called once, after the library was created for the first time.
+    fn register_plugins(&self, descriptors: &DescriptorTable);
+}
+
+/// Static storage of a library and its plugin descriptors.
+pub struct LibraryContext<L: Library> {
+    library: Mutex<Option<(Arc<L>, usize)>>,
+    descriptors: DescriptorTable,
+}
+
+impl<L: Library> LibraryContext<L> {
+    /// Create a new, empty context.
+    pub const fn new() -> Self {
+        Self {
+            library: Mutex::new(None),
+            descriptors: DescriptorTable::new(),
+        }
+    }
+
+    /// Return the library, if it is currently loaded.
+    ///
+    /// Plugins call this method in their `instantiate` function and keep the returned pointer.
+    /// `None` is returned if the host didn't load the library via `lv2_lib_descriptor`.
+    pub fn get(&self) -> Option<Arc<L>> {
+        let library = self.library.lock().unwrap();
+        library.as_ref().map(|(library, _)| library.clone())
+    }
+
+    /// Return the descriptors of the plugins.
+    pub fn descriptors(&self) -> &DescriptorTable {
+        &self.descriptors
+    }
+
+    /// Load the library if necessary and count the new user.
+    fn acquire(&self, bundle_path: &CStr, features: Option<&FeaturesList>) -> Option<Arc<L>> {
+        let mut guard = self.library.lock().unwrap();
+        let library = match guard.as_mut() {
+            Some((library, users)) => {
+                *users += 1;
+                library.clone()
+            }
+            None => {
+                let library = Arc::new(L::instantiate(bundle_path, features)?);
+                *guard = Some((library.clone(), 1));
+                library
+            }
+        };
+        drop(guard);
+        self.descriptors
+            .initialize(|descriptors| library.register_plugins(descriptors));
+        Some(library)
+    }
+
+    /// Count a user less and drop the library's pointer if it was the last one.
+    fn release(&self) {
+        let mut library = self.library.lock().unwrap();
+        let last = match library.as_mut() {
+            Some((_, users)) => {
+                *users -= 1;
+                *users == 0
+            }
+            None => false,
+        };
+        if last {
+            *library = None;
+        }
+    }
+}
+
+impl<L: Library> Default for LibraryContext<L> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A library descriptor together with the context it belongs to.
+#[repr(C)]
+struct LibInstance<L: Library> {
+    descriptor: LibDescriptor,
+    context: &'static LibraryContext<L>,
+}
+
+/// Helper function for the `lv2_lib_descriptor` call.
+///
+/// This function takes the raw parameters provided by the C API and turns them into safe Rust data
+/// types. Only functions generated by the `lv2_lib_main` macro should call this function, any
+/// other must not.
+///
+/// # Safety
+///
+/// `bundle_path` has to be a null-terminated string or null and `features` has to be a
+/// null-terminated array of valid features or null.
+pub unsafe fn lib_descriptor<L: Library>(
+    context: &'static LibraryContext<L>,
+    bundle_path: *const c_char,
+    features: *const *const Feature,
+) -> *const LibDescriptor {
+    if bundle_path.is_null() {
+        return std::ptr::null();
+    }
+    let bundle_path = CStr::from_ptr(bundle_path);
+    let features = if features.is_null() {
+        None
+    } else {
+        let mut length = 0;
+        while !(*features.add(length)).is_null() {
+            length += 1;
+        }
+        Some(std::slice::from_raw_parts(
+            features as *const &'static Feature,
+            length,
+        ))
+    };
+    if context.acquire(bundle_path, features).is_none() {
+        return std::ptr::null();
+    }
+
+    let instance = Box::leak(Box::new(LibInstance {
+        descriptor: LibDescriptor {
+            handle: std::ptr::null_mut(),
+            size: std::mem::size_of::<LibDescriptor>() as u32,
+            cleanup: lib_cleanup::<L>,
+            get_plugin: lib_get_plugin::<L>,
+        },
+        context,
+    }));
+    instance.descriptor.handle = instance as *mut LibInstance<L> as LibHandle;
+    &instance.descriptor
+}
+
+unsafe extern "C" fn lib_cleanup<L: Library>(handle: LibHandle) {
+    if !handle.is_null() {
+        let instance = Box::from_raw(handle as *mut LibInstance<L>);
+        instance.context.release();
+    }
+}
+
+unsafe extern "C" fn lib_get_plugin<L: Library>(
+    handle: LibHandle,
+    index: u32,
+) -> *const Descriptor {
+    match (handle as *const LibInstance<L>).as_ref() {
+        Some(instance) => match instance.context.descriptors.get(index) {
+            Some(descriptor) => descriptor,
+            None => std::ptr::null(),
+        },
+        None => std::ptr::null(),
+    }
+}
@@ -1,8 +1,12 @@
 //! General Plugin-related traits and functions.
+use crate::inline_display::{self, InlineDisplay};
+use crate::ports::ParameterOutputPort;
 use crate::{Feature, FeaturesList};
 
+use std::any::TypeId;
 use std::ffi::CStr;
 use std::os::raw::*;
+use std::sync::{Arc, Mutex, Once};
 
 /**
    Plugin Instance Handle.
@@ -194,6 +198,195 @@ pub struct Descriptor {
     pub extension_data: unsafe extern "C" fn(uri: *const c_char) -> *const c_void,
 }
 
+// The descriptor only contains function pointers and a pointer to a static URI, which are never
+// changed after the descriptor was created.
+unsafe impl Send for Descriptor {}
+unsafe impl Sync for Descriptor {}
+
+impl Descriptor {
+    /// Create a descriptor for the plugin type `P` with the given URI.
+    ///
+    /// The [`lv2_main`](macro.lv2_main.html) macro creates the descriptor of its plugin at compile
+    /// time. This function does the same at runtime, which is needed if the URIs of the plugins
+    /// are only known at runtime.
+    pub fn new<P: Plugin>(uri: &'static CStr) -> Self {
+        Self {
+            uri: uri.as_ptr(),
+            instantiate: extern_instantiate::<P>,
+            connect_port: extern_connect_port::<P>,
+            activate: extern_activate::<P>,
+            run: extern_run::<P>,
+            deactivate: extern_deactivate::<P>,
+            cleanup: extern_cleanup::<P>,
+            extension_data: extern_extension_data::<P>,
+        }
+    }
+
+    /// Return the URI of the plugin.
+    pub fn get_uri(&self) -> &CStr {
+        unsafe { CStr::from_ptr(self.uri) }
+    }
+}
+
+unsafe extern "C" fn extern_instantiate<P: Plugin>(
+    descriptor: *const Descriptor,
+    rate: f64,
+    bundle_path: *const c_char,
+    features: *const *const Feature,
+) -> Handle {
+    instantiate::<P>(descriptor, rate, bundle_path, features)
+}
+
+unsafe extern "C" fn extern_connect_port<P: Plugin>(
+    instance: Handle,
+    port: u32,
+    data: *mut c_void,
+) {
+    connect_port::<P>(instance, port, data);
+}
+
+unsafe extern "C" fn extern_activate<P: Plugin>(instance: Handle) {
+    activate::<P>(instance);
+}
+
+unsafe extern "C" fn extern_run<P: Plugin>(instance: Handle, n_samples: u32) {
+    run::<P>(instance, n_samples);
+}
+
+unsafe extern "C" fn extern_deactivate<P: Plugin>(instance: Handle) {
+    deactivate::<P>(instance);
+}
+
+unsafe extern "C" fn extern_cleanup<P: Plugin>(instance: Handle) {
+    cleanup::<P>(instance);
+}
+
+unsafe extern "C" fn extern_extension_data<P: Plugin>(uri: *const c_char) -> *const c_void {
+    extension_data::<P>(uri)
+}
+
+/// A table of plugin descriptors that is filled at runtime.
+///
+/// [`lv2_main`](macro.lv2_main.html) exports a single descriptor that is known at compile time.
+/// Libraries whose plugins are generated at runtime, for example from configuration files, store
+/// their descriptors in a static table instead and look them up by index in their
+/// `lv2_descriptor` function. The table is filled once by the function passed to
+/// [`initialize`](#method.initialize):
+///
+///     extern crate lv2rs_core as core;
+///     use core::DescriptorTable;
+///     use std::ffi::CStr;
+///
+///     struct Script;
+///
+///     impl core::Plugin for Script {
+///         fn instantiate(
+///             descriptor: &core::Descriptor,
+///             _rate: f64,
+///             _bundle_path: &CStr,
+///             _features: Option<&core::FeaturesList>,
+///         ) -> Option<Self> {
+///             // The URI of the descriptor tells which script should be loaded.
+///             let uri = descriptor.get_uri().to_bytes();
+///             assert!(uri.starts_with(b"https://example.org/scripts/"));
+///             Some(Script)
+///         }
+///
+///         fn connect_port(&mut self, _port: u32, _data: *mut ()) {}
+///
+///         fn run(&mut self, _n_samples: u32) {}
+///     }
+///
+///     static DESCRIPTORS: DescriptorTable = DescriptorTable::new();
+///
+///     fn register_scripts(table: &DescriptorTable) {
+///         for name in ["delay", "reverb"].iter() {
+///             let uri = format!("https://example.org/scripts/{}", name);
+///             table.register::<Script>(&std::ffi::CString::new(uri).unwrap());
+///         }
+///     }
+///
+///     DESCRIPTORS.initialize(register_scripts);
+///     assert_eq!(2, DESCRIPTORS.len());
+///     let reverb = DESCRIPTORS.get(1).unwrap();
+///     assert_eq!(b"https://example.org/scripts/reverb", reverb.get_uri().to_bytes());
+pub struct DescriptorTable {
+    init: Once,
+    descriptors: Mutex<Vec<(&'static Descriptor, TypeId)>>,
+}
+
+impl DescriptorTable {
+    /// Create a new, empty table.
+    pub const fn new() -> Self {
+        Self {
+            init: Once::new(),
+            descriptors: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Fill the table with the given function, if this wasn't done yet.
+    ///
+    /// The function is only called by the first call of this method; Every other call waits until
+    /// the table is filled and returns. Therefore, the function must not call `initialize` again.
+    pub fn initialize<F: FnOnce(&Self)>(&self, function: F) {
+        self.init.call_once(|| function(self));
+    }
+
+    /// Add a descriptor for the plugin type `P` with the given URI and return it.
+    ///
+    /// The URI is copied and the descriptor lives as long as the library is loaded, since the host
+    /// may keep pointers to it. If a descriptor with the same URI was already registered for `P`,
+    /// the existing descriptor is returned. If it was registered for another plugin type, `None`
+    /// is returned, since the host would otherwise create instances of the wrong type.
+    pub fn register<P: Plugin + 'static>(&self, uri: &CStr) -> Option<&'static Descriptor> {
+        let mut descriptors = self.descriptors.lock().unwrap();
+        if let Some((descriptor, type_id)) = descriptors.iter().find(|(d, _)| d.get_uri() == uri) {
+            return if *type_id == TypeId::of::<P>() {
+                Some(descriptor)
+            } else {
+                None
+            };
+        }
+        let uri: &'static CStr = Box::leak(uri.to_owned().into_boxed_c_str());
+        let descriptor: &'static Descriptor = Box::leak(Box::new(Descriptor::new::<P>(uri)));
+        descriptors.push((descriptor, TypeId::of::<P>()));
+        Some(descriptor)
+    }
+
+    /// Return the descriptor with the given index, if there is one.
+    pub fn get(&self, index: u32) -> Option<&'static Descriptor> {
+        let descriptors = self.descriptors.lock().unwrap();
+        descriptors
+            .get(index as usize)
+            .map(|(descriptor, _)| *descriptor)
+    }
+
+    /// Return the descriptor of the plugin with the given URI, if there is one.
+    pub fn find(&self, uri: &CStr) -> Option<&'static Descriptor> {
+        let descriptors = self.descriptors.lock().unwrap();
+        descriptors
+            .iter()
+            .find(|(d, _)| d.get_uri() == uri)
+            .map(|(descriptor, _)| *descriptor)
+    }
+
+    /// Return the number of descriptors in the table.
+    pub fn len(&self) -> usize {
+        self.descriptors.lock().unwrap().len()
+    }
+
+    /// Return `true` if the table contains no descriptors.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Default for DescriptorTable {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// LV2 plugin trait.
 ///
 /// This trait helps you implementing plugins, since it requires you to implement all
@@ -247,6 +440,29 @@ pub trait Plugin {
     /// it somewhere.
     fn run(&mut self, n_samples: u32);
 
+    /// Pass all trigger output ports of the plugin to the visitor.
+    ///
+    /// Output ports with the [`Trigger`](port_props/enum.PortProperty.html#variant.Trigger)
+    /// property signal momentary events. The value of such a port has to stay until the host has
+    /// read it after `run` and then, it has to be reset to its default. Therefore, the ports
+    /// visited by this function are reset right before every call to [`run`](#tymethod.run).
+    ///
+    /// The default implementation visits no ports.
+    fn visit_trigger_ports(&mut self, _visitor: &mut dyn FnMut(&mut ParameterOutputPort)) {}
+
+    /// Return the state of the plugin's inline display.
+    ///
+    /// This function is called once, right after the plugin was instantiated. The returned state
+    /// is drawn by the [`InlineDisplayInterface`](inline_display/struct.InlineDisplayInterface.html)
+    /// in the render thread of the host, which never touches the plugin itself. See the
+    /// [`inline_display`](inline_display/index.html) module for more information.
+    ///
+    /// The default implementation returns `None`, which means that the plugin has no inline
+    /// display.
+    fn inline_display(&self) -> Option<Arc<dyn InlineDisplay>> {
+        None
+    }
+
     /// Deactivate the plugin.
     ///
     /// If your plugin can be turned on or off, you should override this function and destroy the
@@ -326,8 +542,13 @@ pub unsafe fn instantiate<P: Plugin>(
 
     match P::instantiate(descriptor, rate, bundle_path, features) {
         Some(instance) => {
-            let instance = Box::new(instance);
-            Box::leak(instance) as *const P as Handle
+            let instance = Box::leak(Box::new(instance));
+            let display = instance.inline_display();
+            let handle = instance as *const P as Handle;
+            if let Some(display) = display {
+                inline_display::register(handle, display);
+            }
+            handle
         }
         None => std::ptr::null_mut(),
     }
@@ -357,8 +578,12 @@ pub unsafe fn activate<P: Plugin>(instance: Handle) {
 /// This function takes the raw parameters provided by the C API, turns them into safe Rust data
 /// types, and calls the trait's function. Only functions generated by the `lv2_main` should call
 /// this function, any other must not.
+///
+/// Before the plugin runs, its [trigger ports](trait.Plugin.html#method.visit_trigger_ports) are
+/// reset.
 pub unsafe fn run<P: Plugin>(instance: Handle, n_samples: u32) {
     let instance = (instance as *mut P).as_mut().unwrap();
+    instance.visit_trigger_ports(&mut |port| port.reset_trigger());
     instance.run(n_samples);
 }
 
@@ -378,6 +603,7 @@ pub unsafe fn deactivate<P: Plugin>(instance: Handle) {
 /// types, and calls the trait's function. Only functions generated by the `lv2_main` should call
 /// this function, any other must not.
 pub unsafe fn cleanup<P: Plugin>(instance: Handle) {
+    inline_display::unregister(instance);
     core::ptr::drop_in_place(instance as *mut P);
 }
 
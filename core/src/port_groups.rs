@@ -0,0 +1,189 @@
+//! Groups of ports and the designations of their channels.
+//!
+//! A plugin with more than one audio input or output should tell the host how its ports belong
+//! together, for example which two ports form the main stereo input and which port is a sidechain.
+//! This is done with the [port groups extension](http://lv2plug.in/ns/ext/port-groups): Every port
+//! of a group refers to the group and designates its [`Channel`](enum.Channel.html) in it.
+//!
+//! Audio ports are grouped with an [`AudioBus`](../ports/struct.AudioBus.html), which writes the
+//! description of the group as well as the description of its ports.
+use crate::ttl;
+use std::ffi::CStr;
+
+pub const URI: &[u8] = b"http://lv2plug.in/ns/ext/port-groups\0";
+pub const DISCRETE_GROUP_URI: &[u8] = b"http://lv2plug.in/ns/ext/port-groups#DiscreteGroup\0";
+pub const GROUP_URI: &[u8] = b"http://lv2plug.in/ns/ext/port-groups#Group\0";
+pub const INPUT_GROUP_URI: &[u8] = b"http://lv2plug.in/ns/ext/port-groups#InputGroup\0";
+pub const MID_SIDE_GROUP_URI: &[u8] = b"http://lv2plug.in/ns/ext/port-groups#MidSideGroup\0";
+pub const MONO_GROUP_URI: &[u8] = b"http://lv2plug.in/ns/ext/port-groups#MonoGroup\0";
+pub const OUTPUT_GROUP_URI: &[u8] = b"http://lv2plug.in/ns/ext/port-groups#OutputGroup\0";
+pub const STEREO_GROUP_URI: &[u8] = b"http://lv2plug.in/ns/ext/port-groups#StereoGroup\0";
+pub const CENTER_URI: &[u8] = b"http://lv2plug.in/ns/ext/port-groups#center\0";
+pub const CENTER_LEFT_URI: &[u8] = b"http://lv2plug.in/ns/ext/port-groups#centerLeft\0";
+pub const CENTER_RIGHT_URI: &[u8] = b"http://lv2plug.in/ns/ext/port-groups#centerRight\0";
+pub const ELEMENT_URI: &[u8] = b"http://lv2plug.in/ns/ext/port-groups#element\0";
+pub const GROUP_PREDICATE_URI: &[u8] = b"http://lv2plug.in/ns/ext/port-groups#group\0";
+pub const LEFT_URI: &[u8] = b"http://lv2plug.in/ns/ext/port-groups#left\0";
+pub const LOW_FREQUENCY_EFFECTS_URI: &[u8] =
+    b"http://lv2plug.in/ns/ext/port-groups#lowFrequencyEffects\0";
+pub const MAIN_INPUT_URI: &[u8] = b"http://lv2plug.in/ns/ext/port-groups#mainInput\0";
+pub const MAIN_OUTPUT_URI: &[u8] = b"http://lv2plug.in/ns/ext/port-groups#mainOutput\0";
+pub const REAR_CENTER_URI: &[u8] = b"http://lv2plug.in/ns/ext/port-groups#rearCenter\0";
+pub const REAR_LEFT_URI: &[u8] = b"http://lv2plug.in/ns/ext/port-groups#rearLeft\0";
+pub const REAR_RIGHT_URI: &[u8] = b"http://lv2plug.in/ns/ext/port-groups#rearRight\0";
+pub const RIGHT_URI: &[u8] = b"http://lv2plug.in/ns/ext/port-groups#right\0";
+pub const SIDE_URI: &[u8] = b"http://lv2plug.in/ns/ext/port-groups#side\0";
+pub const SIDE_CHAIN_OF_URI: &[u8] = b"http://lv2plug.in/ns/ext/port-groups#sideChainOf\0";
+pub const SIDE_LEFT_URI: &[u8] = b"http://lv2plug.in/ns/ext/port-groups#sideLeft\0";
+pub const SIDE_RIGHT_URI: &[u8] = b"http://lv2plug.in/ns/ext/port-groups#sideRight\0";
+pub const SOURCE_URI: &[u8] = b"http://lv2plug.in/ns/ext/port-groups#source\0";
+pub const SUB_GROUP_OF_URI: &[u8] = b"http://lv2plug.in/ns/ext/port-groups#subGroupOf\0";
+
+/// The designation of a port in a group.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Channel {
+    /// `pg:left`: The left channel of a stereo or surround signal.
+    Left,
+    /// `pg:right`: The right channel of a stereo or surround signal.
+    Right,
+    /// `pg:center`: The center channel of a surround signal or the single channel of a mono
+    /// signal. It's also the mid channel of a mid-side signal.
+    Center,
+    /// `pg:side`: The side channel of a mid-side signal.
+    Side,
+    /// `pg:centerLeft`: The channel between the center and the left channel.
+    CenterLeft,
+    /// `pg:centerRight`: The channel between the center and the right channel.
+    CenterRight,
+    /// `pg:sideLeft`: The left side channel of a surround signal.
+    SideLeft,
+    /// `pg:sideRight`: The right side channel of a surround signal.
+    SideRight,
+    /// `pg:rearLeft`: The left rear channel of a surround signal.
+    RearLeft,
+    /// `pg:rearRight`: The right rear channel of a surround signal.
+    RearRight,
+    /// `pg:rearCenter`: The center rear channel of a surround signal.
+    RearCenter,
+    /// `pg:lowFrequencyEffects`: The subwoofer channel of a surround signal.
+    LowFrequencyEffects,
+}
+
+impl Channel {
+    /// Return the URI of the designation.
+    pub fn get_uri(&self) -> &'static CStr {
+        let uri = match self {
+            Channel::Left => LEFT_URI,
+            Channel::Right => RIGHT_URI,
+            Channel::Center => CENTER_URI,
+            Channel::Side => SIDE_URI,
+            Channel::CenterLeft => CENTER_LEFT_URI,
+            Channel::CenterRight => CENTER_RIGHT_URI,
+            Channel::SideLeft => SIDE_LEFT_URI,
+            Channel::SideRight => SIDE_RIGHT_URI,
+            Channel::RearLeft => REAR_LEFT_URI,
+            Channel::RearRight => REAR_RIGHT_URI,
+            Channel::RearCenter => REAR_CENTER_URI,
+            Channel::LowFrequencyEffects => LOW_FREQUENCY_EFFECTS_URI,
+        };
+        unsafe { CStr::from_bytes_with_nul_unchecked(uri) }
+    }
+
+    /// Return the designation as a prefixed name, e.g. `pg:left`.
+    ///
+    /// The name uses the prefixes of [`ttl::PREFIXES`](../ttl/constant.PREFIXES.html).
+    pub fn prefixed_name(&self) -> String {
+        let uri = self.get_uri().to_str().unwrap();
+        format!("pg:{}", &uri[uri.find('#').unwrap() + 1..])
+    }
+}
+
+/// Return the class of a group with the given channels as a prefixed name.
+///
+/// A single center channel is a `pg:MonoGroup`, left and right channels are a `pg:StereoGroup` and
+/// center and side channels are a `pg:MidSideGroup`. Every other combination is a plain
+/// `pg:Group`.
+pub fn group_class(channels: &[Channel]) -> &'static str {
+    match channels {
+        [Channel::Center] => "pg:MonoGroup",
+        [Channel::Left, Channel::Right] => "pg:StereoGroup",
+        [Channel::Center, Channel::Side] => "pg:MidSideGroup",
+        _ => "pg:Group",
+    }
+}
+
+/// The description of a port group.
+///
+/// Like a plugin, a group is identified by a URI and has a symbol and a human-readable name. A
+/// group may also be the sidechain of another group, which is usually the main input of the
+/// plugin.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PortGroup {
+    uri: String,
+    symbol: String,
+    name: String,
+    side_chain_of: Option<String>,
+}
+
+impl PortGroup {
+    /// Create the description of a group with the given URI, symbol and human-readable name.
+    pub fn new(uri: &str, symbol: &str, name: &str) -> Self {
+        Self {
+            uri: uri.to_owned(),
+            symbol: symbol.to_owned(),
+            name: name.to_owned(),
+            side_chain_of: None,
+        }
+    }
+
+    /// Declare that the group is the sidechain of the group with the given URI.
+    ///
+    /// The ports of a sidechain group are marked with the `lv2:isSideChain` property.
+    pub fn with_side_chain_of(mut self, group_uri: &str) -> Self {
+        self.side_chain_of = Some(group_uri.to_owned());
+        self
+    }
+
+    /// Return the URI of the group.
+    pub fn uri(&self) -> &str {
+        &self.uri
+    }
+
+    /// Return the symbol of the group.
+    pub fn symbol(&self) -> &str {
+        &self.symbol
+    }
+
+    /// Return the human-readable name of the group.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Return the URI of the group this group is a sidechain of, if it is one.
+    pub fn side_chain_of(&self) -> Option<&str> {
+        self.side_chain_of.as_deref()
+    }
+
+    /// Return `true` if the group is the sidechain of another group.
+    pub fn is_side_chain(&self) -> bool {
+        self.side_chain_of.is_some()
+    }
+
+    /// Create the Turtle description of the group.
+    ///
+    /// The group is described as a named node with the given classes, e.g. `pg:StereoGroup` and
+    /// `pg:InputGroup`. The generated statements use the prefixes of
+    /// [`ttl::PREFIXES`](../ttl/constant.PREFIXES.html).
+    pub fn to_node(&self, classes: &[&str]) -> ttl::Node {
+        let mut node = ttl::Node::new(ttl::uri(&self.uri));
+        for class in classes {
+            node.add("a", *class);
+        }
+        node.add("lv2:symbol", ttl::string(&self.symbol))
+            .add("lv2:name", ttl::string(&self.name));
+        if let Some(main) = &self.side_chain_of {
+            node.add("pg:sideChainOf", ttl::uri(main));
+        }
+        node
+    }
+}
@@ -0,0 +1,91 @@
+//! Properties of ports.
+//!
+//! Port properties tell the host and the plugin how the values of a port should be treated, for
+//! example that a port only accepts integers or that it is a switch. Some of them are part of the
+//! LV2 core, the others are part of the
+//! [port properties extension](http://lv2plug.in/ns/ext/port-props).
+//!
+//! The properties of a port are declared in its [`PortMetadata`](../ports/struct.PortMetadata.html),
+//! which writes them to the port's description and applies them to the values read from and
+//! written to the port.
+use crate::uris;
+use std::ffi::CStr;
+
+pub const URI: &[u8] = b"http://lv2plug.in/ns/ext/port-props\0";
+pub const CAUSES_ARTIFACTS_URI: &[u8] = b"http://lv2plug.in/ns/ext/port-props#causesArtifacts\0";
+pub const CONTINUOUS_CV_URI: &[u8] = b"http://lv2plug.in/ns/ext/port-props#continuousCV\0";
+pub const DISCRETE_CV_URI: &[u8] = b"http://lv2plug.in/ns/ext/port-props#discreteCV\0";
+pub const DISPLAY_PRIORITY_URI: &[u8] = b"http://lv2plug.in/ns/ext/port-props#displayPriority\0";
+pub const EXPENSIVE_URI: &[u8] = b"http://lv2plug.in/ns/ext/port-props#expensive\0";
+pub const HAS_STRICT_BOUNDS_URI: &[u8] = b"http://lv2plug.in/ns/ext/port-props#hasStrictBounds\0";
+pub const LOGARITHMIC_URI: &[u8] = b"http://lv2plug.in/ns/ext/port-props#logarithmic\0";
+pub const NOT_AUTOMATIC_URI: &[u8] = b"http://lv2plug.in/ns/ext/port-props#notAutomatic\0";
+pub const NOT_ON_GUI_URI: &[u8] = b"http://lv2plug.in/ns/ext/port-props#notOnGUI\0";
+pub const RANGE_STEPS_URI: &[u8] = b"http://lv2plug.in/ns/ext/port-props#rangeSteps\0";
+pub const SUPPORTS_STRICT_BOUNDS_URI: &[u8] =
+    b"http://lv2plug.in/ns/ext/port-props#supportsStrictBounds\0";
+pub const TRIGGER_URI: &[u8] = b"http://lv2plug.in/ns/ext/port-props#trigger\0";
+
+/// A property of a port.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PortProperty {
+    /// `lv2:connectionOptional`: The port may be left unconnected by the host.
+    ConnectionOptional,
+    /// `lv2:integer`: The port only takes integer values.
+    Integer,
+    /// `lv2:toggled`: The port is a switch, where zero is "off" and every other value is "on".
+    Toggled,
+    /// `lv2:enumeration`: The port only takes the values of its scale points.
+    Enumeration,
+    /// `lv2:isSideChain`: The port is a sidechain input, which isn't part of the main signal path.
+    IsSideChain,
+    /// `pprops:logarithmic`: The values of the port should be presented on a logarithmic scale.
+    Logarithmic,
+    /// `pprops:trigger`: The port signals a momentary event and is reset to its default value
+    /// after every `run` call. Input triggers are reset by the host, output triggers are reset
+    /// before the next `run` call if the plugin lists them in
+    /// [`Plugin::visit_trigger_ports`](../trait.Plugin.html#method.visit_trigger_ports).
+    Trigger,
+    /// `pprops:notOnGUI`: The port should not be displayed by generic UIs.
+    NotOnGUI,
+    /// `pprops:expensive`: Changing the value of the port is expensive.
+    Expensive,
+    /// `pprops:causesArtifacts`: Changing the value of the port may cause audible artifacts.
+    CausesArtifacts,
+    /// `pprops:hasStrictBounds`: The values of the port must stay within its range.
+    HasStrictBounds,
+}
+
+impl PortProperty {
+    /// Return the URI of the property.
+    pub fn get_uri(&self) -> &'static CStr {
+        let uri = match self {
+            PortProperty::ConnectionOptional => uris::CONNECTION_OPTIONAL_URI,
+            PortProperty::Integer => uris::INTEGER_URI,
+            PortProperty::Toggled => uris::TOGGLED_URI,
+            PortProperty::Enumeration => uris::ENUMERATION_URI,
+            PortProperty::IsSideChain => uris::IS_SIDE_CHAIN_URI,
+            PortProperty::Logarithmic => LOGARITHMIC_URI,
+            PortProperty::Trigger => TRIGGER_URI,
+            PortProperty::NotOnGUI => NOT_ON_GUI_URI,
+            PortProperty::Expensive => EXPENSIVE_URI,
+            PortProperty::CausesArtifacts => CAUSES_ARTIFACTS_URI,
+            PortProperty::HasStrictBounds => HAS_STRICT_BOUNDS_URI,
+        };
+        unsafe { CStr::from_bytes_with_nul_unchecked(uri) }
+    }
+
+    /// Return the property as a prefixed name, e.g. `lv2:integer` or `pprops:trigger`.
+    ///
+    /// The name uses the prefixes of [`ttl::PREFIXES`](../ttl/constant.PREFIXES.html).
+    pub fn prefixed_name(&self) -> String {
+        let uri = self.get_uri().to_str().unwrap();
+        let (namespace, name) = uri.split_at(uri.find('#').unwrap() + 1);
+        let prefix = if namespace == "http://lv2plug.in/ns/lv2core#" {
+            "lv2"
+        } else {
+            "pprops"
+        };
+        format!("{}:{}", prefix, name)
+    }
+}
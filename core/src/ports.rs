@@ -7,6 +7,14 @@
 //! You should use these wrappers in your plugin struct, since they clearly communicate what type of
 //! data they contain. If you only store raw pointers to the ports, you can not tell an
 //! audio port from a parameter port only looking at the type, for example.
+//!
+//! Parameter ports may also carry their [`PortMetadata`](struct.PortMetadata.html), which is used
+//! to generate their part of the plugin's Turtle description. Audio ports that belong together,
+//! like the two channels of a stereo input, are bundled in an [`AudioBus`](struct.AudioBus.html).
+use crate::port_groups::{self, Channel, PortGroup};
+use crate::port_props::PortProperty;
+use crate::ttl;
+use crate::units::Unit;
 
 /// Wrapper for raw audio input lists.
 pub struct AudioInputPort {
@@ -42,6 +50,12 @@ impl AudioInputPort {
     }
 }
 
+impl Default for AudioInputPort {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// Wrapper for raw audio output lists.
 pub struct AudioOutputPort {
     raw: *mut f32,
@@ -76,9 +90,297 @@ impl AudioOutputPort {
     }
 }
 
+impl Default for AudioOutputPort {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Errors that may occur when accessing a port.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PortError {
+    /// The port wasn't connected by the host, although it isn't optional.
+    NotConnected,
+}
+
+/// The description of a port.
+///
+/// The Turtle description of a plugin has to match its code, which is hard to maintain by hand.
+/// Therefore, the index, symbol, name, range, unit and [properties](../port_props/index.html) of a
+/// port can be declared in the code and written to the description with
+/// [`to_node`](#method.to_node).
+///
+/// The properties are also applied at runtime: Parameter ports with metadata restrict the values
+/// they read and write according to their properties, reset trigger output ports and report
+/// whether an unconnected port is an error or not.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PortMetadata {
+    index: u32,
+    symbol: String,
+    name: String,
+    range: Option<(f32, f32, f32)>,
+    unit: Option<Unit>,
+    properties: Vec<PortProperty>,
+    scale_points: Vec<(String, f32)>,
+    range_steps: Option<u32>,
+    minimum_size: Option<usize>,
+}
+
+impl PortMetadata {
+    /// Create the metadata of a port with the given index, symbol and human-readable name.
+    pub fn new(index: u32, symbol: &str, name: &str) -> Self {
+        Self {
+            index,
+            symbol: symbol.to_owned(),
+            name: name.to_owned(),
+            range: None,
+            unit: None,
+            properties: Vec::new(),
+            scale_points: Vec::new(),
+            range_steps: None,
+            minimum_size: None,
+        }
+    }
+
+    /// Declare the default value and the range of the port.
+    pub fn with_range(mut self, default: f32, minimum: f32, maximum: f32) -> Self {
+        self.range = Some((default, minimum, maximum));
+        self
+    }
+
+    /// Declare the unit of the port's values.
+    pub fn with_unit(mut self, unit: Unit) -> Self {
+        self.unit = Some(unit);
+        self
+    }
+
+    /// Declare a property of the port.
+    pub fn with_property(mut self, property: PortProperty) -> Self {
+        if !self.properties.contains(&property) {
+            self.properties.push(property);
+        }
+        self
+    }
+
+    /// Declare a named value of the port.
+    ///
+    /// If the port has the `Enumeration` property, it only takes the values of its scale points.
+    pub fn with_scale_point(mut self, label: &str, value: f32) -> Self {
+        self.scale_points.push((label.to_owned(), value));
+        self
+    }
+
+    /// Declare the number of steps a host should use to change the value of the port.
+    pub fn with_range_steps(mut self, steps: u32) -> Self {
+        self.range_steps = Some(steps);
+        self
+    }
+
+    /// Declare the minimum size of the port's buffer, in bytes.
+    ///
+    /// This is used by atom ports that need more space than the host provides by default. If the
+    /// size isn't known in advance, the buffer can also be grown at runtime with the
+    /// [resize-port feature](../resize_port/index.html).
+    pub fn with_minimum_size(mut self, size: usize) -> Self {
+        self.minimum_size = Some(size);
+        self
+    }
+
+    /// Return the index of the port.
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    /// Return the symbol of the port.
+    pub fn symbol(&self) -> &str {
+        &self.symbol
+    }
+
+    /// Return the human-readable name of the port.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Return the default value, the minimum and the maximum of the port, if they were declared.
+    pub fn range(&self) -> Option<(f32, f32, f32)> {
+        self.range
+    }
+
+    /// Return the unit of the port, if it was declared.
+    pub fn unit(&self) -> Option<&Unit> {
+        self.unit.as_ref()
+    }
+
+    /// Return the properties of the port.
+    pub fn properties(&self) -> &[PortProperty] {
+        self.properties.as_slice()
+    }
+
+    /// Return `true` if the port has the given property.
+    pub fn has_property(&self, property: PortProperty) -> bool {
+        self.properties.contains(&property)
+    }
+
+    /// Iterate over the labels and values of the port's scale points.
+    pub fn scale_points(&self) -> impl Iterator<Item = (&str, f32)> {
+        self.scale_points
+            .iter()
+            .map(|(label, value)| (label.as_str(), *value))
+    }
+
+    /// Return the number of steps of the port, if it was declared.
+    pub fn range_steps(&self) -> Option<u32> {
+        self.range_steps
+    }
+
+    /// Return the minimum size of the port's buffer, if it was declared.
+    pub fn minimum_size(&self) -> Option<usize> {
+        self.minimum_size
+    }
+
+    /// Restrict a value according to the properties of the port.
+    ///
+    /// Values of ports with strict bounds are clamped into the range, values of enumerations are
+    /// replaced with the nearest scale point, values of integer ports are rounded and values of
+    /// toggled ports are replaced with either `0.0` or `1.0`.
+    pub fn apply_properties(&self, mut value: f32) -> f32 {
+        if let (true, Some((_, minimum, maximum))) =
+            (self.has_property(PortProperty::HasStrictBounds), self.range)
+        {
+            value = value.max(minimum).min(maximum);
+        }
+        if self.has_property(PortProperty::Enumeration) {
+            let nearest = self
+                .scale_points
+                .iter()
+                .map(|(_, point)| *point)
+                .min_by(|a, b| {
+                    (a - value)
+                        .abs()
+                        .partial_cmp(&(b - value).abs())
+                        .unwrap_or(std::cmp::Ordering::Equal)
+                });
+            if let Some(nearest) = nearest {
+                value = nearest;
+            }
+        }
+        if self.has_property(PortProperty::Integer) {
+            value = value.round();
+        }
+        if self.has_property(PortProperty::Toggled) {
+            value = if value > 0.0 { 1.0 } else { 0.0 };
+        }
+        value
+    }
+
+    /// Map a value of the port to the interval from zero to one.
+    ///
+    /// This is the position of the value on a generic slider: The mapping is logarithmic if the
+    /// port has the `Logarithmic` property and a positive range, and the position is snapped to
+    /// the declared number of steps. `None` is returned if the port has no range or if the
+    /// minimum isn't smaller than the maximum, since there is no slider to place the value on.
+    pub fn normalize(&self, value: f32) -> Option<f32> {
+        let (_, minimum, maximum) = self.range?;
+        if maximum <= minimum {
+            return None;
+        }
+        let value = value.max(minimum).min(maximum);
+        let position = if self.is_logarithmic() {
+            (value / minimum).ln() / (maximum / minimum).ln()
+        } else {
+            (value - minimum) / (maximum - minimum)
+        };
+        Some(self.snap(position))
+    }
+
+    /// Map a position in the interval from zero to one to a value of the port.
+    ///
+    /// This is the inverse of [`normalize`](#method.normalize).
+    pub fn denormalize(&self, position: f32) -> Option<f32> {
+        let (_, minimum, maximum) = self.range?;
+        let position = self.snap(position.max(0.0).min(1.0));
+        if self.is_logarithmic() {
+            Some(minimum * (maximum / minimum).powf(position))
+        } else {
+            Some(minimum + position * (maximum - minimum))
+        }
+    }
+
+    fn is_logarithmic(&self) -> bool {
+        match self.range {
+            Some((_, minimum, maximum)) => {
+                self.has_property(PortProperty::Logarithmic) && minimum > 0.0 && maximum > minimum
+            }
+            None => false,
+        }
+    }
+
+    fn snap(&self, position: f32) -> f32 {
+        match self.range_steps {
+            Some(steps) if steps > 1 => {
+                let intervals = (steps - 1) as f32;
+                (position * intervals).round() / intervals
+            }
+            _ => position,
+        }
+    }
+
+    /// Create the Turtle description of the port.
+    ///
+    /// The port is described as a blank node with the given classes, e.g. `lv2:InputPort`, which
+    /// should be added to the plugin with the `lv2:port` predicate. The generated statements use
+    /// the prefixes of [`ttl::PREFIXES`](../ttl/constant.PREFIXES.html).
+    pub fn to_node(&self, classes: &[&str]) -> ttl::Node {
+        let mut node = ttl::Node::blank();
+        for class in classes {
+            node.add("a", *class);
+        }
+        node.add("lv2:index", ttl::integer(i64::from(self.index)))
+            .add("lv2:symbol", ttl::string(&self.symbol))
+            .add("lv2:name", ttl::string(&self.name));
+        if let Some((default, minimum, maximum)) = self.range {
+            node.add("lv2:default", ttl::float(f64::from(default)))
+                .add("lv2:minimum", ttl::float(f64::from(minimum)))
+                .add("lv2:maximum", ttl::float(f64::from(maximum)));
+        }
+        if let Some(unit) = &self.unit {
+            unit.add_to_node(&mut node);
+        }
+        for property in self.properties.iter() {
+            node.add("lv2:portProperty", property.prefixed_name());
+        }
+        if let Some(steps) = self.range_steps {
+            node.add("pprops:rangeSteps", ttl::integer(i64::from(steps)));
+        }
+        if let Some(size) = self.minimum_size {
+            node.add("rsz:minimumSize", ttl::integer(size as i64));
+        }
+        for (label, value) in self.scale_points.iter() {
+            let mut scale_point = ttl::Node::blank();
+            scale_point
+                .add("rdfs:label", ttl::string(label))
+                .add("rdf:value", ttl::float(f64::from(*value)));
+            node.add_node("lv2:scalePoint", scale_point);
+        }
+        node
+    }
+
+    fn is_optional(&self) -> bool {
+        self.has_property(PortProperty::ConnectionOptional)
+    }
+
+    fn trigger_default(&self) -> Option<f32> {
+        match self.range {
+            Some((default, _, _)) if self.has_property(PortProperty::Trigger) => Some(default),
+            _ => None,
+        }
+    }
+}
+
 /// Wrapper for raw parameter inputs.
 pub struct ParameterInputPort {
     raw: *const f32,
+    metadata: Option<PortMetadata>,
 }
 
 impl ParameterInputPort {
@@ -86,9 +388,30 @@ impl ParameterInputPort {
     pub fn new() -> Self {
         Self {
             raw: std::ptr::null(),
+            metadata: None,
+        }
+    }
+
+    /// Create a new instance with metadata that points to null.
+    pub fn with_metadata(metadata: PortMetadata) -> Self {
+        Self {
+            raw: std::ptr::null(),
+            metadata: Some(metadata),
         }
     }
 
+    /// Return the metadata of the port, if it has any.
+    pub fn metadata(&self) -> Option<&PortMetadata> {
+        self.metadata.as_ref()
+    }
+
+    /// Create the Turtle description of the port from its metadata.
+    pub fn to_node(&self) -> Option<ttl::Node> {
+        self.metadata
+            .as_ref()
+            .map(|metadata| metadata.to_node(&["lv2:InputPort", "lv2:ControlPort"]))
+    }
+
     /// Set the internal data pointer.
     ///
     /// This function should only be called by a plugin's `connect_port` function.
@@ -102,11 +425,30 @@ impl ParameterInputPort {
     pub unsafe fn get(&self) -> Option<&f32> {
         self.raw.as_ref()
     }
+
+    /// Read the value of the port, restricted by the properties of its metadata.
+    ///
+    /// If the port isn't connected, `Ok(None)` is returned for connection-optional ports and
+    /// `Err(PortError::NotConnected)` for all other ports, including ports without metadata.
+    ///
+    /// # Safety
+    ///
+    /// The port has to be connected to a valid value or to null, which is the case during and
+    /// between the `run` calls of a plugin.
+    pub unsafe fn value(&self) -> Result<Option<f32>, PortError> {
+        match (self.raw.as_ref(), &self.metadata) {
+            (Some(value), Some(metadata)) => Ok(Some(metadata.apply_properties(*value))),
+            (Some(value), None) => Ok(Some(*value)),
+            (None, Some(metadata)) if metadata.is_optional() => Ok(None),
+            (None, _) => Err(PortError::NotConnected),
+        }
+    }
 }
 
 /// Safer wrapper for raw parameter outputs.
 pub struct ParameterOutputPort {
     raw: *mut f32,
+    metadata: Option<PortMetadata>,
 }
 
 impl ParameterOutputPort {
@@ -114,9 +456,30 @@ impl ParameterOutputPort {
     pub fn new() -> Self {
         Self {
             raw: std::ptr::null_mut(),
+            metadata: None,
+        }
+    }
+
+    /// Create a new instance with metadata that points to null.
+    pub fn with_metadata(metadata: PortMetadata) -> Self {
+        Self {
+            raw: std::ptr::null_mut(),
+            metadata: Some(metadata),
         }
     }
 
+    /// Return the metadata of the port, if it has any.
+    pub fn metadata(&self) -> Option<&PortMetadata> {
+        self.metadata.as_ref()
+    }
+
+    /// Create the Turtle description of the port from its metadata.
+    pub fn to_node(&self) -> Option<ttl::Node> {
+        self.metadata
+            .as_ref()
+            .map(|metadata| metadata.to_node(&["lv2:OutputPort", "lv2:ControlPort"]))
+    }
+
     /// Set the internal data pointer.
     ///
     /// This function should only be called by a plugin's `connect_port` function.
@@ -130,4 +493,349 @@ impl ParameterOutputPort {
     pub unsafe fn get_mut(&mut self) -> Option<&mut f32> {
         self.raw.as_mut()
     }
+
+    /// Write a value to the port, restricted by the properties of its metadata.
+    ///
+    /// If the port isn't connected, nothing is written. This is fine for connection-optional
+    /// ports, but an `Err(PortError::NotConnected)` is returned for all other ports, including
+    /// ports without metadata.
+    ///
+    /// # Safety
+    ///
+    /// The port has to be connected to a valid value or to null, which is the case during and
+    /// between the `run` calls of a plugin.
+    pub unsafe fn set(&mut self, value: f32) -> Result<(), PortError> {
+        match (self.raw.as_mut(), &self.metadata) {
+            (Some(port), Some(metadata)) => *port = metadata.apply_properties(value),
+            (Some(port), None) => *port = value,
+            (None, Some(metadata)) if metadata.is_optional() => (),
+            (None, _) => return Err(PortError::NotConnected),
+        }
+        Ok(())
+    }
+
+    /// Reset a trigger port to its default value.
+    ///
+    /// An output port with the `Trigger` property signals a momentary event and has to be reset
+    /// after the host has read it. This happens automatically before every `run` call for the ports
+    /// that are passed to the visitor of
+    /// [`Plugin::visit_trigger_ports`](../trait.Plugin.html#method.visit_trigger_ports). This
+    /// method does nothing if the port is no trigger, has no declared default value or isn't
+    /// connected.
+    ///
+    /// # Safety
+    ///
+    /// The port has to be connected to a valid value or to null, which is the case during and
+    /// between the `run` calls of a plugin.
+    pub unsafe fn reset_trigger(&mut self) {
+        let default = self
+            .metadata
+            .as_ref()
+            .and_then(PortMetadata::trigger_default);
+        if let (Some(default), Some(port)) = (default, self.raw.as_mut()) {
+            *port = default;
+        }
+    }
+}
+
+/// A group of audio ports, like a stereo input or a sidechain.
+///
+/// The bus bundles `N` [`AudioInputPort`s](struct.AudioInputPort.html) or
+/// [`AudioOutputPort`s](struct.AudioOutputPort.html) with the [designation](../port_groups/enum.Channel.html)
+/// and the metadata of every channel. It connects its ports, grants access to their data per
+/// channel or per frame and writes the Turtle description of the group and its ports:
+///
+///     extern crate lv2rs_core as core;
+///     use core::port_groups::{Channel, PortGroup};
+///     use core::ports::*;
+///
+///     let mut input: AudioBus<AudioInputPort, 2> = AudioBus::new(
+///         PortGroup::new("https://example.org#in", "in", "Input"),
+///         [
+///             (Channel::Left, PortMetadata::new(0, "in_l", "Left Input")),
+///             (Channel::Right, PortMetadata::new(1, "in_r", "Right Input")),
+///         ],
+///     );
+///
+///     // Usually done by the plugin's `connect_port` function.
+///     let mut left = [1.0f32, 2.0];
+///     let mut right = [3.0f32, 4.0];
+///     assert!(input.connect(0, left.as_mut_ptr() as *mut ()));
+///     assert!(input.connect(1, right.as_mut_ptr() as *mut ()));
+///     assert!(!input.connect(2, std::ptr::null_mut()));
+///
+///     // Usually done by the plugin's `run` function.
+///     let frames: Vec<[f32; 2]> = unsafe { input.frames(2) }.unwrap().collect();
+///     assert_eq!(vec![[1.0, 3.0], [2.0, 4.0]], frames);
+///
+///     assert!(input.group_node().to_string().contains("a pg:StereoGroup"));
+pub struct AudioBus<P, const N: usize> {
+    group: PortGroup,
+    channels: [Channel; N],
+    metadata: [PortMetadata; N],
+    ports: [P; N],
+}
+
+impl<P: Default, const N: usize> AudioBus<P, N> {
+    /// Create a new bus with the given group and channels, whose ports point to null.
+    ///
+    /// If the group is a sidechain, the `lv2:isSideChain` property is added to the metadata of
+    /// every port.
+    pub fn new(group: PortGroup, channels: [(Channel, PortMetadata); N]) -> Self {
+        let side_chain = group.is_side_chain();
+        let mut designations = [Channel::Center; N];
+        let mut index = 0;
+        let metadata = channels.map(|(channel, mut metadata)| {
+            designations[index] = channel;
+            index += 1;
+            if side_chain && !metadata.has_property(PortProperty::IsSideChain) {
+                metadata = metadata.with_property(PortProperty::IsSideChain);
+            }
+            metadata
+        });
+        Self {
+            group,
+            channels: designations,
+            metadata,
+            ports: std::array::from_fn(|_| P::default()),
+        }
+    }
+}
+
+impl<P, const N: usize> AudioBus<P, N> {
+    /// Return the description of the group.
+    pub fn group(&self) -> &PortGroup {
+        &self.group
+    }
+
+    /// Return the designations of the channels, in the order of the ports.
+    pub fn channels(&self) -> &[Channel; N] {
+        &self.channels
+    }
+
+    /// Return the metadata of the ports.
+    pub fn metadata(&self) -> &[PortMetadata; N] {
+        &self.metadata
+    }
+
+    /// Return the port with the given designation, if the bus has it.
+    pub fn port(&self, channel: Channel) -> Option<&P> {
+        self.position(channel).map(|index| &self.ports[index])
+    }
+
+    /// Return the port with the given designation mutably, if the bus has it.
+    pub fn port_mut(&mut self, channel: Channel) -> Option<&mut P> {
+        self.position(channel)
+            .map(move |index| &mut self.ports[index])
+    }
+
+    fn position(&self, channel: Channel) -> Option<usize> {
+        self.channels.iter().position(|other| *other == channel)
+    }
+
+    fn find_port(&mut self, port: u32) -> Option<&mut P> {
+        let index = self
+            .metadata
+            .iter()
+            .position(|metadata| metadata.index() == port)?;
+        Some(&mut self.ports[index])
+    }
+
+    fn describe_group(&self, direction: &str) -> ttl::Node {
+        self.group
+            .to_node(&[port_groups::group_class(&self.channels), direction])
+    }
+
+    fn describe_ports(&self, classes: &[&str]) -> Vec<ttl::Node> {
+        self.metadata
+            .iter()
+            .zip(self.channels.iter())
+            .map(|(metadata, channel)| {
+                let mut node = metadata.to_node(classes);
+                node.add("pg:group", ttl::uri(self.group.uri()))
+                    .add("lv2:designation", channel.prefixed_name());
+                node
+            })
+            .collect()
+    }
+}
+
+impl<const N: usize> AudioBus<AudioInputPort, N> {
+    /// Connect the port with the given index, if it belongs to the bus.
+    ///
+    /// This function should only be called by a plugin's `connect_port` function. It returns
+    /// `true` if the port belongs to the bus and `false` otherwise.
+    pub fn connect(&mut self, port: u32, data: *mut ()) -> bool {
+        match self.find_port(port) {
+            Some(port) => {
+                port.connect(data as *const f32);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Try to create immutable slices of the audio data of all channels, in the order of the
+    /// ports.
+    ///
+    /// `None` is returned if one of the ports isn't connected.
+    ///
+    /// # Safety
+    ///
+    /// This function has the same requirements as
+    /// [`AudioInputPort::as_slice`](struct.AudioInputPort.html#method.as_slice).
+    pub unsafe fn as_slices(&self, n_samples: u32) -> Option<[&[f32]; N]> {
+        let mut slices = [&[][..]; N];
+        for (slice, port) in slices.iter_mut().zip(self.ports.iter()) {
+            *slice = port.as_slice(n_samples)?;
+        }
+        Some(slices)
+    }
+
+    /// Try to create an iterator over the frames of the bus.
+    ///
+    /// Every frame contains one sample of every channel, in the order of the ports. `None` is
+    /// returned if one of the ports isn't connected.
+    ///
+    /// # Safety
+    ///
+    /// This function has the same requirements as
+    /// [`AudioInputPort::as_slice`](struct.AudioInputPort.html#method.as_slice).
+    pub unsafe fn frames(&self, n_samples: u32) -> Option<Frames<'_, N>> {
+        Some(Frames {
+            channels: self.as_slices(n_samples)?,
+            position: 0,
+            length: n_samples as usize,
+        })
+    }
+
+    /// Create the Turtle description of the group, as a `pg:InputGroup`.
+    ///
+    /// The node should be placed next to the description of the plugin. The generated statements
+    /// use the prefixes of [`ttl::PREFIXES`](../ttl/constant.PREFIXES.html).
+    pub fn group_node(&self) -> ttl::Node {
+        self.describe_group("pg:InputGroup")
+    }
+
+    /// Create the Turtle descriptions of the ports, as `lv2:InputPort`s and `lv2:AudioPort`s.
+    ///
+    /// Every port refers to the group and declares its designation. The nodes should be added to
+    /// the plugin with the `lv2:port` predicate.
+    pub fn port_nodes(&self) -> Vec<ttl::Node> {
+        self.describe_ports(&["lv2:InputPort", "lv2:AudioPort"])
+    }
+}
+
+impl<const N: usize> AudioBus<AudioOutputPort, N> {
+    /// Connect the port with the given index, if it belongs to the bus.
+    ///
+    /// This function should only be called by a plugin's `connect_port` function. It returns
+    /// `true` if the port belongs to the bus and `false` otherwise.
+    pub fn connect(&mut self, port: u32, data: *mut ()) -> bool {
+        match self.find_port(port) {
+            Some(port) => {
+                port.connect(data as *mut f32);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Try to create mutable slices of the audio data of all channels, in the order of the ports.
+    ///
+    /// `None` is returned if one of the ports isn't connected.
+    ///
+    /// # Safety
+    ///
+    /// This function has the same requirements as
+    /// [`AudioOutputPort::as_slice`](struct.AudioOutputPort.html#method.as_slice). Additionally,
+    /// the host must not connect two ports of the bus to the same buffer.
+    pub unsafe fn as_slices(&mut self, n_samples: u32) -> Option<[&mut [f32]; N]> {
+        let mut slices: [&mut [f32]; N] = std::array::from_fn(|_| &mut [][..]);
+        for (slice, port) in slices.iter_mut().zip(self.ports.iter_mut()) {
+            *slice = port.as_slice(n_samples)?;
+        }
+        Some(slices)
+    }
+
+    /// Try to create an iterator over the frames of the bus.
+    ///
+    /// Every frame contains a mutable reference to one sample of every channel, in the order of
+    /// the ports. `None` is returned if one of the ports isn't connected.
+    ///
+    /// # Safety
+    ///
+    /// This function has the same requirements as [`as_slices`](#method.as_slices-1).
+    pub unsafe fn frames(&mut self, n_samples: u32) -> Option<FramesMut<'_, N>> {
+        Some(FramesMut {
+            channels: self.as_slices(n_samples)?,
+            remaining: n_samples as usize,
+        })
+    }
+
+    /// Create the Turtle description of the group, as a `pg:OutputGroup`.
+    ///
+    /// The node should be placed next to the description of the plugin. The generated statements
+    /// use the prefixes of [`ttl::PREFIXES`](../ttl/constant.PREFIXES.html).
+    pub fn group_node(&self) -> ttl::Node {
+        self.describe_group("pg:OutputGroup")
+    }
+
+    /// Create the Turtle descriptions of the ports, as `lv2:OutputPort`s and `lv2:AudioPort`s.
+    ///
+    /// Every port refers to the group and declares its designation. The nodes should be added to
+    /// the plugin with the `lv2:port` predicate.
+    pub fn port_nodes(&self) -> Vec<ttl::Node> {
+        self.describe_ports(&["lv2:OutputPort", "lv2:AudioPort"])
+    }
+}
+
+/// Iterator over the frames of an input bus.
+///
+/// This iterator is created by [`AudioBus::frames`](struct.AudioBus.html#method.frames).
+pub struct Frames<'a, const N: usize> {
+    channels: [&'a [f32]; N],
+    position: usize,
+    length: usize,
+}
+
+impl<'a, const N: usize> Iterator for Frames<'a, N> {
+    type Item = [f32; N];
+
+    fn next(&mut self) -> Option<[f32; N]> {
+        if self.position >= self.length {
+            return None;
+        }
+        let position = self.position;
+        self.position += 1;
+        Some(std::array::from_fn(|channel| {
+            self.channels[channel][position]
+        }))
+    }
+}
+
+/// Iterator over the frames of an output bus.
+///
+/// This iterator is created by [`AudioBus::frames`](struct.AudioBus.html#method.frames-1).
+pub struct FramesMut<'a, const N: usize> {
+    channels: [&'a mut [f32]; N],
+    remaining: usize,
+}
+
+impl<'a, const N: usize> Iterator for FramesMut<'a, N> {
+    type Item = [&'a mut f32; N];
+
+    fn next(&mut self) -> Option<[&'a mut f32; N]> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        Some(std::array::from_fn(|channel| {
+            let (sample, rest) = std::mem::take(&mut self.channels[channel])
+                .split_first_mut()
+                .unwrap();
+            self.channels[channel] = rest;
+            sample
+        }))
+    }
 }
@@ -0,0 +1,352 @@
+//! Generating and loading presets.
+//!
+//! A preset is a named set of control port values and state properties that a host can apply to
+//! a plugin instance. Presets are stored as Turtle documents: Every preset has its own file, which
+//! is announced in the bundle's manifest. This module creates these files from the current values
+//! of a plugin instance and reads them back, so that factory presets don't have to be written by
+//! hand. Values are set one by one or captured from an instance that implements
+//! [`CapturePreset`](trait.CapturePreset.html):
+//!
+//!     extern crate lv2rs_core as core;
+//!     use core::presets::*;
+//!
+//!     let mut preset = Preset::new("https://example.org/presets#loud", "https://example.org#amp");
+//!     preset.set_label("Loud");
+//!     preset.set_port_value("gain", 2.0);
+//!     preset.set_state_value("https://example.org#name", StateValue::String("Loud".to_owned()));
+//!
+//!     // The preset file and its manifest entry.
+//!     let document = preset.to_turtle();
+//!     let entry = preset.manifest_entry("loud.ttl");
+//!     assert!(entry.contains("rdfs:seeAlso <loud.ttl>"));
+//!
+//!     // Loading the preset file and applying it to an instance.
+//!     let presets = Preset::from_turtle(&document).unwrap();
+//!     assert_eq!(vec![preset.clone()], presets);
+//!
+//!     let mut gain = 1.0;
+//!     presets[0].apply(
+//!         |symbol, value| if symbol == "gain" { gain = value },
+//!         |_property, _value| (),
+//!     );
+//!     assert_eq!(2.0, gain);
+use crate::ports::ParameterInputPort;
+use crate::ttl::{self, Term};
+use std::convert::TryFrom;
+
+const PRESET_URI: &str = "http://lv2plug.in/ns/ext/presets#Preset";
+const VALUE_URI: &str = "http://lv2plug.in/ns/ext/presets#value";
+const STATE_URI: &str = "http://lv2plug.in/ns/ext/state#state";
+const APPLIES_TO_URI: &str = "http://lv2plug.in/ns/lv2core#appliesTo";
+const PORT_URI: &str = "http://lv2plug.in/ns/lv2core#port";
+const SYMBOL_URI: &str = "http://lv2plug.in/ns/lv2core#symbol";
+const LABEL_URI: &str = "http://www.w3.org/2000/01/rdf-schema#label";
+const PATH_TYPE_URI: &str = "http://lv2plug.in/ns/ext/atom#Path";
+
+/// The value of a state property in a preset.
+#[derive(Clone, Debug, PartialEq)]
+pub enum StateValue {
+    /// A number, written as a decimal.
+    Float(f32),
+    /// An integer.
+    Int(i32),
+    /// A switch.
+    Bool(bool),
+    /// A file path, written as a string with the type `atom:Path`.
+    Path(String),
+    /// A string.
+    String(String),
+}
+
+impl StateValue {
+    fn term(&self) -> String {
+        match self {
+            StateValue::Float(value) => ttl::float(f64::from(*value)),
+            StateValue::Int(value) => ttl::integer(i64::from(*value)),
+            StateValue::Bool(value) => ttl::boolean(*value),
+            StateValue::Path(value) => format!("{}^^atom:Path", ttl::string(value)),
+            StateValue::String(value) => ttl::string(value),
+        }
+    }
+
+    fn from_term(term: &Term) -> Option<Self> {
+        match term {
+            Term::Decimal(value) => Some(StateValue::Float(*value as f32)),
+            Term::Integer(value) => i32::try_from(*value).ok().map(StateValue::Int),
+            Term::Boolean(value) => Some(StateValue::Bool(*value)),
+            Term::String(value, Some(datatype)) if datatype == PATH_TYPE_URI => {
+                Some(StateValue::Path(value.clone()))
+            }
+            Term::String(value, _) => Some(StateValue::String(value.clone())),
+            _ => None,
+        }
+    }
+}
+
+/// Errors that may occur when loading presets.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PresetError {
+    /// The document is not valid Turtle.
+    Parse(ttl::ParseError),
+    /// The preset with the given URI doesn't say which plugin it applies to.
+    MissingPlugin(String),
+    /// A port of the preset with the given URI has no symbol or no numeric value.
+    MalformedPort(String),
+    /// A state property of the preset with the given URI has an unsupported value, like an
+    /// integer that doesn't fit into an `i32`.
+    MalformedState(String),
+}
+
+/// A plugin instance whose values can be captured in a preset.
+///
+/// The instance lists its control input ports and state properties, which are then read by
+/// [`Preset::capture`](struct.Preset.html#method.capture).
+pub trait CapturePreset {
+    /// Pass every control input port to the visitor, together with its symbol.
+    fn visit_ports(&self, visitor: &mut dyn FnMut(&str, &ParameterInputPort));
+
+    /// Pass the URI and current value of every state property to the visitor.
+    ///
+    /// The default implementation visits no properties.
+    fn visit_state(&self, _visitor: &mut dyn FnMut(&str, StateValue)) {}
+}
+
+/// A preset for a plugin.
+///
+/// See the [module documentation](index.html) for more information.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Preset {
+    uri: String,
+    plugin_uri: String,
+    label: Option<String>,
+    port_values: Vec<(String, f32)>,
+    state: Vec<(String, StateValue)>,
+}
+
+impl Preset {
+    /// Create a new, empty preset for the given plugin.
+    pub fn new(uri: &str, plugin_uri: &str) -> Self {
+        Self {
+            uri: uri.to_owned(),
+            plugin_uri: plugin_uri.to_owned(),
+            label: None,
+            port_values: Vec::new(),
+            state: Vec::new(),
+        }
+    }
+
+    /// Return the URI of the preset.
+    pub fn uri(&self) -> &str {
+        &self.uri
+    }
+
+    /// Return the URI of the plugin the preset applies to.
+    pub fn plugin_uri(&self) -> &str {
+        &self.plugin_uri
+    }
+
+    /// Return the human-readable name of the preset, if it has one.
+    pub fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+
+    /// Set the human-readable name of the preset.
+    pub fn set_label(&mut self, label: &str) {
+        self.label = Some(label.to_owned());
+    }
+
+    /// Set the value of the control port with the given symbol.
+    ///
+    /// If the port already has a value, it is replaced.
+    pub fn set_port_value(&mut self, symbol: &str, value: f32) {
+        match self.port_values.iter_mut().find(|(s, _)| s == symbol) {
+            Some(port) => port.1 = value,
+            None => self.port_values.push((symbol.to_owned(), value)),
+        }
+    }
+
+    /// Capture the current value of a control input port.
+    ///
+    /// Nothing is captured if the port isn't connected.
+    ///
+    /// # Safety
+    ///
+    /// The port has to be connected to a valid value or to null, which is the case during and
+    /// between the `run` calls of a plugin.
+    pub unsafe fn capture_port(&mut self, symbol: &str, port: &ParameterInputPort) {
+        if let Some(value) = port.get() {
+            self.set_port_value(symbol, *value);
+        }
+    }
+
+    /// Capture the current values of all control input ports and state properties of an instance.
+    ///
+    /// Ports that aren't connected are skipped, just like in
+    /// [`capture_port`](#method.capture_port).
+    ///
+    /// # Safety
+    ///
+    /// The ports of the instance have to be connected to valid values or to null, which is the case
+    /// during and between the `run` calls of a plugin.
+    pub unsafe fn capture<C: CapturePreset + ?Sized>(&mut self, instance: &C) {
+        instance.visit_ports(&mut |symbol, port| self.capture_port(symbol, port));
+        instance.visit_state(&mut |property, value| self.set_state_value(property, value));
+    }
+
+    /// Return the value of the control port with the given symbol.
+    pub fn port_value(&self, symbol: &str) -> Option<f32> {
+        self.port_values
+            .iter()
+            .find(|(s, _)| s == symbol)
+            .map(|(_, value)| *value)
+    }
+
+    /// Iterate over the symbols and values of all control ports.
+    pub fn port_values(&self) -> impl Iterator<Item = (&str, f32)> {
+        self.port_values
+            .iter()
+            .map(|(symbol, value)| (symbol.as_str(), *value))
+    }
+
+    /// Set the value of the state property with the given URI.
+    ///
+    /// If the property already has a value, it is replaced.
+    pub fn set_state_value(&mut self, property: &str, value: StateValue) {
+        match self.state.iter_mut().find(|(p, _)| p == property) {
+            Some(property) => property.1 = value,
+            None => self.state.push((property.to_owned(), value)),
+        }
+    }
+
+    /// Return the value of the state property with the given URI.
+    pub fn state_value(&self, property: &str) -> Option<&StateValue> {
+        self.state
+            .iter()
+            .find(|(p, _)| p == property)
+            .map(|(_, value)| value)
+    }
+
+    /// Iterate over the URIs and values of all state properties.
+    pub fn state_values(&self) -> impl Iterator<Item = (&str, &StateValue)> {
+        self.state
+            .iter()
+            .map(|(property, value)| (property.as_str(), value))
+    }
+
+    /// Apply the preset.
+    ///
+    /// `set_port` is called with the symbol and value of every control port and `set_state` is
+    /// called with the URI and value of every state property, in the order they were added or
+    /// read. A host connects these functions to the ports and the state interface of the instance.
+    pub fn apply<P, S>(&self, mut set_port: P, mut set_state: S)
+    where
+        P: FnMut(&str, f32),
+        S: FnMut(&str, &StateValue),
+    {
+        for (symbol, value) in self.port_values.iter() {
+            set_port(symbol, *value);
+        }
+        for (property, value) in self.state.iter() {
+            set_state(property, value);
+        }
+    }
+
+    /// Generate the Turtle document of the preset, including the prefix declarations.
+    pub fn to_turtle(&self) -> String {
+        let mut node = ttl::Node::new(ttl::uri(&self.uri));
+        node.add("a", "pset:Preset")
+            .add("lv2:appliesTo", ttl::uri(&self.plugin_uri));
+        if let Some(label) = &self.label {
+            node.add("rdfs:label", ttl::string(label));
+        }
+        for (symbol, value) in self.port_values.iter() {
+            let mut port = ttl::Node::blank();
+            port.add("lv2:symbol", ttl::string(symbol))
+                .add("pset:value", ttl::float(f64::from(*value)));
+            node.add_node("lv2:port", port);
+        }
+        if !self.state.is_empty() {
+            let mut state = ttl::Node::blank();
+            for (property, value) in self.state.iter() {
+                state.add(&ttl::uri(property), value.term());
+            }
+            node.add_node("state:state", state);
+        }
+        format!("{}\n{}", ttl::PREFIXES, node)
+    }
+
+    /// Generate the entry of the preset in the bundle's manifest.
+    ///
+    /// `file` is the URI of the preset file relative to the manifest, usually just its name. The
+    /// generated statements use the prefixes of [`ttl::PREFIXES`](../ttl/constant.PREFIXES.html),
+    /// which have to be declared once in the manifest.
+    pub fn manifest_entry(&self, file: &str) -> String {
+        let mut node = ttl::Node::new(ttl::uri(&self.uri));
+        node.add("a", "pset:Preset")
+            .add("lv2:appliesTo", ttl::uri(&self.plugin_uri))
+            .add("rdfs:seeAlso", ttl::uri(file));
+        node.to_string()
+    }
+
+    /// Read all presets from a Turtle document.
+    ///
+    /// Every subject of the type `pset:Preset` is read as a preset, which has to apply to a plugin.
+    /// A manifest usually only contains the type and plugin of a preset, which means that the
+    /// presets read from a manifest are empty; The values are read from the preset files instead.
+    pub fn from_turtle(document: &str) -> Result<Vec<Self>, PresetError> {
+        let triples = ttl::parse(document).map_err(PresetError::Parse)?;
+        let preset_type = Term::Uri(PRESET_URI.to_owned());
+        ttl::subjects(&triples, ttl::RDF_TYPE, &preset_type)
+            .filter_map(|subject| match subject {
+                Term::Uri(uri) => Some((subject, uri)),
+                _ => None,
+            })
+            .map(|(subject, uri)| Self::from_triples(&triples, subject, uri))
+            .collect()
+    }
+
+    fn from_triples(
+        triples: &[ttl::Triple],
+        subject: &Term,
+        uri: &str,
+    ) -> Result<Self, PresetError> {
+        let plugin_uri = ttl::objects(triples, subject, APPLIES_TO_URI)
+            .find_map(|object| match object {
+                Term::Uri(plugin_uri) => Some(plugin_uri.as_str()),
+                _ => None,
+            })
+            .ok_or_else(|| PresetError::MissingPlugin(uri.to_owned()))?;
+        let mut preset = Self::new(uri, plugin_uri);
+
+        preset.label = ttl::objects(triples, subject, LABEL_URI).find_map(|object| match object {
+            Term::String(label, _) => Some(label.clone()),
+            _ => None,
+        });
+
+        for port in ttl::objects(triples, subject, PORT_URI) {
+            let symbol = ttl::objects(triples, port, SYMBOL_URI).find_map(|object| match object {
+                Term::String(symbol, _) => Some(symbol),
+                _ => None,
+            });
+            let value = ttl::objects(triples, port, VALUE_URI).find_map(|object| match object {
+                Term::Decimal(value) => Some(*value as f32),
+                Term::Integer(value) => Some(*value as f32),
+                _ => None,
+            });
+            match (symbol, value) {
+                (Some(symbol), Some(value)) => preset.set_port_value(symbol, value),
+                _ => return Err(PresetError::MalformedPort(uri.to_owned())),
+            }
+        }
+
+        for state in ttl::objects(triples, subject, STATE_URI) {
+            for triple in triples.iter().filter(|triple| triple.subject == *state) {
+                let value = StateValue::from_term(&triple.object)
+                    .ok_or_else(|| PresetError::MalformedState(uri.to_owned()))?;
+                preset.set_state_value(&triple.predicate, value);
+            }
+        }
+
+        Ok(preset)
+    }
+}
@@ -0,0 +1,84 @@
+//! Requesting larger port buffers from the host.
+//!
+//! The buffers of atom ports are allocated by the host and may be too small for plugins that send
+//! a lot of data, for example waveforms or spectra. Such plugins should declare the minimum size
+//! of the buffer with [`PortMetadata::with_minimum_size`](../ports/struct.PortMetadata.html#method.with_minimum_size)
+//! and, if this isn't enough, request a larger buffer with the [`ResizePort`](struct.ResizePort.html)
+//! feature. A typical plugin grows the buffer when writing an atom failed because of missing
+//! space:
+//!
+//!     extern crate lv2rs_core as core;
+//!     use core::resize_port::ResizePort;
+//!
+//!     /// Try to grow the buffer of an atom port after a failed write.
+//!     ///
+//!     /// If this returns `true`, the port is reconnected to a larger buffer by the host.
+//!     fn grow_buffer(resize: Option<&mut ResizePort>, port_index: u32, required: usize) -> bool {
+//!         match resize {
+//!             Some(resize) => resize.resize(port_index, required).is_ok(),
+//!             None => false,
+//!         }
+//!     }
+//!
+//!     assert!(!grow_buffer(None, 2, 4096));
+use crate::feature::{Feature, FeaturesList};
+use std::ffi::CStr;
+use std::os::raw::*;
+
+pub const URI: &[u8] = b"http://lv2plug.in/ns/ext/resize-port\0";
+pub const AS_LARGE_AS_URI: &[u8] = b"http://lv2plug.in/ns/ext/resize-port#asLargeAs\0";
+pub const MINIMUM_SIZE_URI: &[u8] = b"http://lv2plug.in/ns/ext/resize-port#minimumSize\0";
+pub const RESIZE_URI: &[u8] = b"http://lv2plug.in/ns/ext/resize-port#resize\0";
+
+/// The request was successful and the port was reconnected to a new buffer.
+pub const SUCCESS: c_int = 0;
+/// The request failed for an unknown reason.
+pub const ERR_UNKNOWN: c_int = 1;
+/// The host couldn't allocate a buffer of the requested size.
+pub const ERR_NO_SPACE: c_int = 2;
+
+/// Errors that may occur when requesting a larger buffer.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ResizePortError {
+    /// The request failed for an unknown reason.
+    Unknown,
+    /// The host couldn't allocate a buffer of the requested size.
+    NoSpace,
+}
+
+/// The `rsz:resize` feature.
+///
+/// Hosts providing this feature allow plugins to request larger buffers for their ports.
+#[repr(C)]
+pub struct ResizePort {
+    /// Opaque data of the host, which must be passed to `resize`.
+    pub data: *mut c_void,
+    /// Request a resize of the buffer of the port with the given index, in bytes.
+    pub resize: unsafe extern "C" fn(data: *mut c_void, index: u32, size: usize) -> c_int,
+}
+
+impl ResizePort {
+    /// Try to find the resize-port feature of the host in the features list.
+    ///
+    /// This function returns `None` if the host does not support resizing port buffers.
+    pub fn try_from_features(features: &FeaturesList) -> Option<&'static mut Self> {
+        unsafe {
+            Feature::get_feature::<Self>(features, CStr::from_bytes_with_nul_unchecked(RESIZE_URI))
+        }
+    }
+
+    /// Request a buffer of the given size, in bytes, for the port with the given index.
+    ///
+    /// This method may only be called in the plugin's `run` function. The host preserves the
+    /// contents of the buffer when resizing it, but the buffer may move: If the request was
+    /// successful, the host connects the port to the new location by calling the plugin's
+    /// `connect_port` function. Therefore, pointers to the old buffer must not be used anymore.
+    /// The port has to be read again from the pointer passed to `connect_port`.
+    pub fn resize(&mut self, index: u32, size: usize) -> Result<(), ResizePortError> {
+        match unsafe { (self.resize)(self.data, index, size) } {
+            SUCCESS => Ok(()),
+            ERR_NO_SPACE => Err(ResizePortError::NoSpace),
+            _ => Err(ResizePortError::Unknown),
+        }
+    }
+}
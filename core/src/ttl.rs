@@ -0,0 +1,683 @@
+//! Minimal Turtle writer and reader for plugin descriptions.
+//!
+//! LV2 plugins are described by Turtle documents, which are read by the host before the plugin
+//! library is even loaded. Usually, these documents are written by hand, but some parts of them
+//! depend on the code of the plugin, for example the parameters or the properties of ports.
+//! Hand-written descriptions of these tend to go stale, which is why some parts of this library
+//! are able to generate their own description. This module contains the common means to do that.
+//!
+//! A description consists of [`Node`s](struct.Node.html), which are subjects with a list of
+//! predicates and objects. Predicates and objects are written as they appear in the Turtle
+//! document and therefore, this module also contains functions to create properly escaped terms:
+//!
+//!     extern crate lv2rs_core as core;
+//!     use core::ttl::*;
+//!
+//!     let mut node = Node::new(uri("https://example.org#gain"));
+//!     node.add("a", "lv2:Parameter")
+//!         .add("rdfs:label", string("Gain"))
+//!         .add("lv2:default", float(0.5));
+//!
+//!     assert_eq!(
+//!         "<https://example.org#gain>\n    a lv2:Parameter ;\n    rdfs:label \"Gain\" ;\n    lv2:default 0.5 .\n",
+//!         node.to_string()
+//!     );
+//!
+//! Documents that were generated this way can also be read back with [`parse`](fn.parse.html),
+//! which returns the statements of the document as [`Triple`s](struct.Triple.html). The reader
+//! only supports the subset of Turtle that is commonly used in LV2 bundles: Prefix declarations,
+//! URIs, prefixed names, blank nodes in square brackets, string literals and numbers.
+//! Collections, multi-line strings and base URIs are not supported.
+use std::collections::HashMap;
+use std::fmt;
+
+/// The prefix declarations of the most common LV2 vocabularies.
+///
+/// The terms written by this library use these prefixes and therefore, this string should be placed
+/// in front of generated documents.
+pub const PREFIXES: &str = "@prefix atom: <http://lv2plug.in/ns/ext/atom#> .
+@prefix lv2: <http://lv2plug.in/ns/lv2core#> .
+@prefix morph: <http://lv2plug.in/ns/ext/morph#> .
+@prefix patch: <http://lv2plug.in/ns/ext/patch#> .
+@prefix pg: <http://lv2plug.in/ns/ext/port-groups#> .
+@prefix pprops: <http://lv2plug.in/ns/ext/port-props#> .
+@prefix pset: <http://lv2plug.in/ns/ext/presets#> .
+@prefix rdf: <http://www.w3.org/1999/02/22-rdf-syntax-ns#> .
+@prefix rdfs: <http://www.w3.org/2000/01/rdf-schema#> .
+@prefix rsz: <http://lv2plug.in/ns/ext/resize-port#> .
+@prefix state: <http://lv2plug.in/ns/ext/state#> .
+@prefix units: <http://lv2plug.in/ns/extensions/units#> .
+@prefix xsd: <http://www.w3.org/2001/XMLSchema#> .
+";
+
+/// Create a URI reference term, e.g. `<https://example.org>`.
+pub fn uri(uri: &str) -> String {
+    format!("<{}>", uri)
+}
+
+/// Create an escaped string literal term, e.g. `"Gain"`.
+pub fn string(string: &str) -> String {
+    let mut term = String::with_capacity(string.len() + 2);
+    term.push('"');
+    for c in string.chars() {
+        match c {
+            '"' => term.push_str("\\\""),
+            '\\' => term.push_str("\\\\"),
+            '\n' => term.push_str("\\n"),
+            '\r' => term.push_str("\\r"),
+            '\t' => term.push_str("\\t"),
+            c => term.push(c),
+        }
+    }
+    term.push('"');
+    term
+}
+
+/// Create a decimal literal term, e.g. `0.5`.
+///
+/// Turtle reads numbers without a decimal point as integers and therefore, integral values are
+/// written with a trailing `.0`.
+pub fn float(value: f64) -> String {
+    if value.fract() == 0.0 && value.is_finite() {
+        format!("{:.1}", value)
+    } else {
+        format!("{}", value)
+    }
+}
+
+/// Create an integer literal term, e.g. `42`.
+pub fn integer(value: i64) -> String {
+    format!("{}", value)
+}
+
+/// Create a boolean literal term, either `true` or `false`.
+pub fn boolean(value: bool) -> String {
+    format!("{}", value)
+}
+
+/// The object of a statement: Either a term or a nested blank node.
+enum Object {
+    Term(String),
+    Node(Node),
+}
+
+/// A subject with its predicates and objects.
+///
+/// Named nodes are written as stand-alone statements and blank nodes are written in square
+/// brackets; This way, blank nodes can be nested in other nodes with the
+/// [`add_node`](#method.add_node) method. The order of the predicates is preserved.
+pub struct Node {
+    subject: Option<String>,
+    statements: Vec<(String, Object)>,
+}
+
+impl Node {
+    /// Create a new, named node.
+    ///
+    /// The subject has to be a valid term, for example a [`uri`](fn.uri.html) or a prefixed name.
+    pub fn new<S: Into<String>>(subject: S) -> Self {
+        Self {
+            subject: Some(subject.into()),
+            statements: Vec::new(),
+        }
+    }
+
+    /// Create a new blank node.
+    pub fn blank() -> Self {
+        Self {
+            subject: None,
+            statements: Vec::new(),
+        }
+    }
+
+    /// Add a statement with a term as its object.
+    pub fn add<S: Into<String>>(&mut self, predicate: &str, object: S) -> &mut Self {
+        self.statements
+            .push((predicate.to_owned(), Object::Term(object.into())));
+        self
+    }
+
+    /// Add a statement with a nested blank node as its object.
+    pub fn add_node(&mut self, predicate: &str, node: Node) -> &mut Self {
+        self.statements
+            .push((predicate.to_owned(), Object::Node(node)));
+        self
+    }
+
+    /// Return `true` if no statement was added to the node.
+    pub fn is_empty(&self) -> bool {
+        self.statements.is_empty()
+    }
+
+    fn write(&self, f: &mut fmt::Formatter, indent: usize) -> fmt::Result {
+        let inner_indent = indent + 4;
+        match &self.subject {
+            Some(subject) => write!(f, "{}", subject)?,
+            None => write!(f, "[")?,
+        }
+        for (index, (predicate, object)) in self.statements.iter().enumerate() {
+            if index > 0 {
+                write!(f, " ;")?;
+            }
+            write!(f, "\n{:indent$}{} ", "", predicate, indent = inner_indent)?;
+            match object {
+                Object::Term(term) => write!(f, "{}", term)?,
+                Object::Node(node) => node.write(f, inner_indent)?,
+            }
+        }
+        match &self.subject {
+            Some(_) => writeln!(f, " ."),
+            None => write!(f, "\n{:indent$}]", "", indent = indent),
+        }
+    }
+}
+
+impl fmt::Display for Node {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.write(f, 0)
+    }
+}
+
+/// The URI of `rdf:type`, which is abbreviated with `a`.
+pub const RDF_TYPE: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#type";
+
+/// A term read from a Turtle document.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Term {
+    /// A URI. Prefixed names are expanded, relative URIs are kept as they are.
+    Uri(String),
+    /// A blank node, identified by its number in the document.
+    Blank(usize),
+    /// A string literal with the URI of its datatype, if it has one.
+    String(String, Option<String>),
+    /// An integer literal.
+    Integer(i64),
+    /// A decimal or double literal.
+    Decimal(f64),
+    /// A boolean literal.
+    Boolean(bool),
+}
+
+/// A statement read from a Turtle document.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Triple {
+    pub subject: Term,
+    /// The expanded URI of the predicate.
+    pub predicate: String,
+    pub object: Term,
+}
+
+/// Errors that may occur when parsing a Turtle document.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ParseError {
+    /// The document ended in the middle of a statement.
+    UnexpectedEnd,
+    /// An unexpected or unsupported token was found in the given line.
+    UnexpectedToken(usize),
+    /// A prefixed name uses a prefix that wasn't declared.
+    UnknownPrefix(String),
+}
+
+/// Parse a Turtle document and return its statements.
+///
+///     extern crate lv2rs_core as core;
+///     use core::ttl::*;
+///
+///     let document = "@prefix lv2: <http://lv2plug.in/ns/lv2core#> .
+///     <https://example.org#plugin> a lv2:Plugin ; lv2:port [ lv2:index 0 ] .";
+///     let triples = parse(document).unwrap();
+///
+///     let plugin = Term::Uri("https://example.org#plugin".to_owned());
+///     let port = objects(&triples, &plugin, "http://lv2plug.in/ns/lv2core#port")
+///         .next()
+///         .unwrap();
+///     assert_eq!(
+///         Some(&Term::Integer(0)),
+///         objects(&triples, port, "http://lv2plug.in/ns/lv2core#index").next()
+///     );
+pub fn parse(document: &str) -> Result<Vec<Triple>, ParseError> {
+    let mut parser = Parser {
+        tokens: tokenize(document)?,
+        position: 0,
+        prefixes: HashMap::new(),
+        blank_nodes: 0,
+        triples: Vec::new(),
+    };
+    parser.parse_document()?;
+    Ok(parser.triples)
+}
+
+/// Iterate over the objects of all statements with the given subject and predicate.
+pub fn objects<'a>(
+    triples: &'a [Triple],
+    subject: &'a Term,
+    predicate: &'a str,
+) -> impl Iterator<Item = &'a Term> + 'a {
+    triples
+        .iter()
+        .filter(move |triple| triple.subject == *subject && triple.predicate == predicate)
+        .map(|triple| &triple.object)
+}
+
+/// Iterate over the subjects of all statements with the given predicate and object.
+pub fn subjects<'a>(
+    triples: &'a [Triple],
+    predicate: &'a str,
+    object: &'a Term,
+) -> impl Iterator<Item = &'a Term> + 'a {
+    triples
+        .iter()
+        .filter(move |triple| triple.predicate == predicate && triple.object == *object)
+        .map(|triple| &triple.subject)
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Iri(String),
+    Name(String),
+    Literal(String),
+    Number(String),
+    Prefix,
+    LanguageTag,
+    Datatype,
+    OpenBracket,
+    CloseBracket,
+    Semicolon,
+    Comma,
+    Dot,
+}
+
+fn tokenize(document: &str) -> Result<Vec<(Token, usize)>, ParseError> {
+    let mut tokens = Vec::new();
+    let mut chars = document.chars().peekable();
+    let mut line = 1;
+    while let Some(&c) = chars.peek() {
+        let token = match c {
+            '\n' => {
+                line += 1;
+                chars.next();
+                continue;
+            }
+            c if c.is_whitespace() => {
+                chars.next();
+                continue;
+            }
+            '#' => {
+                while chars.peek().map_or(false, |c| *c != '\n') {
+                    chars.next();
+                }
+                continue;
+            }
+            '<' => {
+                chars.next();
+                let mut iri = String::new();
+                loop {
+                    match chars.next() {
+                        Some('>') => break,
+                        Some('\n') | None => return Err(ParseError::UnexpectedToken(line)),
+                        Some(c) => iri.push(c),
+                    }
+                }
+                Token::Iri(iri)
+            }
+            '"' => {
+                chars.next();
+                let mut string = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some('n') => string.push('\n'),
+                            Some('r') => string.push('\r'),
+                            Some('t') => string.push('\t'),
+                            Some(c @ '"') | Some(c @ '\\') | Some(c @ '\'') => string.push(c),
+                            _ => return Err(ParseError::UnexpectedToken(line)),
+                        },
+                        Some('\n') | None => return Err(ParseError::UnexpectedToken(line)),
+                        Some(c) => string.push(c),
+                    }
+                }
+                Token::Literal(string)
+            }
+            '^' => {
+                chars.next();
+                if chars.next() != Some('^') {
+                    return Err(ParseError::UnexpectedToken(line));
+                }
+                Token::Datatype
+            }
+            '@' => {
+                chars.next();
+                if read_word(&mut chars) == "prefix" {
+                    Token::Prefix
+                } else {
+                    Token::LanguageTag
+                }
+            }
+            '[' => {
+                chars.next();
+                Token::OpenBracket
+            }
+            ']' => {
+                chars.next();
+                Token::CloseBracket
+            }
+            ';' => {
+                chars.next();
+                Token::Semicolon
+            }
+            ',' => {
+                chars.next();
+                Token::Comma
+            }
+            _ => {
+                let word = read_word(&mut chars);
+                if word.is_empty() {
+                    return Err(ParseError::UnexpectedToken(line));
+                }
+                // A dot at the end of a word terminates the statement.
+                let trimmed = word.trim_end_matches('.');
+                if !trimmed.is_empty() {
+                    let first = trimmed.chars().next().unwrap();
+                    if first.is_ascii_digit() || first == '-' || first == '+' || first == '.' {
+                        tokens.push((Token::Number(trimmed.to_owned()), line));
+                    } else {
+                        tokens.push((Token::Name(trimmed.to_owned()), line));
+                    }
+                }
+                for _ in trimmed.len()..word.len() {
+                    tokens.push((Token::Dot, line));
+                }
+                continue;
+            }
+        };
+        tokens.push((token, line));
+    }
+    Ok(tokens)
+}
+
+fn read_word<I: Iterator<Item = char>>(chars: &mut std::iter::Peekable<I>) -> String {
+    let mut word = String::new();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() || "<>\"[];,#()^@".contains(c) {
+            break;
+        }
+        word.push(c);
+        chars.next();
+    }
+    word
+}
+
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    position: usize,
+    prefixes: HashMap<String, String>,
+    blank_nodes: usize,
+    triples: Vec<Triple>,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position).map(|(token, _)| token)
+    }
+
+    fn next(&mut self) -> Result<(Token, usize), ParseError> {
+        let token = self
+            .tokens
+            .get(self.position)
+            .cloned()
+            .ok_or(ParseError::UnexpectedEnd)?;
+        self.position += 1;
+        Ok(token)
+    }
+
+    fn expect(&mut self, expected: Token) -> Result<(), ParseError> {
+        match self.next()? {
+            (ref token, _) if *token == expected => Ok(()),
+            (_, line) => Err(ParseError::UnexpectedToken(line)),
+        }
+    }
+
+    fn parse_document(&mut self) -> Result<(), ParseError> {
+        while let Some(token) = self.peek() {
+            if *token == Token::Prefix {
+                self.next()?;
+                let name = match self.next()? {
+                    (Token::Name(ref name), _) if name.ends_with(':') => name.clone(),
+                    (_, line) => return Err(ParseError::UnexpectedToken(line)),
+                };
+                let iri = match self.next()? {
+                    (Token::Iri(iri), _) => iri,
+                    (_, line) => return Err(ParseError::UnexpectedToken(line)),
+                };
+                self.prefixes
+                    .insert(name.trim_end_matches(':').to_owned(), iri);
+            } else if *token == Token::OpenBracket {
+                let subject = self.parse_blank_node()?;
+                if self.peek() != Some(&Token::Dot) {
+                    self.parse_predicate_object_list(&subject)?;
+                }
+            } else {
+                let subject = self.parse_resource()?;
+                self.parse_predicate_object_list(&subject)?;
+            }
+            self.expect(Token::Dot)?;
+        }
+        Ok(())
+    }
+
+    fn parse_predicate_object_list(&mut self, subject: &Term) -> Result<(), ParseError> {
+        loop {
+            let predicate = match self.next()? {
+                (Token::Name(ref name), _) if name == "a" => RDF_TYPE.to_owned(),
+                (Token::Name(name), line) => self.expand(&name, line)?,
+                (Token::Iri(iri), _) => iri,
+                (_, line) => return Err(ParseError::UnexpectedToken(line)),
+            };
+            loop {
+                let object = self.parse_object()?;
+                self.triples.push(Triple {
+                    subject: subject.clone(),
+                    predicate: predicate.clone(),
+                    object,
+                });
+                if self.peek() == Some(&Token::Comma) {
+                    self.next()?;
+                } else {
+                    break;
+                }
+            }
+            if self.peek() != Some(&Token::Semicolon) {
+                return Ok(());
+            }
+            while self.peek() == Some(&Token::Semicolon) {
+                self.next()?;
+            }
+            match self.peek() {
+                Some(Token::Dot) | Some(Token::CloseBracket) => return Ok(()),
+                _ => (),
+            }
+        }
+    }
+
+    fn parse_blank_node(&mut self) -> Result<Term, ParseError> {
+        self.expect(Token::OpenBracket)?;
+        let node = Term::Blank(self.blank_nodes);
+        self.blank_nodes += 1;
+        if self.peek() != Some(&Token::CloseBracket) {
+            self.parse_predicate_object_list(&node)?;
+        }
+        self.expect(Token::CloseBracket)?;
+        Ok(node)
+    }
+
+    fn parse_resource(&mut self) -> Result<Term, ParseError> {
+        match self.next()? {
+            (Token::Iri(iri), _) => Ok(Term::Uri(iri)),
+            (Token::Name(name), line) => self.expand(&name, line).map(Term::Uri),
+            (_, line) => Err(ParseError::UnexpectedToken(line)),
+        }
+    }
+
+    fn parse_object(&mut self) -> Result<Term, ParseError> {
+        match self.peek() {
+            Some(Token::OpenBracket) => self.parse_blank_node(),
+            Some(Token::Literal(_)) => {
+                let string = match self.next()? {
+                    (Token::Literal(string), _) => string,
+                    _ => unreachable!(),
+                };
+                let datatype = match self.peek() {
+                    Some(Token::Datatype) => {
+                        self.next()?;
+                        match self.parse_resource()? {
+                            Term::Uri(uri) => Some(uri),
+                            _ => unreachable!(),
+                        }
+                    }
+                    Some(Token::LanguageTag) => {
+                        self.next()?;
+                        None
+                    }
+                    _ => None,
+                };
+                Ok(Term::String(string, datatype))
+            }
+            Some(Token::Number(_)) => {
+                let (number, line) = match self.next()? {
+                    (Token::Number(number), line) => (number, line),
+                    _ => unreachable!(),
+                };
+                let term = if number.contains(['.', 'e', 'E']) {
+                    number.parse().ok().map(Term::Decimal)
+                } else {
+                    number.parse().ok().map(Term::Integer)
+                };
+                term.ok_or(ParseError::UnexpectedToken(line))
+            }
+            Some(Token::Name(name)) if name == "true" || name == "false" => {
+                let value = name == "true";
+                self.next()?;
+                Ok(Term::Boolean(value))
+            }
+            _ => self.parse_resource(),
+        }
+    }
+
+    fn expand(&self, name: &str, line: usize) -> Result<String, ParseError> {
+        let colon = name.find(':').ok_or(ParseError::UnexpectedToken(line))?;
+        let prefix = &name[..colon];
+        self.prefixes
+            .get(prefix)
+            .map(|namespace| format!("{}{}", namespace, &name[colon + 1..]))
+            .ok_or_else(|| ParseError::UnknownPrefix(prefix.to_owned()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::ttl::*;
+
+    #[test]
+    fn test_terms() {
+        assert_eq!("<https://example.org>", uri("https://example.org"));
+        assert_eq!("\"a \\\"b\\\"\\n\"", string("a \"b\"\n"));
+        assert_eq!("1.0", float(1.0));
+        assert_eq!("-0.25", float(-0.25));
+        assert_eq!("42", integer(42));
+        assert_eq!("true", boolean(true));
+    }
+
+    #[test]
+    fn test_nested_nodes() {
+        let mut port = Node::blank();
+        port.add("a", "lv2:InputPort").add("lv2:index", integer(0));
+        let mut plugin = Node::new(uri("https://example.org"));
+        plugin.add("a", "lv2:Plugin").add_node("lv2:port", port);
+
+        assert_eq!(
+            "<https://example.org>\n    a lv2:Plugin ;\n    lv2:port [\n        a lv2:InputPort ;\n        lv2:index 0\n    ] .\n",
+            plugin.to_string()
+        );
+    }
+
+    #[test]
+    fn test_parse() {
+        let document = format!(
+            "{}\n# A comment.\n<https://example.org#a> a lv2:Plugin , lv2:DelayPlugin ;\n    rdfs:label \"A \\\"b\\\"\"@en ;\n    lv2:port [ lv2:index 0 ; lv2:default -0.5 ; lv2:maximum 1e1 ] ;\n    lv2:optionalFeature false ;\n    rdfs:comment \"/tmp\"^^atom:Path .\n",
+            PREFIXES
+        );
+        let triples = parse(&document).unwrap();
+        let subject = Term::Uri("https://example.org#a".to_owned());
+        let lv2 = |name: &str| format!("http://lv2plug.in/ns/lv2core#{}", name);
+
+        let types: Vec<&Term> = objects(&triples, &subject, RDF_TYPE).collect();
+        assert_eq!(
+            vec![
+                &Term::Uri(lv2("Plugin")),
+                &Term::Uri(lv2("DelayPlugin"))
+            ],
+            types
+        );
+        assert_eq!(
+            Some(&subject),
+            subjects(&triples, RDF_TYPE, &Term::Uri(lv2("Plugin"))).next()
+        );
+        assert_eq!(
+            Some(&Term::String("A \"b\"".to_owned(), None)),
+            objects(
+                &triples,
+                &subject,
+                "http://www.w3.org/2000/01/rdf-schema#label"
+            )
+            .next()
+        );
+
+        let port_predicate = lv2("port");
+        let port = objects(&triples, &subject, &port_predicate)
+            .next()
+            .unwrap();
+        assert_eq!(&Term::Blank(0), port);
+        assert_eq!(
+            Some(&Term::Integer(0)),
+            objects(&triples, port, &lv2("index")).next()
+        );
+        assert_eq!(
+            Some(&Term::Decimal(-0.5)),
+            objects(&triples, port, &lv2("default")).next()
+        );
+        assert_eq!(
+            Some(&Term::Decimal(10.0)),
+            objects(&triples, port, &lv2("maximum")).next()
+        );
+        assert_eq!(
+            Some(&Term::Boolean(false)),
+            objects(&triples, &subject, &lv2("optionalFeature")).next()
+        );
+        assert_eq!(
+            Some(&Term::String(
+                "/tmp".to_owned(),
+                Some("http://lv2plug.in/ns/ext/atom#Path".to_owned())
+            )),
+            objects(
+                &triples,
+                &subject,
+                "http://www.w3.org/2000/01/rdf-schema#comment"
+            )
+            .next()
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            Err(ParseError::UnknownPrefix("foo".to_owned())),
+            parse("<a> foo:b <c> .")
+        );
+        assert_eq!(Err(ParseError::UnexpectedEnd), parse("<a> <b> <c>"));
+        assert_eq!(
+            Err(ParseError::UnexpectedToken(2)),
+            parse("<a> <b> <c> .\n<a> <b> ( <c> ) .")
+        );
+    }
+}
@@ -0,0 +1,250 @@
+//! Units of control port values.
+//!
+//! Hosts can only display a value like "440 Hz" or "-6 dB" if the port declares its unit with
+//! `units:unit`. This module contains the [`Unit`](enum.Unit.html) enum, which covers the
+//! [LV2 units vocabulary](http://lv2plug.in/ns/extensions/units) as well as custom units, and
+//! helper functions to convert between common units.
+//!
+//! A unit is attached to the [`PortMetadata`](../ports/struct.PortMetadata.html) of a port, which
+//! writes it to the port's Turtle description:
+//!
+//!     extern crate lv2rs_core as core;
+//!     use core::ports::PortMetadata;
+//!     use core::units::*;
+//!
+//!     let metadata = PortMetadata::new(0, "gain", "Gain")
+//!         .with_range(0.0, -90.0, 24.0)
+//!         .with_unit(Unit::Db);
+//!     assert_eq!(Some(&Unit::Db), metadata.unit());
+//!     assert!(metadata.to_node(&["lv2:ControlPort"]).to_string().contains("units:unit units:db"));
+//!
+//!     // The plugin works with coefficients.
+//!     assert_eq!(1.0, db_to_coefficient(0.0));
+use crate::ttl;
+use std::ffi::CStr;
+
+pub const URI: &[u8] = b"http://lv2plug.in/ns/extensions/units\0";
+pub const UNIT_URI: &[u8] = b"http://lv2plug.in/ns/extensions/units#Unit\0";
+pub const BAR_URI: &[u8] = b"http://lv2plug.in/ns/extensions/units#bar\0";
+pub const BEAT_URI: &[u8] = b"http://lv2plug.in/ns/extensions/units#beat\0";
+pub const BPM_URI: &[u8] = b"http://lv2plug.in/ns/extensions/units#bpm\0";
+pub const CENT_URI: &[u8] = b"http://lv2plug.in/ns/extensions/units#cent\0";
+pub const CM_URI: &[u8] = b"http://lv2plug.in/ns/extensions/units#cm\0";
+pub const COEF_URI: &[u8] = b"http://lv2plug.in/ns/extensions/units#coef\0";
+pub const DB_URI: &[u8] = b"http://lv2plug.in/ns/extensions/units#db\0";
+pub const DEGREE_URI: &[u8] = b"http://lv2plug.in/ns/extensions/units#degree\0";
+pub const FRAME_URI: &[u8] = b"http://lv2plug.in/ns/extensions/units#frame\0";
+pub const HZ_URI: &[u8] = b"http://lv2plug.in/ns/extensions/units#hz\0";
+pub const INCH_URI: &[u8] = b"http://lv2plug.in/ns/extensions/units#inch\0";
+pub const KHZ_URI: &[u8] = b"http://lv2plug.in/ns/extensions/units#khz\0";
+pub const KM_URI: &[u8] = b"http://lv2plug.in/ns/extensions/units#km\0";
+pub const M_URI: &[u8] = b"http://lv2plug.in/ns/extensions/units#m\0";
+pub const MHZ_URI: &[u8] = b"http://lv2plug.in/ns/extensions/units#mhz\0";
+pub const MIDI_NOTE_URI: &[u8] = b"http://lv2plug.in/ns/extensions/units#midiNote\0";
+pub const MILE_URI: &[u8] = b"http://lv2plug.in/ns/extensions/units#mile\0";
+pub const MIN_URI: &[u8] = b"http://lv2plug.in/ns/extensions/units#min\0";
+pub const MM_URI: &[u8] = b"http://lv2plug.in/ns/extensions/units#mm\0";
+pub const MS_URI: &[u8] = b"http://lv2plug.in/ns/extensions/units#ms\0";
+pub const OCT_URI: &[u8] = b"http://lv2plug.in/ns/extensions/units#oct\0";
+pub const PC_URI: &[u8] = b"http://lv2plug.in/ns/extensions/units#pc\0";
+pub const S_URI: &[u8] = b"http://lv2plug.in/ns/extensions/units#s\0";
+pub const SEMITONE_12TET_URI: &[u8] = b"http://lv2plug.in/ns/extensions/units#semitone12TET\0";
+
+/// The unit of a control port.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Unit {
+    /// Bars.
+    Bar,
+    /// Beats.
+    Beat,
+    /// Beats per minute.
+    Bpm,
+    /// Cents, hundredths of a semitone.
+    Cent,
+    /// Centimetres.
+    Cm,
+    /// A coefficient, for example a gain factor.
+    Coef,
+    /// Decibels.
+    Db,
+    /// Degrees of an angle.
+    Degree,
+    /// Audio frames.
+    Frame,
+    /// Hertz.
+    Hz,
+    /// Inches.
+    Inch,
+    /// Kilohertz.
+    Khz,
+    /// Kilometres.
+    Km,
+    /// Metres.
+    M,
+    /// Megahertz.
+    Mhz,
+    /// MIDI note numbers.
+    MidiNote,
+    /// Miles.
+    Mile,
+    /// Minutes.
+    Min,
+    /// Millimetres.
+    Mm,
+    /// Milliseconds.
+    Ms,
+    /// Octaves.
+    Oct,
+    /// Percent.
+    Pc,
+    /// Seconds.
+    S,
+    /// Semitones in twelve-tone equal temperament.
+    Semitone12TET,
+    /// A unit that isn't part of the vocabulary.
+    ///
+    /// `render` is a printf-style format string for a single float, for example `"%.1f rad"`.
+    Custom {
+        label: String,
+        symbol: String,
+        render: String,
+    },
+}
+
+const STANDARD_UNITS: [(Unit, &[u8]); 24] = [
+    (Unit::Bar, BAR_URI),
+    (Unit::Beat, BEAT_URI),
+    (Unit::Bpm, BPM_URI),
+    (Unit::Cent, CENT_URI),
+    (Unit::Cm, CM_URI),
+    (Unit::Coef, COEF_URI),
+    (Unit::Db, DB_URI),
+    (Unit::Degree, DEGREE_URI),
+    (Unit::Frame, FRAME_URI),
+    (Unit::Hz, HZ_URI),
+    (Unit::Inch, INCH_URI),
+    (Unit::Khz, KHZ_URI),
+    (Unit::Km, KM_URI),
+    (Unit::M, M_URI),
+    (Unit::Mhz, MHZ_URI),
+    (Unit::MidiNote, MIDI_NOTE_URI),
+    (Unit::Mile, MILE_URI),
+    (Unit::Min, MIN_URI),
+    (Unit::Mm, MM_URI),
+    (Unit::Ms, MS_URI),
+    (Unit::Oct, OCT_URI),
+    (Unit::Pc, PC_URI),
+    (Unit::S, S_URI),
+    (Unit::Semitone12TET, SEMITONE_12TET_URI),
+];
+
+impl Unit {
+    /// Return the URI of the unit.
+    ///
+    /// Custom units don't have a URI and therefore, `None` is returned for them.
+    pub fn get_uri(&self) -> Option<&'static CStr> {
+        STANDARD_UNITS
+            .iter()
+            .find(|(unit, _)| unit == self)
+            .map(|(_, uri)| unsafe { CStr::from_bytes_with_nul_unchecked(uri) })
+    }
+
+    /// Try to get a standard unit from its URI.
+    pub fn from_uri(uri: &CStr) -> Option<Unit> {
+        STANDARD_UNITS
+            .iter()
+            .find(|(_, unit_uri)| *unit_uri == uri.to_bytes_with_nul())
+            .map(|(unit, _)| unit.clone())
+    }
+
+    /// Add the unit to the Turtle description of a port.
+    ///
+    /// Standard units are written as prefixed names, e.g. `units:unit units:db`, and custom units
+    /// as a nested node with their label, symbol and render format. The statement uses the
+    /// prefixes of [`ttl::PREFIXES`](../ttl/constant.PREFIXES.html).
+    pub fn add_to_node(&self, node: &mut ttl::Node) {
+        match self {
+            Unit::Custom {
+                label,
+                symbol,
+                render,
+            } => {
+                let mut unit = ttl::Node::blank();
+                unit.add("a", "units:Unit")
+                    .add("rdfs:label", ttl::string(label))
+                    .add("units:symbol", ttl::string(symbol))
+                    .add("units:render", ttl::string(render));
+                node.add_node("units:unit", unit);
+            }
+            _ => {
+                let uri = self.get_uri().unwrap().to_str().unwrap();
+                let name = &uri[uri.find('#').unwrap() + 1..];
+                node.add("units:unit", format!("units:{}", name));
+            }
+        }
+    }
+}
+
+/// Convert decibels to a gain coefficient.
+pub fn db_to_coefficient(db: f32) -> f32 {
+    10f32.powf(db / 20.0)
+}
+
+/// Convert a gain coefficient to decibels.
+///
+/// A coefficient of zero is converted to negative infinity.
+pub fn coefficient_to_db(coefficient: f32) -> f32 {
+    20.0 * coefficient.log10()
+}
+
+/// Convert a MIDI note number to a frequency in hertz, with A4 (note 69) tuned to 440 Hz.
+pub fn midi_note_to_hz(note: f32) -> f32 {
+    440.0 * 2f32.powf((note - 69.0) / 12.0)
+}
+
+/// Convert a frequency in hertz to a, possibly fractional, MIDI note number.
+pub fn hz_to_midi_note(hz: f32) -> f32 {
+    69.0 + 12.0 * (hz / 440.0).log2()
+}
+
+/// Convert milliseconds to a number of frames, given the sample rate passed to `instantiate`.
+pub fn ms_to_frames(ms: f64, sample_rate: f64) -> f64 {
+    ms * sample_rate / 1000.0
+}
+
+/// Convert a number of frames to milliseconds, given the sample rate passed to `instantiate`.
+pub fn frames_to_ms(frames: f64, sample_rate: f64) -> f64 {
+    frames * 1000.0 / sample_rate
+}
+
+#[cfg(test)]
+mod test {
+    use crate::units::*;
+
+    #[test]
+    fn test_uris() {
+        for (unit, uri) in STANDARD_UNITS.iter() {
+            let uri = CStr::from_bytes_with_nul(uri).unwrap();
+            assert_eq!(Some(uri), unit.get_uri());
+            assert_eq!(Some(unit.clone()), Unit::from_uri(uri));
+        }
+        let custom = Unit::Custom {
+            label: "radian".to_owned(),
+            symbol: "rad".to_owned(),
+            render: "%f rad".to_owned(),
+        };
+        assert_eq!(None, custom.get_uri());
+    }
+
+    #[test]
+    fn test_conversions() {
+        assert!((db_to_coefficient(-6.0) - 0.501_187).abs() < 1e-6);
+        assert!((coefficient_to_db(0.5) + 6.020_6).abs() < 1e-4);
+        assert_eq!(f32::NEG_INFINITY, coefficient_to_db(0.0));
+        assert_eq!(440.0, midi_note_to_hz(69.0));
+        assert!((midi_note_to_hz(60.0) - 261.625_58).abs() < 1e-3);
+        assert!((hz_to_midi_note(880.0) - 81.0).abs() < 1e-5);
+        assert_eq!(4410.0, ms_to_frames(100.0, 44100.0));
+        assert_eq!(100.0, frames_to_ms(4800.0, 48000.0));
+    }
+}
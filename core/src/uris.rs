@@ -68,6 +68,7 @@ pub const IN_PLACE_BROKEN_URI: &[u8] = b"http://lv2plug.in/ns/lv2core#inPlaceBro
 pub const INDEX_URI: &[u8] = b"http://lv2plug.in/ns/lv2core#index\0";
 pub const INTEGER_URI: &[u8] = b"http://lv2plug.in/ns/lv2core#integer\0";
 pub const IS_LIVE_URI: &[u8] = b"http://lv2plug.in/ns/lv2core#isLive\0";
+pub const IS_SIDE_CHAIN_URI: &[u8] = b"http://lv2plug.in/ns/lv2core#isSideChain\0";
 pub const LATENCY_URI: &[u8] = b"http://lv2plug.in/ns/lv2core#latency\0";
 pub const MAXIMUM_URI: &[u8] = b"http://lv2plug.in/ns/lv2core#maximum\0";
 pub const MICRO_VERSION_URI: &[u8] = b"http://lv2plug.in/ns/lv2core#microVersion\0";
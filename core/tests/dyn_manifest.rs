@@ -0,0 +1,153 @@
+extern crate lv2rs_core as core;
+
+use core::dyn_manifest::*;
+use core::{ttl, DescriptorTable};
+use std::ffi::{CStr, CString};
+use std::os::raw::*;
+use std::ptr::{null, null_mut};
+
+extern "C" {
+    fn tmpfile() -> *mut File;
+    fn rewind(stream: *mut File);
+    fn fread(ptr: *mut c_void, size: usize, count: usize, stream: *mut File) -> usize;
+    fn fclose(stream: *mut File) -> c_int;
+}
+
+const SCRIPTS: [&str; 2] = ["delay", "reverb"];
+
+fn script_uri(name: &str) -> String {
+    format!("http://example.org/scripts/{}", name)
+}
+
+/// A plugin whose behaviour depends on the URI it was instantiated with.
+struct Script {
+    name: String,
+}
+
+impl core::Plugin for Script {
+    fn instantiate(
+        descriptor: &core::Descriptor,
+        _rate: f64,
+        _bundle_path: &CStr,
+        _features: Option<&core::FeaturesList>,
+    ) -> Option<Self> {
+        let uri = descriptor.get_uri().to_str().ok()?;
+        let name = SCRIPTS.iter().find(|name| script_uri(name) == uri)?;
+        Some(Self {
+            name: name.to_string(),
+        })
+    }
+
+    fn connect_port(&mut self, _port: u32, _data: *mut ()) {}
+
+    fn run(&mut self, _n_samples: u32) {}
+}
+
+struct Scripts;
+
+impl DynManifest for Scripts {
+    fn register_plugins(descriptors: &DescriptorTable) {
+        for name in SCRIPTS.iter() {
+            descriptors.register::<Script>(&CString::new(script_uri(name)).unwrap());
+        }
+    }
+
+    fn open(_features: Option<&core::FeaturesList>) -> Option<Self> {
+        Some(Scripts)
+    }
+
+    fn subjects(&mut self) -> Vec<ttl::Node> {
+        SCRIPTS
+            .iter()
+            .map(|name| {
+                let mut node = ttl::Node::new(ttl::uri(&script_uri(name)));
+                node.add("a", "lv2:Plugin");
+                node
+            })
+            .collect()
+    }
+
+    fn data(&mut self, uri: &CStr) -> Option<Vec<ttl::Node>> {
+        let uri = uri.to_str().ok()?;
+        let name = SCRIPTS.iter().find(|name| script_uri(name) == uri)?;
+        let mut node = ttl::Node::new(ttl::uri(uri));
+        node.add("a", "lv2:Plugin")
+            .add("lv2:name", ttl::string(name));
+        Some(vec![node])
+    }
+}
+
+core::lv2_dyn_manifest!(core, Scripts);
+
+/// Call the function with a temporary stream and return the written data.
+unsafe fn read_stream<F: FnOnce(*mut File) -> c_int>(function: F) -> (c_int, String) {
+    let stream = tmpfile();
+    assert!(!stream.is_null());
+    let result = function(stream);
+    rewind(stream);
+    let mut buffer = vec![0u8; 4096];
+    let length = fread(buffer.as_mut_ptr() as *mut c_void, 1, buffer.len(), stream);
+    fclose(stream);
+    buffer.truncate(length);
+    (result, String::from_utf8(buffer).unwrap())
+}
+
+#[test]
+fn test_dyn_manifest() {
+    // Descriptors.
+    let delay = unsafe { lv2_descriptor(0).as_ref() }.unwrap();
+    let reverb = unsafe { lv2_descriptor(1).as_ref() }.unwrap();
+    assert_eq!(null(), unsafe { lv2_descriptor(2) });
+    assert_eq!(
+        b"http://example.org/scripts/delay",
+        delay.get_uri().to_bytes()
+    );
+    assert_eq!(
+        Some(reverb as *const core::Descriptor),
+        DESCRIPTORS
+            .find(&CString::new(script_uri("reverb")).unwrap())
+            .map(|descriptor| descriptor as *const core::Descriptor)
+    );
+    unsafe {
+        let handle =
+            (reverb.instantiate)(reverb, 44100.0, b"/\0".as_ptr() as *const c_char, null());
+        assert_eq!("reverb", (*(handle as *mut Script)).name);
+        (reverb.cleanup)(handle);
+    }
+
+    // Manifest generator.
+    assert_eq!(1, unsafe { lv2_dyn_manifest_open(null_mut(), null()) });
+    let mut handle: DynManifestHandle = null_mut();
+    assert_eq!(0, unsafe { lv2_dyn_manifest_open(&mut handle, null()) });
+    assert!(!handle.is_null());
+
+    let (result, subjects) =
+        unsafe { read_stream(|stream| lv2_dyn_manifest_get_subjects(handle, stream)) };
+    assert_eq!(0, result);
+    assert_eq!(
+        format!(
+            "{}\n<http://example.org/scripts/delay>\n    a lv2:Plugin .\n\n<http://example.org/scripts/reverb>\n    a lv2:Plugin .\n",
+            ttl::PREFIXES
+        ),
+        subjects
+    );
+    let triples = ttl::parse(&subjects).unwrap();
+    assert_eq!(2, triples.len());
+
+    let uri = CString::new(script_uri("delay")).unwrap();
+    let (result, data) =
+        unsafe { read_stream(|stream| lv2_dyn_manifest_get_data(handle, stream, uri.as_ptr())) };
+    assert_eq!(0, result);
+    assert!(data.ends_with(
+        "<http://example.org/scripts/delay>\n    a lv2:Plugin ;\n    lv2:name \"delay\" .\n"
+    ));
+
+    let unknown = CString::new(script_uri("chorus")).unwrap();
+    let (result, data) = unsafe {
+        read_stream(|stream| lv2_dyn_manifest_get_data(handle, stream, unknown.as_ptr()))
+    };
+    assert_eq!(1, result);
+    assert!(data.is_empty());
+
+    unsafe { lv2_dyn_manifest_close(handle) };
+}
@@ -0,0 +1,167 @@
+extern crate lv2rs_core as core;
+
+use core::inline_display::*;
+use std::ffi::CStr;
+use std::os::raw::*;
+use std::ptr::{null, null_mut};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+
+/// The display state of the meter, which draws its level as a vertical bar.
+///
+/// The level is stored as the bits of an `f32`, since it's shared with the render thread.
+struct MeterDisplay {
+    level: AtomicU32,
+    storage: DisplayStorage,
+}
+
+impl InlineDisplay for MeterDisplay {
+    fn display_storage(&self) -> &DisplayStorage {
+        &self.storage
+    }
+
+    fn render(&self, width: u32, _max_height: u32) -> Option<Surface> {
+        let level = f32::from_bits(self.level.load(Ordering::Relaxed));
+        if level < 0.0 {
+            return None;
+        }
+        // The meter is always four pixels high, even if it doesn't fit.
+        let height = 4;
+        let mut surface = Surface::new(width, height);
+        let bar_height = (level * height as f32) as u32;
+        surface.fill_rect(
+            0,
+            height - bar_height,
+            width,
+            bar_height,
+            argb(0xff, 0, 0xff, 0),
+        );
+        Some(surface)
+    }
+}
+
+struct Meter {
+    display: Arc<MeterDisplay>,
+}
+
+impl core::Plugin for Meter {
+    fn instantiate(
+        _descriptor: &core::Descriptor,
+        _rate: f64,
+        _bundle_path: &CStr,
+        _features: Option<&core::FeaturesList>,
+    ) -> Option<Self> {
+        Some(Self {
+            display: Arc::new(MeterDisplay {
+                level: AtomicU32::new(0.5f32.to_bits()),
+                storage: DisplayStorage::new(),
+            }),
+        })
+    }
+
+    fn connect_port(&mut self, _port: u32, _data: *mut ()) {}
+
+    fn run(&mut self, _n_samples: u32) {}
+
+    fn inline_display(&self) -> Option<Arc<dyn InlineDisplay>> {
+        Some(self.display.clone())
+    }
+}
+
+core::lv2_main!(core, Meter, b"http://example.org/Meter\0");
+
+#[test]
+fn test_surface() {
+    assert_eq!(0xff102030, argb(0xff, 0x10, 0x20, 0x30));
+    assert_eq!(0x80800000, argb(0x80, 0xff, 0x00, 0x00));
+    assert_eq!(0, argb(0, 0xff, 0xff, 0xff));
+
+    let mut surface = Surface::new(4, 3);
+    assert_eq!(4, surface.width());
+    assert_eq!(3, surface.height());
+    assert_eq!(&[0; 12], surface.pixels());
+
+    surface.fill(1);
+    surface.fill_rect(2, 1, 10, 10, 2);
+    surface.set_pixel(0, 0, 3);
+    surface.set_pixel(4, 0, 4);
+    surface.fill_rect(5, 0, 1, 1, 4);
+    assert_eq!(&[3, 1, 1, 1, 1, 1, 2, 2, 1, 1, 2, 2], surface.pixels());
+    assert_eq!(Some(2), surface.pixel(3, 2));
+    assert_eq!(None, surface.pixel(4, 2));
+    assert_eq!(None, surface.pixel(0, 3));
+
+    surface.pixels_mut()[1] = 5;
+    assert_eq!(Some(5), surface.pixel(1, 0));
+}
+
+#[test]
+fn test_inline_display_interface() {
+    let descriptor = unsafe { lv2_descriptor(0) };
+    let descriptor_ref = unsafe { descriptor.as_ref() }.unwrap();
+    let handle = unsafe {
+        (descriptor_ref.instantiate)(
+            descriptor,
+            44100.0,
+            b"/\0".as_ptr() as *const c_char,
+            null(),
+        )
+    };
+    assert!(!handle.is_null());
+    let display = unsafe { (handle as *const Meter).as_ref() }
+        .unwrap()
+        .display
+        .clone();
+
+    let interface = InlineDisplayInterface::new();
+    let green = argb(0xff, 0, 0xff, 0);
+
+    let raw = unsafe { (interface.render)(handle, 2, 10).as_ref() }.unwrap();
+    assert_eq!(2, raw.width);
+    assert_eq!(4, raw.height);
+    assert_eq!(8, raw.stride);
+    let pixels = unsafe { std::slice::from_raw_parts(raw.data as *const u32, 8) };
+    assert_eq!(&[0, 0, 0, 0, green, green, green, green], pixels);
+    assert_eq!(pixels, display.storage.surface().unwrap().pixels());
+
+    // The surface is higher than allowed.
+    assert!(unsafe { (interface.render)(handle, 2, 3) }.is_null());
+    assert!(display.storage.surface().is_none());
+
+    // Nothing to display.
+    display.level.store((-1.0f32).to_bits(), Ordering::Relaxed);
+    assert!(unsafe { (interface.render)(handle, 2, 10) }.is_null());
+
+    // Instances without a display and cleaned up instances aren't drawn.
+    display.level.store(0.5f32.to_bits(), Ordering::Relaxed);
+    assert!(unsafe { (interface.render)(null_mut(), 2, 10) }.is_null());
+    unsafe { (descriptor_ref.cleanup)(handle) };
+    assert!(unsafe { (interface.render)(handle, 2, 10) }.is_null());
+}
+
+static DRAWS: AtomicU32 = AtomicU32::new(0);
+
+unsafe extern "C" fn queue_draw(handle: *mut c_void) {
+    assert_eq!(0x1234 as *mut c_void, handle);
+    DRAWS.fetch_add(1, Ordering::SeqCst);
+}
+
+#[test]
+fn test_queue_draw() {
+    let mut feature_data = QueueDraw {
+        handle: 0x1234 as *mut c_void,
+        queue_draw,
+    };
+    let feature = core::Feature::new(
+        CStr::from_bytes_with_nul(QUEUE_DRAW_URI).unwrap(),
+        &mut feature_data as *mut QueueDraw as *mut c_void,
+    );
+    let features = [unsafe { &*(&feature as *const core::Feature) }];
+
+    let queue = QueueDraw::try_from_features(&features).unwrap();
+    queue.queue_draw();
+    queue.queue_draw();
+    assert_eq!(2, DRAWS.load(Ordering::SeqCst));
+
+    assert!(QueueDraw::try_from_features(&[]).is_none());
+}
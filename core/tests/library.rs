@@ -0,0 +1,112 @@
+extern crate lv2rs_core as core;
+
+use core::library::*;
+use core::DescriptorTable;
+use std::ffi::CStr;
+use std::os::raw::*;
+use std::ptr::null;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+static DROPPED: AtomicBool = AtomicBool::new(false);
+
+/// A wavetable bank that is loaded once for all oscillators.
+struct Wavetables {
+    bundle_path: String,
+    tables: Vec<Vec<f32>>,
+}
+
+impl Library for Wavetables {
+    fn instantiate(bundle_path: &CStr, _features: Option<&core::FeaturesList>) -> Option<Self> {
+        Some(Self {
+            bundle_path: bundle_path.to_str().ok()?.to_owned(),
+            tables: vec![vec![0.5; 16], vec![1.0; 16]],
+        })
+    }
+
+    fn register_plugins(&self, descriptors: &DescriptorTable) {
+        for index in 0..self.tables.len() {
+            let uri = format!("http://example.org/oscillator/{}\0", index);
+            descriptors.register::<Oscillator>(CStr::from_bytes_with_nul(uri.as_bytes()).unwrap());
+        }
+    }
+}
+
+impl Drop for Wavetables {
+    fn drop(&mut self) {
+        DROPPED.store(true, Ordering::SeqCst);
+    }
+}
+
+/// An oscillator that plays one of the tables.
+struct Oscillator {
+    wavetables: Arc<Wavetables>,
+    table: usize,
+}
+
+impl core::Plugin for Oscillator {
+    fn instantiate(
+        descriptor: &core::Descriptor,
+        _rate: f64,
+        _bundle_path: &CStr,
+        _features: Option<&core::FeaturesList>,
+    ) -> Option<Self> {
+        let uri = descriptor.get_uri().to_str().ok()?;
+        let table = uri.rsplit('/').next()?.parse().ok()?;
+        Some(Self {
+            wavetables: LIBRARY.get()?,
+            table,
+        })
+    }
+
+    fn connect_port(&mut self, _port: u32, _data: *mut ()) {}
+
+    fn run(&mut self, _n_samples: u32) {}
+}
+
+core::lv2_lib_main!(core, Wavetables);
+
+#[test]
+fn test_library() {
+    let bundle_path = b"/usr/lib/lv2/wavetables.lv2/\0";
+    let bundle_path = bundle_path.as_ptr() as *const c_char;
+    assert!(LIBRARY.get().is_none());
+    assert!(unsafe { lv2_lib_descriptor(null(), null()) }.is_null());
+
+    // The host loads the library twice.
+    let first = unsafe { lv2_lib_descriptor(bundle_path, null()).as_ref() }.unwrap();
+    let second = unsafe { lv2_lib_descriptor(bundle_path, null()).as_ref() }.unwrap();
+    assert_eq!(std::mem::size_of::<LibDescriptor>() as u32, first.size);
+    assert_eq!(2, LIBRARY.descriptors().len());
+    assert_eq!(
+        "/usr/lib/lv2/wavetables.lv2/",
+        LIBRARY.get().unwrap().bundle_path
+    );
+
+    let descriptor = unsafe { (first.get_plugin)(first.handle, 1).as_ref() }.unwrap();
+    assert_eq!(
+        b"http://example.org/oscillator/1",
+        descriptor.get_uri().to_bytes()
+    );
+    assert!(unsafe { (first.get_plugin)(first.handle, 2) }.is_null());
+    assert_eq!(descriptor as *const core::Descriptor, unsafe {
+        (second.get_plugin)(second.handle, 1)
+    });
+
+    let instance = unsafe { (descriptor.instantiate)(descriptor, 44100.0, bundle_path, null()) };
+    let oscillator = unsafe { (instance as *mut Oscillator).as_ref() }.unwrap();
+    assert_eq!(1, oscillator.table);
+    assert_eq!(
+        &[1.0; 16][..],
+        &oscillator.wavetables.tables[oscillator.table][..]
+    );
+
+    // The library stays loaded as long as it's used.
+    unsafe { (first.cleanup)(first.handle) };
+    assert!(LIBRARY.get().is_some());
+    unsafe { (second.cleanup)(second.handle) };
+    assert!(LIBRARY.get().is_none());
+    assert!(!DROPPED.load(Ordering::SeqCst));
+    unsafe { (descriptor.cleanup)(instance) };
+    assert!(DROPPED.load(Ordering::SeqCst));
+}
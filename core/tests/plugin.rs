@@ -157,3 +157,40 @@ fn test_plugin() {
     // cleanup.
     unsafe { (descriptor_ref.cleanup)(host.handle) };
 }
+
+/// A plugin that does nothing, used to test the registration of descriptors.
+struct SilentPlugin;
+
+impl core::Plugin for SilentPlugin {
+    fn instantiate(
+        _descriptor: &core::Descriptor,
+        _rate: f64,
+        _bundle_path: &CStr,
+        _features: Option<&core::FeaturesList>,
+    ) -> Option<Self> {
+        Some(SilentPlugin)
+    }
+
+    fn connect_port(&mut self, _port: u32, _data: *mut ()) {}
+
+    fn run(&mut self, _n_samples: u32) {}
+}
+
+#[test]
+fn test_descriptor_table() {
+    let table = core::DescriptorTable::new();
+    let uri = CStr::from_bytes_with_nul(b"http://example.org/Plugin\0").unwrap();
+
+    let descriptor = table.register::<TestPlugin>(uri).unwrap();
+    assert_eq!(uri, descriptor.get_uri());
+
+    // Registering the same plugin again returns the existing descriptor.
+    let again = table.register::<TestPlugin>(uri).unwrap();
+    assert!(std::ptr::eq(descriptor, again));
+
+    // Another plugin type can't take over the URI.
+    assert!(table.register::<SilentPlugin>(uri).is_none());
+    assert_eq!(1, table.len());
+    assert!(std::ptr::eq(descriptor, table.find(uri).unwrap()));
+    assert!(std::ptr::eq(descriptor, table.get(0).unwrap()));
+}
@@ -0,0 +1,367 @@
+extern crate lv2rs_core as core;
+
+use core::port_groups::{Channel, PortGroup};
+use core::port_props::PortProperty;
+use core::ports::*;
+use core::units::Unit;
+
+#[test]
+fn test_port_metadata() {
+    let frequency = ParameterInputPort::with_metadata(
+        PortMetadata::new(0, "frequency", "Frequency")
+            .with_range(440.0, 20.0, 20000.0)
+            .with_unit(Unit::Hz),
+    );
+    assert_eq!(
+        "[
+    a lv2:InputPort ;
+    a lv2:ControlPort ;
+    lv2:index 0 ;
+    lv2:symbol \"frequency\" ;
+    lv2:name \"Frequency\" ;
+    lv2:default 440.0 ;
+    lv2:minimum 20.0 ;
+    lv2:maximum 20000.0 ;
+    units:unit units:hz
+]",
+        frequency.to_node().unwrap().to_string()
+    );
+
+    let phase = ParameterOutputPort::with_metadata(
+        PortMetadata::new(1, "phase", "Phase").with_unit(Unit::Custom {
+            label: "radian".to_owned(),
+            symbol: "rad".to_owned(),
+            render: "%.2f rad".to_owned(),
+        }),
+    );
+    assert_eq!(
+        "[
+    a lv2:OutputPort ;
+    a lv2:ControlPort ;
+    lv2:index 1 ;
+    lv2:symbol \"phase\" ;
+    lv2:name \"Phase\" ;
+    units:unit [
+        a units:Unit ;
+        rdfs:label \"radian\" ;
+        units:symbol \"rad\" ;
+        units:render \"%.2f rad\"
+    ]
+]",
+        phase.to_node().unwrap().to_string()
+    );
+
+    assert!(ParameterInputPort::new().to_node().is_none());
+    assert_eq!(
+        Some(&Unit::Hz),
+        frequency.metadata().and_then(PortMetadata::unit)
+    );
+}
+
+#[test]
+fn test_port_properties() {
+    let mode_metadata = PortMetadata::new(0, "mode", "Mode")
+        .with_range(0.0, 0.0, 2.0)
+        .with_property(PortProperty::Enumeration)
+        .with_property(PortProperty::Integer)
+        .with_property(PortProperty::CausesArtifacts)
+        .with_scale_point("Low", 0.0)
+        .with_scale_point("High", 2.0);
+    assert_eq!(2.0, mode_metadata.apply_properties(1.2));
+    assert_eq!(0.0, mode_metadata.apply_properties(-5.0));
+    assert_eq!(
+        "[
+    a lv2:InputPort ;
+    a lv2:ControlPort ;
+    lv2:index 0 ;
+    lv2:symbol \"mode\" ;
+    lv2:name \"Mode\" ;
+    lv2:default 0.0 ;
+    lv2:minimum 0.0 ;
+    lv2:maximum 2.0 ;
+    lv2:portProperty lv2:enumeration ;
+    lv2:portProperty lv2:integer ;
+    lv2:portProperty pprops:causesArtifacts ;
+    lv2:scalePoint [
+        rdfs:label \"Low\" ;
+        rdf:value 0.0
+    ] ;
+    lv2:scalePoint [
+        rdfs:label \"High\" ;
+        rdf:value 2.0
+    ]
+]",
+        ParameterInputPort::with_metadata(mode_metadata)
+            .to_node()
+            .unwrap()
+            .to_string()
+    );
+
+    // Strict bounds and integers.
+    let voices = PortMetadata::new(1, "voices", "Voices")
+        .with_range(4.0, 1.0, 16.0)
+        .with_property(PortProperty::HasStrictBounds)
+        .with_property(PortProperty::Integer);
+    assert_eq!(16.0, voices.apply_properties(100.0));
+    assert_eq!(3.0, voices.apply_properties(2.6));
+
+    // Switches.
+    let bypass = PortMetadata::new(2, "bypass", "Bypass").with_property(PortProperty::Toggled);
+    assert_eq!(1.0, bypass.apply_properties(0.3));
+    assert_eq!(0.0, bypass.apply_properties(-1.0));
+
+    // Logarithmic ranges with steps.
+    let frequency = PortMetadata::new(3, "frequency", "Frequency")
+        .with_range(1000.0, 10.0, 10000.0)
+        .with_property(PortProperty::Logarithmic)
+        .with_range_steps(4);
+    assert_eq!(Some(2.0 / 3.0), frequency.normalize(1000.0));
+    assert!((frequency.denormalize(0.4).unwrap() - 100.0).abs() < 1e-3);
+    assert_eq!(None, PortMetadata::new(4, "x", "X").normalize(1.0));
+    let linear = PortMetadata::new(5, "mix", "Mix").with_range(0.5, 0.0, 2.0);
+    assert_eq!(Some(0.25), linear.normalize(0.5));
+    assert_eq!(Some(1.0), linear.denormalize(0.5));
+
+    // Empty ranges can't be normalized.
+    let fixed = PortMetadata::new(6, "fixed", "Fixed").with_range(1.0, 1.0, 1.0);
+    assert_eq!(None, fixed.normalize(1.0));
+    let fixed = fixed.with_property(PortProperty::Logarithmic);
+    assert_eq!(None, fixed.normalize(1.0));
+}
+
+#[test]
+fn test_port_runtime_behaviour() {
+    // Unconnected ports.
+    let optional = ParameterInputPort::with_metadata(
+        PortMetadata::new(0, "sidechain", "Sidechain")
+            .with_property(PortProperty::ConnectionOptional),
+    );
+    let required = ParameterInputPort::with_metadata(PortMetadata::new(1, "gain", "Gain"));
+    unsafe {
+        assert_eq!(Ok(None), optional.value());
+        assert_eq!(Err(PortError::NotConnected), required.value());
+        assert_eq!(
+            Err(PortError::NotConnected),
+            ParameterInputPort::new().value()
+        );
+    }
+    let mut optional_output = ParameterOutputPort::with_metadata(
+        PortMetadata::new(2, "level", "Level").with_property(PortProperty::ConnectionOptional),
+    );
+    let mut required_output = ParameterOutputPort::new();
+    unsafe {
+        assert_eq!(Ok(()), optional_output.set(1.0));
+        assert_eq!(Err(PortError::NotConnected), required_output.set(1.0));
+    }
+
+    // Connected ports apply their properties.
+    let steps_value: f32 = 2.4;
+    let mut steps = ParameterInputPort::with_metadata(
+        PortMetadata::new(3, "steps", "Steps").with_property(PortProperty::Integer),
+    );
+    steps.connect(&steps_value);
+    unsafe { assert_eq!(Ok(Some(2.0)), steps.value()) };
+    let mut toggle_value: f32 = 0.0;
+    let mut toggle = ParameterOutputPort::with_metadata(
+        PortMetadata::new(4, "active", "Active").with_property(PortProperty::Toggled),
+    );
+    toggle.connect(&mut toggle_value);
+    unsafe { toggle.set(0.7) }.unwrap();
+    assert_eq!(1.0, toggle_value);
+
+    // Trigger ports are reset to their default.
+    let mut beat = ParameterOutputPort::with_metadata(
+        PortMetadata::new(6, "beat", "Beat")
+            .with_range(0.0, 0.0, 1.0)
+            .with_property(PortProperty::Trigger),
+    );
+    let mut beat_value: f32 = 0.0;
+    beat.connect(&mut beat_value);
+    unsafe {
+        beat.set(1.0).unwrap();
+        beat.reset_trigger();
+    }
+    assert_eq!(0.0, beat_value);
+
+    // Other ports are not touched.
+    let mut level = ParameterOutputPort::with_metadata(
+        PortMetadata::new(7, "level", "Level").with_range(0.0, 0.0, 1.0),
+    );
+    let mut level_value: f32 = 0.0;
+    level.connect(&mut level_value);
+    unsafe {
+        level.set(0.5).unwrap();
+        level.reset_trigger();
+    }
+    assert_eq!(0.5, level_value);
+}
+
+/// A plugin that signals a beat whenever it's told to.
+struct Metronome {
+    beat: ParameterOutputPort,
+    signal: bool,
+}
+
+impl core::Plugin for Metronome {
+    fn instantiate(
+        _descriptor: &core::Descriptor,
+        _rate: f64,
+        _bundle_path: &std::ffi::CStr,
+        _features: Option<&core::FeaturesList>,
+    ) -> Option<Self> {
+        None
+    }
+
+    fn connect_port(&mut self, _port: u32, data: *mut ()) {
+        self.beat.connect(data as *mut f32);
+    }
+
+    fn run(&mut self, _n_samples: u32) {
+        if self.signal {
+            unsafe { self.beat.set(1.0) }.unwrap();
+        }
+    }
+
+    fn visit_trigger_ports(&mut self, visitor: &mut dyn FnMut(&mut ParameterOutputPort)) {
+        visitor(&mut self.beat);
+    }
+}
+
+#[test]
+fn test_trigger_reset() {
+    let mut metronome = Metronome {
+        beat: ParameterOutputPort::with_metadata(
+            PortMetadata::new(0, "beat", "Beat")
+                .with_range(0.0, 0.0, 1.0)
+                .with_property(PortProperty::Trigger),
+        ),
+        signal: true,
+    };
+    let mut beat_value: f32 = 0.0;
+    core::Plugin::connect_port(&mut metronome, 0, &mut beat_value as *mut f32 as *mut ());
+    let handle = &mut metronome as *mut Metronome as core::Handle;
+
+    // The beat stays until the next run and is reset then.
+    unsafe { core::run::<Metronome>(handle, 1) };
+    assert_eq!(1.0, beat_value);
+    metronome.signal = false;
+    let handle = &mut metronome as *mut Metronome as core::Handle;
+    unsafe { core::run::<Metronome>(handle, 1) };
+    assert_eq!(0.0, beat_value);
+}
+
+#[test]
+fn test_audio_bus() {
+    let mut input: AudioBus<AudioInputPort, 2> = AudioBus::new(
+        PortGroup::new("https://example.org#in", "in", "Input"),
+        [
+            (Channel::Left, PortMetadata::new(0, "in_l", "Left Input")),
+            (Channel::Right, PortMetadata::new(1, "in_r", "Right Input")),
+        ],
+    );
+    let mut sidechain: AudioBus<AudioInputPort, 1> = AudioBus::new(
+        PortGroup::new("https://example.org#sidechain", "sidechain", "Sidechain")
+            .with_side_chain_of("https://example.org#in"),
+        [(Channel::Center, PortMetadata::new(2, "sc", "Sidechain"))],
+    );
+    let mut output: AudioBus<AudioOutputPort, 2> = AudioBus::new(
+        PortGroup::new("https://example.org#out", "out", "Output"),
+        [
+            (Channel::Center, PortMetadata::new(3, "mid", "Mid")),
+            (Channel::Side, PortMetadata::new(4, "side", "Side")),
+        ],
+    );
+
+    // Description.
+    assert_eq!(
+        "<https://example.org#in>
+    a pg:StereoGroup ;
+    a pg:InputGroup ;
+    lv2:symbol \"in\" ;
+    lv2:name \"Input\" .
+",
+        input.group_node().to_string()
+    );
+    assert_eq!(
+        "<https://example.org#sidechain>
+    a pg:MonoGroup ;
+    a pg:InputGroup ;
+    lv2:symbol \"sidechain\" ;
+    lv2:name \"Sidechain\" ;
+    pg:sideChainOf <https://example.org#in> .
+",
+        sidechain.group_node().to_string()
+    );
+    assert!(output
+        .group_node()
+        .to_string()
+        .contains("a pg:MidSideGroup ;\n    a pg:OutputGroup"));
+    let port_nodes: Vec<String> = input
+        .port_nodes()
+        .iter()
+        .map(|node| node.to_string())
+        .collect();
+    assert_eq!(
+        "[
+    a lv2:InputPort ;
+    a lv2:AudioPort ;
+    lv2:index 1 ;
+    lv2:symbol \"in_r\" ;
+    lv2:name \"Right Input\" ;
+    pg:group <https://example.org#in> ;
+    lv2:designation pg:right
+]",
+        port_nodes[1]
+    );
+    assert!(sidechain.port_nodes()[0]
+        .to_string()
+        .contains("lv2:portProperty lv2:isSideChain"));
+    assert!(sidechain.metadata()[0].has_property(PortProperty::IsSideChain));
+    assert!(output.port_nodes()[0]
+        .to_string()
+        .contains("a lv2:OutputPort ;\n    a lv2:AudioPort"));
+
+    // Connection.
+    let mut left = [0.0f32, 0.5, 1.0];
+    let mut right = [1.0f32, 0.5, 0.0];
+    let mut mid = [0.0f32; 3];
+    let mut side = [0.0f32; 3];
+    assert!(input.connect(0, left.as_mut_ptr() as *mut ()));
+    assert!(!input.connect(2, std::ptr::null_mut()));
+    assert!(!sidechain.connect(0, left.as_mut_ptr() as *mut ()));
+    assert!(output.connect(3, mid.as_mut_ptr() as *mut ()));
+    assert!(output.connect(4, side.as_mut_ptr() as *mut ()));
+    unsafe {
+        assert!(input.as_slices(3).is_none());
+        assert!(input.frames(3).is_none());
+    }
+    assert!(input.connect(1, right.as_mut_ptr() as *mut ()));
+    assert!(input.port(Channel::Right).is_some());
+    assert!(input.port(Channel::Center).is_none());
+    assert_eq!(&[Channel::Left, Channel::Right], input.channels());
+
+    // Per-channel access.
+    unsafe {
+        let [left, right] = input.as_slices(3).unwrap();
+        assert_eq!(&[0.0, 0.5, 1.0], left);
+        assert_eq!(&[1.0, 0.5, 0.0], right);
+        for channel in output.as_slices(3).unwrap().iter_mut() {
+            for sample in channel.iter_mut() {
+                *sample = 2.0;
+            }
+        }
+    }
+    assert_eq!([2.0; 3], mid);
+
+    // Per-frame access.
+    unsafe {
+        for ([left, right], [mid, side]) in input.frames(3).unwrap().zip(output.frames(3).unwrap())
+        {
+            *mid = (left + right) / 2.0;
+            *side = (left - right) / 2.0;
+        }
+        assert_eq!(3, input.frames(3).unwrap().count());
+    }
+    assert_eq!([0.5; 3], mid);
+    assert_eq!([-0.5, 0.0, 0.5], side);
+}
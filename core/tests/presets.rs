@@ -0,0 +1,198 @@
+extern crate lv2rs_core as core;
+
+use core::ports::ParameterInputPort;
+use core::presets::*;
+use core::ttl::ParseError;
+
+#[test]
+fn test_presets() {
+    let gain_value: f32 = 0.75;
+    let mut gain = ParameterInputPort::new();
+    let bypass = ParameterInputPort::new();
+
+    // Capturing the values of the connected ports.
+    let mut preset = Preset::new(
+        "https://example.org/presets#quiet",
+        "https://example.org#amp",
+    );
+    preset.set_label("Quiet \"Amp\"");
+    gain.connect(&gain_value);
+    unsafe {
+        preset.capture_port("gain", &gain);
+        preset.capture_port("bypass", &bypass);
+    }
+    preset.set_state_value(
+        "https://example.org#sample",
+        StateValue::Path("/tmp/kick.wav".to_owned()),
+    );
+    assert_eq!(Some(0.75), preset.port_value("gain"));
+    assert_eq!(None, preset.port_value("bypass"));
+
+    assert_eq!(
+        format!(
+            "{}
+<https://example.org/presets#quiet>
+    a pset:Preset ;
+    lv2:appliesTo <https://example.org#amp> ;
+    rdfs:label \"Quiet \\\"Amp\\\"\" ;
+    lv2:port [
+        lv2:symbol \"gain\" ;
+        pset:value 0.75
+    ] ;
+    state:state [
+        <https://example.org#sample> \"/tmp/kick.wav\"^^atom:Path
+    ] .
+",
+            core::ttl::PREFIXES
+        ),
+        preset.to_turtle()
+    );
+    assert_eq!(
+        "<https://example.org/presets#quiet>
+    a pset:Preset ;
+    lv2:appliesTo <https://example.org#amp> ;
+    rdfs:seeAlso <quiet.ttl> .
+",
+        preset.manifest_entry("quiet.ttl")
+    );
+
+    // Reading a hand-written preset and applying it.
+    let document = "@prefix lv2: <http://lv2plug.in/ns/lv2core#> .
+@prefix pset: <http://lv2plug.in/ns/ext/presets#> .
+@prefix rdfs: <http://www.w3.org/2000/01/rdf-schema#> .
+@prefix state: <http://lv2plug.in/ns/ext/state#> .
+
+<https://example.org/presets#loud>
+    a pset:Preset ;
+    lv2:appliesTo <https://example.org#amp> ;
+    rdfs:label \"Loud\" ;
+    lv2:port [
+        lv2:symbol \"gain\" ;
+        pset:value 2
+    ] , [
+        lv2:symbol \"bypass\" ;
+        pset:value 0.0
+    ] ;
+    state:state [
+        <https://example.org#name> \"Loud\" ;
+        <https://example.org#voices> 4
+    ] .
+";
+    let presets = Preset::from_turtle(document).unwrap();
+    assert_eq!(1, presets.len());
+    let preset = &presets[0];
+    assert_eq!("https://example.org/presets#loud", preset.uri());
+    assert_eq!("https://example.org#amp", preset.plugin_uri());
+    assert_eq!(Some("Loud"), preset.label());
+
+    let mut ports = Vec::new();
+    let mut state = Vec::new();
+    preset.apply(
+        |symbol, value| ports.push((symbol.to_owned(), value)),
+        |property, value| state.push((property.to_owned(), value.clone())),
+    );
+    assert_eq!(
+        vec![("gain".to_owned(), 2.0), ("bypass".to_owned(), 0.0)],
+        ports
+    );
+    assert_eq!(
+        vec![
+            (
+                "https://example.org#name".to_owned(),
+                StateValue::String("Loud".to_owned())
+            ),
+            ("https://example.org#voices".to_owned(), StateValue::Int(4)),
+        ],
+        state
+    );
+
+    // The manifest entry only announces the preset.
+    let manifest = format!(
+        "{}\n{}",
+        core::ttl::PREFIXES,
+        preset.manifest_entry("loud.ttl")
+    );
+    let entries = Preset::from_turtle(&manifest).unwrap();
+    assert_eq!(
+        vec![Preset::new(
+            "https://example.org/presets#loud",
+            "https://example.org#amp"
+        )],
+        entries
+    );
+}
+
+/// An instance of a sampler, with a single control port and a state property.
+struct Sampler {
+    volume: ParameterInputPort,
+    sample: String,
+}
+
+impl CapturePreset for Sampler {
+    fn visit_ports(&self, visitor: &mut dyn FnMut(&str, &ParameterInputPort)) {
+        visitor("volume", &self.volume);
+    }
+
+    fn visit_state(&self, visitor: &mut dyn FnMut(&str, StateValue)) {
+        visitor(
+            "https://example.org#sample",
+            StateValue::Path(self.sample.clone()),
+        );
+    }
+}
+
+#[test]
+fn test_capture_instance() {
+    let volume_value: f32 = 0.5;
+    let mut sampler = Sampler {
+        volume: ParameterInputPort::new(),
+        sample: "/tmp/snare.wav".to_owned(),
+    };
+    sampler.volume.connect(&volume_value);
+
+    let mut preset = Preset::new(
+        "https://example.org/presets#snare",
+        "https://example.org#sampler",
+    );
+    unsafe { preset.capture(&sampler) };
+    assert_eq!(
+        vec![("volume", 0.5)],
+        preset.port_values().collect::<Vec<_>>()
+    );
+    assert_eq!(
+        Some(&StateValue::Path("/tmp/snare.wav".to_owned())),
+        preset.state_value("https://example.org#sample")
+    );
+}
+
+#[test]
+fn test_preset_errors() {
+    let prefixes = "@prefix lv2: <http://lv2plug.in/ns/lv2core#> .
+@prefix pset: <http://lv2plug.in/ns/ext/presets#> .
+@prefix state: <http://lv2plug.in/ns/ext/state#> .
+";
+    assert_eq!(
+        Err(PresetError::Parse(ParseError::UnexpectedEnd)),
+        Preset::from_turtle("<a> <b> <c>")
+    );
+    assert_eq!(
+        Err(PresetError::MissingPlugin("a".to_owned())),
+        Preset::from_turtle(&format!("{}<a> a pset:Preset .", prefixes))
+    );
+    assert_eq!(
+        Err(PresetError::MalformedPort("a".to_owned())),
+        Preset::from_turtle(&format!(
+            "{}<a> a pset:Preset ; lv2:appliesTo <b> ; lv2:port [ pset:value 1.0 ] .",
+            prefixes
+        ))
+    );
+
+    // Integers that don't fit into an `i32` aren't wrapped.
+    assert_eq!(
+        Err(PresetError::MalformedState("a".to_owned())),
+        Preset::from_turtle(&format!(
+            "{}<a> a pset:Preset ; lv2:appliesTo <b> ; state:state [ <c> 3000000000 ] .",
+            prefixes
+        ))
+    );
+}
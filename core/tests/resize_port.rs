@@ -0,0 +1,73 @@
+extern crate lv2rs_core as core;
+
+use core::ports::PortMetadata;
+use core::resize_port::*;
+use core::Feature;
+use std::ffi::CStr;
+use std::os::raw::*;
+use std::ptr::null_mut;
+
+/// A stand-in host that records the requested buffer sizes.
+struct TestHost {
+    sizes: Vec<(u32, usize)>,
+}
+
+unsafe extern "C" fn resize(data: *mut c_void, index: u32, size: usize) -> c_int {
+    let host = (data as *mut TestHost).as_mut().unwrap();
+    if size > 1 << 20 {
+        return ERR_NO_SPACE;
+    }
+    if index > 3 {
+        return ERR_UNKNOWN;
+    }
+    host.sizes.push((index, size));
+    SUCCESS
+}
+
+#[test]
+fn test_resize_port() {
+    let mut host = TestHost { sizes: Vec::new() };
+    let mut resize_port = ResizePort {
+        data: &mut host as *mut TestHost as *mut c_void,
+        resize,
+    };
+    let feature = Feature::new(
+        CStr::from_bytes_with_nul(RESIZE_URI).unwrap(),
+        &mut resize_port as *mut ResizePort as *mut c_void,
+    );
+    let other_feature = Feature::new(
+        CStr::from_bytes_with_nul(b"http://example.org#other\0").unwrap(),
+        null_mut(),
+    );
+    let feature: &'static Feature = unsafe { &*(&feature as *const Feature) };
+    let other_feature: &'static Feature = unsafe { &*(&other_feature as *const Feature) };
+
+    assert!(ResizePort::try_from_features(&[other_feature]).is_none());
+    let resize_port = ResizePort::try_from_features(&[other_feature, feature]).unwrap();
+    assert_eq!(Ok(()), resize_port.resize(2, 4096));
+    assert_eq!(
+        Err(ResizePortError::NoSpace),
+        resize_port.resize(2, 1 << 21)
+    );
+    assert_eq!(Err(ResizePortError::Unknown), resize_port.resize(7, 4096));
+    assert_eq!(vec![(2, 4096)], host.sizes);
+}
+
+#[test]
+fn test_minimum_size() {
+    let metadata = PortMetadata::new(2, "spectrum", "Spectrum").with_minimum_size(65536);
+    assert_eq!(Some(65536), metadata.minimum_size());
+    assert_eq!(
+        "[
+    a lv2:OutputPort ;
+    a atom:AtomPort ;
+    lv2:index 2 ;
+    lv2:symbol \"spectrum\" ;
+    lv2:name \"Spectrum\" ;
+    rsz:minimumSize 65536
+]",
+        metadata
+            .to_node(&["lv2:OutputPort", "atom:AtomPort"])
+            .to_string()
+    );
+}
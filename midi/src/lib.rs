@@ -4,7 +4,8 @@
 //! [`RawMidiMessage`](atom/struct.RawMidiMessage.html) and
 //! [`SystemExclusiveMessage`](atom/struct.SystemExclusiveMessage.html), as well as means to use
 //! them. Using these structs, one can read from external controlling devices or keyboards or
-//! controll synthesizers or even create a light show!
+//! controll synthesizers or even create a light show! Plugins may also tell the host the names of
+//! their notes and controllers with the [`midnam`](midnam/index.html) extension.
 //! 
 //! This is a frozen prototype and therefore, development of this crate will not continue here. Further
 //! development continues as [rust-lv2](https://github.com/rust-dsp/rust-lv2).
@@ -12,11 +13,13 @@
 //! This crate depends on the non-standard integer types introduced by the `ux` crate, but you don't
 //! need to depend on it too. The required types are exported too.
 extern crate lv2rs_atom;
+extern crate lv2rs_core as core;
 extern crate lv2rs_urid;
 extern crate ux;
 
 mod atom;
 mod message;
+pub mod midnam;
 pub mod status_bytes;
 pub mod uris;
 
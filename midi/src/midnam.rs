@@ -0,0 +1,321 @@
+//! Names of notes and controllers.
+//!
+//! Drum machines and other instruments often assign a meaning to every note, like "Kick" or
+//! "Snare", and name the controllers they react to. With the
+//! [midnam extension](http://ardour.org/lv2/midnam), a plugin tells the host about these names,
+//! which are then shown in the piano roll or the automation lanes of the host.
+//!
+//! The names are described with a [`MidiNames`](struct.MidiNames.html) document, which is
+//! translated to the MIDNAM XML format. A plugin provides it by implementing the
+//! [`Midnam`](trait.Midnam.html) trait and returning the [`MidnamInterface`](struct.MidnamInterface.html)
+//! in its `extension_data` function. If the names change, for example because another drum kit
+//! was loaded, the plugin notifies the host with the [`MidnamNotify`](struct.MidnamNotify.html)
+//! feature:
+//!
+//!     extern crate lv2rs_midi as midi;
+//!
+//!     use midi::midnam::*;
+//!     use midi::prelude::*;
+//!
+//!     let names = MidiNames::new("Example Inc.", "Drums")
+//!         .with_note(u7::new(36), "Kick")
+//!         .with_note(u7::new(38), "Snare")
+//!         .with_control(u7::new(7), "Volume");
+//!
+//!     let xml = names.to_xml();
+//!     assert!(xml.contains(r#"<Note Number="36" Name="Kick"/>"#));
+//!     assert!(xml.contains(r#"<Control Type="7bit" Number="7" Name="Volume"/>"#));
+use crate::prelude::*;
+use core::{ExtensionData, Feature, FeaturesList, Handle, Plugin};
+use std::ffi::{CStr, CString};
+use std::os::raw::*;
+
+pub const URI: &[u8] = b"http://ardour.org/lv2/midnam\0";
+pub const INTERFACE_URI: &[u8] = b"http://ardour.org/lv2/midnam#interface\0";
+pub const UPDATE_URI: &[u8] = b"http://ardour.org/lv2/midnam#update\0";
+
+/// A description of the names of notes and controllers, for all 16 channels.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MidiNames {
+    manufacturer: String,
+    model: String,
+    notes: Vec<(u7, String)>,
+    controls: Vec<(u7, String)>,
+}
+
+/// Escape the characters of the string that have a special meaning in XML.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+impl MidiNames {
+    /// Create a new document without names.
+    ///
+    /// The host identifies the document by its model, which therefore should be unique, for
+    /// example by including the address of the plugin instance.
+    pub fn new(manufacturer: &str, model: &str) -> Self {
+        Self {
+            manufacturer: manufacturer.to_owned(),
+            model: model.to_owned(),
+            notes: Vec::new(),
+            controls: Vec::new(),
+        }
+    }
+
+    /// Name the given note.
+    ///
+    /// If the note already has a name, it is replaced.
+    pub fn with_note(mut self, note: u7, name: &str) -> Self {
+        self.notes.retain(|(other, _)| *other != note);
+        self.notes.push((note, name.to_owned()));
+        self.notes.sort_by_key(|(note, _)| *note);
+        self
+    }
+
+    /// Name the given controller.
+    ///
+    /// If the controller already has a name, it is replaced.
+    pub fn with_control(mut self, control: u7, name: &str) -> Self {
+        self.controls.retain(|(other, _)| *other != control);
+        self.controls.push((control, name.to_owned()));
+        self.controls.sort_by_key(|(control, _)| *control);
+        self
+    }
+
+    /// Return the manufacturer of the model.
+    pub fn manufacturer(&self) -> &str {
+        &self.manufacturer
+    }
+
+    /// Return the model the document describes.
+    pub fn model(&self) -> &str {
+        &self.model
+    }
+
+    /// Return the name of the given note, if it has one.
+    pub fn note(&self, note: u7) -> Option<&str> {
+        self.notes
+            .iter()
+            .find(|(other, _)| *other == note)
+            .map(|(_, name)| name.as_str())
+    }
+
+    /// Return the name of the given controller, if it has one.
+    pub fn control(&self, control: u7) -> Option<&str> {
+        self.controls
+            .iter()
+            .find(|(other, _)| *other == control)
+            .map(|(_, name)| name.as_str())
+    }
+
+    /// Create the MIDNAM XML document.
+    ///
+    /// The names apply to all channels.
+    pub fn to_xml(&self) -> String {
+        let mut xml = String::from(concat!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+            "<!DOCTYPE MIDINameDocument PUBLIC \"-//MIDI Manufacturers Association//DTD MIDINameDocument 1.0//EN\" ",
+            "\"http://dev.midi.org/dtds/MIDINameDocument10.dtd\">\n",
+            "<MIDINameDocument>\n",
+            "  <Author/>\n",
+            "  <MasterDeviceNames>\n",
+        ));
+        xml.push_str(&format!(
+            "    <Manufacturer>{}</Manufacturer>\n    <Model>{}</Model>\n",
+            escape(&self.manufacturer),
+            escape(&self.model)
+        ));
+
+        xml.push_str("    <CustomDeviceMode Name=\"Default\">\n");
+        xml.push_str("      <ChannelNameSetAssignments>\n");
+        for channel in 1..=16 {
+            xml.push_str(&format!(
+                "        <ChannelNameSetAssign Channel=\"{}\" NameSet=\"Names\"/>\n",
+                channel
+            ));
+        }
+        xml.push_str("      </ChannelNameSetAssignments>\n");
+        xml.push_str("    </CustomDeviceMode>\n");
+
+        xml.push_str("    <ChannelNameSet Name=\"Names\">\n");
+        xml.push_str("      <AvailableForChannels>\n");
+        for channel in 1..=16 {
+            xml.push_str(&format!(
+                "        <AvailableChannel Channel=\"{}\" Available=\"true\"/>\n",
+                channel
+            ));
+        }
+        xml.push_str("      </AvailableForChannels>\n");
+        if !self.notes.is_empty() {
+            xml.push_str("      <UsesNoteNameList Name=\"Notes\"/>\n");
+        }
+        if !self.controls.is_empty() {
+            xml.push_str("      <UsesControlNameList Name=\"Controls\"/>\n");
+        }
+        xml.push_str("      <PatchBank Name=\"Default\">\n");
+        xml.push_str("        <PatchNameList Name=\"Default\">\n");
+        xml.push_str("          <Patch Number=\"0\" Name=\"Default\" ProgramChange=\"0\"/>\n");
+        xml.push_str("        </PatchNameList>\n");
+        xml.push_str("      </PatchBank>\n");
+        xml.push_str("    </ChannelNameSet>\n");
+
+        if !self.notes.is_empty() {
+            xml.push_str("    <NoteNameList Name=\"Notes\">\n");
+            for (note, name) in &self.notes {
+                xml.push_str(&format!(
+                    "      <Note Number=\"{}\" Name=\"{}\"/>\n",
+                    u8::from(*note),
+                    escape(name)
+                ));
+            }
+            xml.push_str("    </NoteNameList>\n");
+        }
+        if !self.controls.is_empty() {
+            xml.push_str("    <ControlNameList Name=\"Controls\">\n");
+            for (control, name) in &self.controls {
+                xml.push_str(&format!(
+                    "      <Control Type=\"7bit\" Number=\"{}\" Name=\"{}\"/>\n",
+                    u8::from(*control),
+                    escape(name)
+                ));
+            }
+            xml.push_str("    </ControlNameList>\n");
+        }
+
+        xml.push_str("  </MasterDeviceNames>\n");
+        xml.push_str("</MIDINameDocument>\n");
+        xml
+    }
+}
+
+/// A plugin that names its notes and controllers.
+pub trait Midnam: Plugin {
+    /// Return the current names of the plugin.
+    ///
+    /// This method is called by the host after the plugin was instantiated and every time the
+    /// plugin has sent an update notification.
+    fn midi_names(&mut self) -> MidiNames;
+}
+
+/// Raw representation of the midnam interface.
+///
+/// A plugin returns a static reference to this struct when the host asks for the
+/// [`INTERFACE_URI`](constant.INTERFACE_URI.html):
+///
+///     # extern crate lv2rs_core as core;
+///     # extern crate lv2rs_midi as midi;
+///     # use midi::midnam::*;
+///     # use std::ffi::CStr;
+///     # struct Drums;
+///     # impl core::Plugin for Drums {
+///     #     fn instantiate(
+///     #         _: &core::Descriptor,
+///     #         _: f64,
+///     #         _: &CStr,
+///     #         _: Option<&core::FeaturesList>,
+///     #     ) -> Option<Self> {
+///     #         None
+///     #     }
+///     #     fn connect_port(&mut self, _port: u32, _data: *mut ()) {}
+///     #     fn run(&mut self, _n_samples: u32) {}
+///     fn extension_data(uri: &CStr) -> Option<&'static dyn core::ExtensionData> {
+///         static MIDNAM: MidnamInterface = MidnamInterface::new::<Drums>();
+///         if uri.to_bytes_with_nul() == INTERFACE_URI {
+///             Some(&MIDNAM)
+///         } else {
+///             None
+///         }
+///     }
+///     # }
+///     # impl Midnam for Drums {
+///     #     fn midi_names(&mut self) -> MidiNames {
+///     #         MidiNames::new("Example Inc.", "Drums")
+///     #     }
+///     # }
+#[repr(C)]
+pub struct MidnamInterface {
+    /// Return the MIDNAM XML document of the plugin, which has to be freed with `free`.
+    pub midnam: unsafe extern "C" fn(instance: Handle) -> *mut c_char,
+    /// Return the model of the plugin's document, which has to be freed with `free`.
+    pub model: unsafe extern "C" fn(instance: Handle) -> *mut c_char,
+    /// Free a string returned by `midnam` or `model`.
+    pub free: unsafe extern "C" fn(string: *mut c_char),
+}
+
+impl MidnamInterface {
+    /// Create the interface for the given plugin type.
+    pub const fn new<P: Midnam>() -> Self {
+        Self {
+            midnam: midnam::<P>,
+            model: model::<P>,
+            free,
+        }
+    }
+}
+
+impl ExtensionData for MidnamInterface {}
+
+/// Turn the string into a C string that is owned by the caller.
+///
+/// Null bytes can't be represented and are removed.
+fn into_raw_string(string: String) -> *mut c_char {
+    let string = string.replace('\0', "");
+    CString::new(string).unwrap().into_raw()
+}
+
+unsafe extern "C" fn midnam<P: Midnam>(instance: Handle) -> *mut c_char {
+    let instance = (instance as *mut P).as_mut().unwrap();
+    into_raw_string(instance.midi_names().to_xml())
+}
+
+unsafe extern "C" fn model<P: Midnam>(instance: Handle) -> *mut c_char {
+    let instance = (instance as *mut P).as_mut().unwrap();
+    into_raw_string(instance.midi_names().model)
+}
+
+unsafe extern "C" fn free(string: *mut c_char) {
+    if !string.is_null() {
+        drop(CString::from_raw(string));
+    }
+}
+
+/// The `midnam:update` feature.
+///
+/// Hosts providing this feature re-read the names of the plugin when it sends a notification.
+#[repr(C)]
+pub struct MidnamNotify {
+    /// Opaque data of the host, which must be passed to `update`.
+    pub handle: *mut c_void,
+    /// Notify the host that the names have changed.
+    pub update: unsafe extern "C" fn(handle: *mut c_void),
+}
+
+impl MidnamNotify {
+    /// Try to find the update feature of the host in the features list.
+    ///
+    /// This function returns `None` if the host does not support update notifications.
+    pub fn try_from_features(features: &FeaturesList) -> Option<&'static mut Self> {
+        unsafe {
+            Feature::get_feature::<Self>(features, CStr::from_bytes_with_nul_unchecked(UPDATE_URI))
+        }
+    }
+
+    /// Notify the host that the names of the plugin have changed.
+    ///
+    /// The host will then request the new document via the
+    /// [`MidnamInterface`](struct.MidnamInterface.html).
+    pub fn update(&mut self) {
+        unsafe { (self.update)(self.handle) }
+    }
+}
@@ -0,0 +1,128 @@
+extern crate lv2rs_core as core;
+extern crate lv2rs_midi as midi;
+
+use midi::midnam::*;
+use midi::prelude::*;
+use std::ffi::CStr;
+use std::os::raw::*;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+/// A drum machine that switches between two kits.
+struct Drums {
+    electronic: bool,
+}
+
+impl core::Plugin for Drums {
+    fn instantiate(
+        _descriptor: &core::Descriptor,
+        _rate: f64,
+        _bundle_path: &CStr,
+        _features: Option<&core::FeaturesList>,
+    ) -> Option<Self> {
+        Some(Self { electronic: false })
+    }
+
+    fn connect_port(&mut self, _port: u32, _data: *mut ()) {}
+
+    fn run(&mut self, _n_samples: u32) {}
+}
+
+impl Midnam for Drums {
+    fn midi_names(&mut self) -> MidiNames {
+        let names = MidiNames::new("Example Inc.", "Drums & Percussion");
+        if self.electronic {
+            names
+                .with_note(u7::new(36), "Bass Drum")
+                .with_note(u7::new(38), "Clap")
+        } else {
+            names
+                .with_note(u7::new(38), "Snare")
+                .with_note(u7::new(36), "Kick")
+                .with_control(u7::new(7), "Volume")
+        }
+    }
+}
+
+#[test]
+fn test_midi_names() {
+    let names = MidiNames::new("Example Inc.", "Drums")
+        .with_note(u7::new(36), "Kick")
+        .with_note(u7::new(36), "Bass <Drum>");
+    assert_eq!("Example Inc.", names.manufacturer());
+    assert_eq!("Drums", names.model());
+    assert_eq!(Some("Bass <Drum>"), names.note(u7::new(36)));
+    assert_eq!(None, names.note(u7::new(38)));
+    assert_eq!(None, names.control(u7::new(36)));
+
+    let xml = names.to_xml();
+    assert!(xml.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n"));
+    assert!(xml.contains("<Model>Drums</Model>"));
+    assert!(xml.contains(r#"<Note Number="36" Name="Bass &lt;Drum&gt;"/>"#));
+    assert!(xml.contains(r#"<ChannelNameSetAssign Channel="16" NameSet="Names"/>"#));
+    assert!(xml.contains(r#"<UsesNoteNameList Name="Notes"/>"#));
+    assert!(!xml.contains("ControlNameList"));
+    assert!(xml.ends_with("</MIDINameDocument>\n"));
+}
+
+#[test]
+fn test_midnam_interface() {
+    let mut drums = Drums { electronic: false };
+    let handle = &mut drums as *mut Drums as core::Handle;
+    let interface = MidnamInterface::new::<Drums>();
+
+    unsafe {
+        let model = (interface.model)(handle);
+        assert_eq!(
+            "Drums & Percussion",
+            CStr::from_ptr(model).to_str().unwrap()
+        );
+        (interface.free)(model);
+
+        let xml = (interface.midnam)(handle);
+        let document = CStr::from_ptr(xml).to_str().unwrap().to_owned();
+        (interface.free)(xml);
+        assert!(document.contains("<Model>Drums &amp; Percussion</Model>"));
+        let kick = document.find(r#"<Note Number="36" Name="Kick"/>"#).unwrap();
+        let snare = document
+            .find(r#"<Note Number="38" Name="Snare"/>"#)
+            .unwrap();
+        assert!(kick < snare);
+        assert!(document.contains(r#"<Control Type="7bit" Number="7" Name="Volume"/>"#));
+    }
+
+    drums.electronic = true;
+    let handle = &mut drums as *mut Drums as core::Handle;
+    unsafe {
+        let xml = (interface.midnam)(handle);
+        let document = CStr::from_ptr(xml).to_str().unwrap().to_owned();
+        (interface.free)(xml);
+        assert!(document.contains(r#"<Note Number="38" Name="Clap"/>"#));
+        assert!(!document.contains("ControlNameList"));
+    }
+}
+
+static UPDATES: AtomicU32 = AtomicU32::new(0);
+
+unsafe extern "C" fn update(handle: *mut c_void) {
+    assert_eq!(0x1234 as *mut c_void, handle);
+    UPDATES.fetch_add(1, Ordering::SeqCst);
+}
+
+#[test]
+fn test_midnam_notify() {
+    let mut notify = MidnamNotify {
+        handle: 0x1234 as *mut c_void,
+        update,
+    };
+    let feature = core::Feature::new(
+        CStr::from_bytes_with_nul(UPDATE_URI).unwrap(),
+        &mut notify as *mut MidnamNotify as *mut c_void,
+    );
+    let features = [unsafe { &*(&feature as *const core::Feature) }];
+
+    let notify = MidnamNotify::try_from_features(&features).unwrap();
+    notify.update();
+    assert_eq!(1, UPDATES.load(Ordering::SeqCst));
+
+    assert!(MidnamNotify::try_from_features(&[]).is_none());
+}
@@ -0,0 +1,171 @@
+//! Writing frame extension for patch messages.
+use crate::message::*;
+use lv2rs_atom::frame::{NestedFrame, WritingFrame, WritingFrameExt};
+use lv2rs_atom::object::{Object, ObjectWritingFrame};
+use lv2rs_atom::prelude::*;
+use lv2rs_urid::{CachedMap, URID};
+
+/// Extension for [`WritingFrame`s](../lv2rs_atom/frame/trait.WritingFrame.html) that write
+/// objects, used to write patch messages.
+///
+/// Every method of this trait writes the properties of one message type. In order to do that, the
+/// frame has to manage an object with the type of this message, which is created by passing
+/// `(0, PatchType::X.into_urid(urids))` as the initialization parameter. If the object has another
+/// type, the methods return an `Err` without writing anything.
+///
+/// All properties are written using
+/// [`ObjectWritingFrame::push_property`](../lv2rs_atom/object/trait.ObjectWritingFrame.html#method.push_property)
+/// and every method should only be called once per object.
+pub trait PatchWritingFrame<'a>: WritingFrame<'a> + WritingFrameExt<'a, Object> {
+    /// Write the properties of a `patch:Set` message.
+    ///
+    /// The subject and the property are written immediately. The frame of the value is returned,
+    /// just like [`push_property`](../lv2rs_atom/object/trait.ObjectWritingFrame.html#method.push_property)
+    /// does, and can be used to extend the value if its type requires it.
+    fn write_set<'b, A: AtomBody + ?Sized>(
+        &'b mut self,
+        subject: Option<URID>,
+        property: URID,
+        value: &A::InitializationParameter,
+        urids: &mut CachedMap,
+    ) -> Result<NestedFrame<'b, 'a, A>, ()> {
+        check_patch_type(self, PatchType::Set, urids)?;
+        if let Some(subject) = subject {
+            push_urid(self, PatchProperty::Subject, subject, urids)?;
+        }
+        push_urid(self, PatchProperty::Property, property, urids)?;
+        let key = PatchProperty::Value.into_urid(urids);
+        self.push_property::<A>(key, 0, value, urids)
+    }
+
+    /// Write the properties of a `patch:Get` message.
+    fn write_get(
+        &mut self,
+        subject: Option<URID>,
+        property: Option<URID>,
+        urids: &mut CachedMap,
+    ) -> Result<(), ()> {
+        check_patch_type(self, PatchType::Get, urids)?;
+        if let Some(subject) = subject {
+            push_urid(self, PatchProperty::Subject, subject, urids)?;
+        }
+        if let Some(property) = property {
+            push_urid(self, PatchProperty::Property, property, urids)?;
+        }
+        Ok(())
+    }
+
+    /// Write the properties of a `patch:Put` message.
+    ///
+    /// The subject is written immediately and the frame of the body is returned. The body is a
+    /// blank object without a type and the new properties of the subject have to be pushed to it.
+    fn write_put<'b>(
+        &'b mut self,
+        subject: Option<URID>,
+        urids: &mut CachedMap,
+    ) -> Result<NestedFrame<'b, 'a, Object>, ()> {
+        check_patch_type(self, PatchType::Put, urids)?;
+        if let Some(subject) = subject {
+            push_urid(self, PatchProperty::Subject, subject, urids)?;
+        }
+        let key = PatchProperty::Body.into_urid(urids);
+        self.push_property::<Object>(key, 0, &(0, 0), urids)
+    }
+
+    /// Write the subject of a `patch:Patch` message.
+    ///
+    /// A patch also requires the `remove` and the `add` objects, which have to be written with
+    /// [`push_patch_remove`](#method.push_patch_remove) and
+    /// [`push_patch_add`](#method.push_patch_add) afterwards.
+    fn write_patch(&mut self, subject: URID, urids: &mut CachedMap) -> Result<(), ()> {
+        check_patch_type(self, PatchType::Patch, urids)?;
+        push_urid(self, PatchProperty::Subject, subject, urids)
+    }
+
+    /// Push the `remove` object of a `patch:Patch` message and return its frame.
+    fn push_patch_remove<'b>(
+        &'b mut self,
+        urids: &mut CachedMap,
+    ) -> Result<NestedFrame<'b, 'a, Object>, ()> {
+        check_patch_type(self, PatchType::Patch, urids)?;
+        let key = PatchProperty::Remove.into_urid(urids);
+        self.push_property::<Object>(key, 0, &(0, 0), urids)
+    }
+
+    /// Push the `add` object of a `patch:Patch` message and return its frame.
+    fn push_patch_add<'b>(
+        &'b mut self,
+        urids: &mut CachedMap,
+    ) -> Result<NestedFrame<'b, 'a, Object>, ()> {
+        check_patch_type(self, PatchType::Patch, urids)?;
+        let key = PatchProperty::Add.into_urid(urids);
+        self.push_property::<Object>(key, 0, &(0, 0), urids)
+    }
+
+    /// Write the properties of a `patch:Delete` message.
+    fn write_delete(&mut self, subject: URID, urids: &mut CachedMap) -> Result<(), ()> {
+        check_patch_type(self, PatchType::Delete, urids)?;
+        push_urid(self, PatchProperty::Subject, subject, urids)
+    }
+
+    /// Write the properties of a `patch:Copy` message.
+    fn write_copy(
+        &mut self,
+        subject: URID,
+        destination: URID,
+        urids: &mut CachedMap,
+    ) -> Result<(), ()> {
+        check_patch_type(self, PatchType::Copy, urids)?;
+        push_urid(self, PatchProperty::Subject, subject, urids)?;
+        push_urid(self, PatchProperty::Destination, destination, urids)
+    }
+
+    /// Write the properties of a `patch:Move` message.
+    fn write_move(
+        &mut self,
+        subject: URID,
+        destination: URID,
+        urids: &mut CachedMap,
+    ) -> Result<(), ()> {
+        check_patch_type(self, PatchType::Move, urids)?;
+        push_urid(self, PatchProperty::Subject, subject, urids)?;
+        push_urid(self, PatchProperty::Destination, destination, urids)
+    }
+}
+
+impl<'a, W> PatchWritingFrame<'a> for W where W: WritingFrame<'a> + WritingFrameExt<'a, Object> {}
+
+/// Check that the object managed by the frame has the type of the message.
+fn check_patch_type<'a, W>(
+    frame: &W,
+    patch_type: PatchType,
+    urids: &mut CachedMap,
+) -> Result<(), ()>
+where
+    W: WritingFrame<'a> + WritingFrameExt<'a, Object>,
+{
+    let otype = unsafe { frame.get_atom_body(urids) }
+        .map_err(|_| ())?
+        .header
+        .otype;
+    if otype == patch_type.into_urid(urids) {
+        Ok(())
+    } else {
+        Err(())
+    }
+}
+
+/// Push a property with a URID value.
+fn push_urid<'a, W>(
+    frame: &mut W,
+    property: PatchProperty,
+    value: URID,
+    urids: &mut CachedMap,
+) -> Result<(), ()>
+where
+    W: WritingFrame<'a> + WritingFrameExt<'a, Object>,
+{
+    let key = property.into_urid(urids);
+    frame.push_property::<URID>(key, 0, &value, urids)?;
+    Ok(())
+}
@@ -0,0 +1,116 @@
+//! A Rust adaptation of the [LV2 patch library](http://lv2plug.in/ns/ext/patch/patch.html).
+//!
+//! The patch library defines messages to read and manipulate properties of a resource, for example
+//! a plugin. These messages are objects, which are sent to and from a plugin via atom ports. For
+//! example, a host can set a sample file of a sampler plugin with a `patch:Set` message and
+//! request the current sample file with a `patch:Get` message.
+//!
+//! Messages are read from objects with [`PatchMessage::try_from`](enum.PatchMessage.html#method.try_from),
+//! which checks that all required properties of a message are present and well-typed. Writing is
+//! done with the [`PatchWritingFrame`](trait.PatchWritingFrame.html) extension, which is
+//! implemented by every writing frame that writes an object.
+//!
+//! Most plugins only use these messages to expose their parameters. The [`parameters`](parameters/index.html)
+//! module does exactly that: It answers `patch:Get` and applies `patch:Set` messages for a list
+//! of typed parameters.
+//!
+//! This is a frozen prototype and therefore, development of this crate will not continue here. Further
+//! development continues as [rust-lv2](https://github.com/rust-dsp/rust-lv2).
+//!
+//! ## Use
+//!
+//! A plugin answering `patch:Get` messages for its gain property:
+//!
+//!     extern crate lv2rs_atom as atom;
+//!     extern crate lv2rs_patch as patch;
+//!     extern crate lv2rs_urid as urid;
+//!
+//!     use atom::prelude::*;
+//!     use atom::ports::*;
+//!     use patch::{PatchMessage, PatchType, PatchWritingFrame};
+//!     use urid::{CachedMap, URID, debug::DebugMap};
+//!     use std::ffi::CStr;
+//!
+//!     pub struct Plugin {
+//!         in_port: AtomInputPort<Object>,
+//!         out_port: AtomOutputPort<Object>,
+//!         gain: f32,
+//!         gain_urid: URID,
+//!         urids: CachedMap,
+//!     }
+//!
+//!     impl Plugin {
+//!         /// Simulated `run` method.
+//!         fn run(&mut self) {
+//!             let object = unsafe { self.in_port.get_atom_body(&mut self.urids) }.unwrap();
+//!             match PatchMessage::try_from(object, &mut self.urids) {
+//!                 Ok(PatchMessage::Get { property: Some(property), .. })
+//!                     if property == self.gain_urid =>
+//!                 {
+//!                     let set_urid = PatchType::Set.into_urid(&mut self.urids);
+//!                     let mut frame = unsafe {
+//!                         self.out_port.write_atom_body(&(0, set_urid), &mut self.urids)
+//!                     }.unwrap();
+//!                     frame
+//!                         .write_set::<f32>(None, self.gain_urid, &self.gain, &mut self.urids)
+//!                         .unwrap();
+//!                 }
+//!                 _ => (),
+//!             }
+//!         }
+//!     }
+//!
+//!     // Getting a debug URID map.
+//!     let mut debug_map = DebugMap::new();
+//!     let mut urids = unsafe { debug_map.create_cached_map() };
+//!     let gain_urid = urids.map(CStr::from_bytes_with_nul(b"https://example.org#gain\0").unwrap());
+//!
+//!     // Creating the atom spaces and writing a `patch:Get`, just like a host would.
+//!     let mut in_space = vec![0u8; 256];
+//!     let in_atom = unsafe { (in_space.as_mut_ptr() as *mut Atom).as_mut() }.unwrap();
+//!     *(in_atom.mut_size()) = 256 - 8;
+//!     {
+//!         let get_urid = PatchType::Get.into_urid(&mut urids);
+//!         let mut host_port: AtomOutputPort<Object> = AtomOutputPort::new();
+//!         host_port.connect_port(in_atom);
+//!         let mut frame = unsafe { host_port.write_atom_body(&(0, get_urid), &mut urids) }.unwrap();
+//!         frame.write_get(None, Some(gain_urid), &mut urids).unwrap();
+//!     }
+//!     let mut out_space = vec![0u8; 256];
+//!     let out_atom = unsafe { (out_space.as_mut_ptr() as *mut Atom).as_mut() }.unwrap();
+//!     *(out_atom.mut_size()) = 256 - 8;
+//!
+//!     // Creating the plugin and connecting the ports.
+//!     let mut plugin = Plugin {
+//!         in_port: AtomInputPort::new(),
+//!         out_port: AtomOutputPort::new(),
+//!         gain: 0.5,
+//!         gain_urid: gain_urid,
+//!         urids: urids,
+//!     };
+//!     plugin.in_port.connect_port(in_atom as &Atom);
+//!     plugin.out_port.connect_port(out_atom);
+//!
+//!     // Calling `run`.
+//!     plugin.run();
+//!
+//!     // Reading the answer.
+//!     let object = out_atom.get_body::<Object>(&mut plugin.urids).unwrap();
+//!     match PatchMessage::try_from(object, &mut plugin.urids).unwrap() {
+//!         PatchMessage::Set { property, value, .. } => {
+//!             assert_eq!(gain_urid, property);
+//!             assert_eq!(0.5, *value.get_body::<f32>(&mut plugin.urids).unwrap());
+//!         }
+//!         _ => panic!("Wrong message type!"),
+//!     }
+extern crate lv2rs_atom;
+extern crate lv2rs_core;
+extern crate lv2rs_urid;
+
+mod frame;
+mod message;
+pub mod parameters;
+pub mod uris;
+
+pub use frame::*;
+pub use message::*;
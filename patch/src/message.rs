@@ -0,0 +1,282 @@
+//! Typed patch messages.
+use crate::uris;
+use lv2rs_atom::object::Object;
+use lv2rs_atom::prelude::*;
+use lv2rs_urid::{CachedMap, URID};
+use std::ffi::CStr;
+
+/// The type of a patch message.
+///
+/// The type of a message is expressed by the type of the object the message is written in. It is
+/// primarily used to create the object of a message, for example with
+/// `out_port.write_atom_body(&(0, PatchType::Set.into_urid(urids)), urids)`.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum PatchType {
+    Set,
+    Get,
+    Put,
+    Patch,
+    Delete,
+    Copy,
+    Move,
+}
+
+impl PatchType {
+    /// Return the URI of the message type.
+    pub fn get_uri(&self) -> &'static CStr {
+        let uri = match self {
+            PatchType::Set => uris::SET_URI,
+            PatchType::Get => uris::GET_URI,
+            PatchType::Put => uris::PUT_URI,
+            PatchType::Patch => uris::PATCH_URI,
+            PatchType::Delete => uris::DELETE_URI,
+            PatchType::Copy => uris::COPY_URI,
+            PatchType::Move => uris::MOVE_URI,
+        };
+        unsafe { CStr::from_bytes_with_nul_unchecked(uri) }
+    }
+
+    /// Try to get a `PatchType` value from a URID.
+    ///
+    /// `None` is returned if the URID isn't the URID of a known message type.
+    pub fn from_urid(urid: URID, urids: &mut CachedMap) -> Option<PatchType> {
+        [
+            PatchType::Set,
+            PatchType::Get,
+            PatchType::Put,
+            PatchType::Patch,
+            PatchType::Delete,
+            PatchType::Copy,
+            PatchType::Move,
+        ]
+        .iter()
+        .find(|patch_type| patch_type.into_urid(urids) == urid)
+        .cloned()
+    }
+
+    /// Return the corresponding URID of the message type.
+    pub fn into_urid(&self, urids: &mut CachedMap) -> URID {
+        urids.map(self.get_uri())
+    }
+}
+
+/// The properties of patch messages.
+///
+/// This enum is used to tell which property of a message is missing or malformed.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum PatchProperty {
+    /// `patch:subject`, a URID.
+    Subject,
+    /// `patch:property`, a URID.
+    Property,
+    /// `patch:value`, an atom of any type.
+    Value,
+    /// `patch:body`, an object.
+    Body,
+    /// `patch:remove`, an object.
+    Remove,
+    /// `patch:add`, an object.
+    Add,
+    /// `patch:destination`, a URID.
+    Destination,
+}
+
+impl PatchProperty {
+    /// Return the URI of the property.
+    pub fn get_uri(&self) -> &'static CStr {
+        let uri = match self {
+            PatchProperty::Subject => uris::SUBJECT_URI,
+            PatchProperty::Property => uris::PROPERTY_URI,
+            PatchProperty::Value => uris::VALUE_URI,
+            PatchProperty::Body => uris::BODY_URI,
+            PatchProperty::Remove => uris::REMOVE_URI,
+            PatchProperty::Add => uris::ADD_URI,
+            PatchProperty::Destination => uris::DESTINATION_URI,
+        };
+        unsafe { CStr::from_bytes_with_nul_unchecked(uri) }
+    }
+
+    /// Return the corresponding URID of the property.
+    pub fn into_urid(&self, urids: &mut CachedMap) -> URID {
+        urids.map(self.get_uri())
+    }
+}
+
+/// Errors that may occur when calling
+/// [`PatchMessage::try_from`](enum.PatchMessage.html#method.try_from).
+#[derive(Debug, PartialEq)]
+pub enum PatchError {
+    /// The atom is not an object.
+    NotAnObject,
+    /// The type of the object is not a known message type.
+    UnknownMessageType(URID),
+    /// The message type requires the contained property, but the object doesn't have it.
+    MissingProperty(PatchType, PatchProperty),
+    /// The object contains the property, but its body has the wrong type.
+    MalformedProperty(PatchProperty),
+}
+
+/// A patch message.
+///
+/// Patch messages are objects which are used to read and manipulate the properties of a
+/// plugin or any other resource. The most common ones are `Get`, which requests the value of a
+/// property, and `Set`, which sets the value of a property.
+///
+/// Messages are read from objects using the [`try_from`](#method.try_from) method. The values and
+/// bodies of the messages are not copied; they reference the original object. Writing messages is
+/// done with the [`PatchWritingFrame`](trait.PatchWritingFrame.html) trait.
+///
+/// Please consult the [LV2 patch specification](http://lv2plug.in/ns/ext/patch/patch.html) for
+/// the exact meaning of these messages.
+#[derive(Clone, Copy)]
+pub enum PatchMessage<'a> {
+    /// Set a property of the subject to a value.
+    Set {
+        subject: Option<URID>,
+        property: URID,
+        value: &'a Atom,
+    },
+    /// Request the value of a property or, if no property is given, a description of the subject.
+    Get {
+        subject: Option<URID>,
+        property: Option<URID>,
+    },
+    /// Replace the subject with the body.
+    Put {
+        subject: Option<URID>,
+        body: &'a Object,
+    },
+    /// Remove some properties of the subject and add some new.
+    Patch {
+        subject: URID,
+        remove: &'a Object,
+        add: &'a Object,
+    },
+    /// Delete the subject.
+    Delete { subject: URID },
+    /// Copy the subject to the destination.
+    Copy { subject: URID, destination: URID },
+    /// Move the subject to the destination.
+    Move { subject: URID, destination: URID },
+}
+
+impl<'a> PatchMessage<'a> {
+    /// Try to create a `PatchMessage` from an object.
+    ///
+    /// The type of the object decides which message is created. Then, this method checks whether
+    /// all required properties of this message are present and have the right types. If not, the
+    /// returned error tells exactly which property is missing or malformed. Additional properties
+    /// that the message doesn't use are ignored.
+    pub fn try_from(object: &'a Object, urids: &mut CachedMap) -> Result<Self, PatchError> {
+        let patch_type = PatchType::from_urid(object.header.otype, urids)
+            .ok_or(PatchError::UnknownMessageType(object.header.otype))?;
+
+        let subject_urid = PatchProperty::Subject.into_urid(urids);
+        let property_urid = PatchProperty::Property.into_urid(urids);
+        let value_urid = PatchProperty::Value.into_urid(urids);
+        let body_urid = PatchProperty::Body.into_urid(urids);
+        let remove_urid = PatchProperty::Remove.into_urid(urids);
+        let add_urid = PatchProperty::Add.into_urid(urids);
+        let destination_urid = PatchProperty::Destination.into_urid(urids);
+
+        let mut subject: Option<URID> = None;
+        let mut property: Option<URID> = None;
+        let mut value: Option<&'a Atom> = None;
+        let mut body: Option<&'a Object> = None;
+        let mut remove: Option<&'a Object> = None;
+        let mut add: Option<&'a Object> = None;
+        let mut destination: Option<URID> = None;
+
+        for (header, atom) in object.iter() {
+            if header.key == subject_urid {
+                subject = Some(read_urid(atom, PatchProperty::Subject, urids)?);
+            } else if header.key == property_urid {
+                property = Some(read_urid(atom, PatchProperty::Property, urids)?);
+            } else if header.key == value_urid {
+                value = Some(atom);
+            } else if header.key == body_urid {
+                body = Some(read_object(atom, PatchProperty::Body, urids)?);
+            } else if header.key == remove_urid {
+                remove = Some(read_object(atom, PatchProperty::Remove, urids)?);
+            } else if header.key == add_urid {
+                add = Some(read_object(atom, PatchProperty::Add, urids)?);
+            } else if header.key == destination_urid {
+                destination = Some(read_urid(atom, PatchProperty::Destination, urids)?);
+            }
+        }
+
+        let missing = |property: PatchProperty| PatchError::MissingProperty(patch_type, property);
+        let message = match patch_type {
+            PatchType::Set => PatchMessage::Set {
+                subject,
+                property: property.ok_or(missing(PatchProperty::Property))?,
+                value: value.ok_or(missing(PatchProperty::Value))?,
+            },
+            PatchType::Get => PatchMessage::Get { subject, property },
+            PatchType::Put => PatchMessage::Put {
+                subject,
+                body: body.ok_or(missing(PatchProperty::Body))?,
+            },
+            PatchType::Patch => PatchMessage::Patch {
+                subject: subject.ok_or(missing(PatchProperty::Subject))?,
+                remove: remove.ok_or(missing(PatchProperty::Remove))?,
+                add: add.ok_or(missing(PatchProperty::Add))?,
+            },
+            PatchType::Delete => PatchMessage::Delete {
+                subject: subject.ok_or(missing(PatchProperty::Subject))?,
+            },
+            PatchType::Copy => PatchMessage::Copy {
+                subject: subject.ok_or(missing(PatchProperty::Subject))?,
+                destination: destination.ok_or(missing(PatchProperty::Destination))?,
+            },
+            PatchType::Move => PatchMessage::Move {
+                subject: subject.ok_or(missing(PatchProperty::Subject))?,
+                destination: destination.ok_or(missing(PatchProperty::Destination))?,
+            },
+        };
+        Ok(message)
+    }
+
+    /// Try to create a `PatchMessage` from an atom that may be an object.
+    ///
+    /// This is a shortcut for events of a sequence: If the atom is an object, it is passed to
+    /// [`try_from`](#method.try_from). Otherwise, `PatchError::NotAnObject` is returned.
+    pub fn try_from_atom(atom: &'a Atom, urids: &mut CachedMap) -> Result<Self, PatchError> {
+        let object = atom
+            .get_body::<Object>(urids)
+            .map_err(|_| PatchError::NotAnObject)?;
+        Self::try_from(object, urids)
+    }
+
+    /// Return the type of the message.
+    pub fn patch_type(&self) -> PatchType {
+        match self {
+            PatchMessage::Set { .. } => PatchType::Set,
+            PatchMessage::Get { .. } => PatchType::Get,
+            PatchMessage::Put { .. } => PatchType::Put,
+            PatchMessage::Patch { .. } => PatchType::Patch,
+            PatchMessage::Delete { .. } => PatchType::Delete,
+            PatchMessage::Copy { .. } => PatchType::Copy,
+            PatchMessage::Move { .. } => PatchType::Move,
+        }
+    }
+}
+
+fn read_urid(
+    atom: &Atom,
+    property: PatchProperty,
+    urids: &mut CachedMap,
+) -> Result<URID, PatchError> {
+    atom.get_body::<URID>(urids)
+        .copied()
+        .map_err(|_| PatchError::MalformedProperty(property))
+}
+
+fn read_object<'a>(
+    atom: &'a Atom,
+    property: PatchProperty,
+    urids: &mut CachedMap,
+) -> Result<&'a Object, PatchError> {
+    atom.get_body::<Object>(urids)
+        .map_err(|_| PatchError::MalformedProperty(property))
+}
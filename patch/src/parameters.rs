@@ -0,0 +1,762 @@
+//! Typed plugin parameters, exposed via patch messages.
+//!
+//! Control ports can only carry floating-point numbers and are fixed once the plugin is described.
+//! Modern plugins therefore expose their other settings, like sample files or names, as
+//! parameters: Properties of the plugin that are read with `patch:Get` and written with
+//! `patch:Set` messages on an atom port.
+//!
+//! A plugin declares its parameters by adding [`Parameter`s](struct.Parameter.html) to a
+//! [`Parameters`](struct.Parameters.html) collection. In every `run` call, the collection
+//! handles the incoming messages with [`handle_input`](struct.Parameters.html#method.handle_input):
+//! `patch:Get` messages are answered and `patch:Set` messages are applied. Every changed value is
+//! then announced with a `patch:Set` message on the output sequence, which keeps user interfaces
+//! and other clients up to date.
+//!
+//! Parameters are also part of the plugin's state and description: The values of the writable
+//! parameters can be saved with [`store`](struct.Parameters.html#method.store) and restored
+//! with [`set_from_atom`](struct.Parameters.html#method.set_from_atom), presets are created and
+//! applied with [`store_preset`](struct.Parameters.html#method.store_preset) and
+//! [`load_preset`](struct.Parameters.html#method.load_preset), and
+//! [`to_turtle`](struct.Parameters.html#method.to_turtle) generates the Turtle description of all
+//! parameters.
+//!
+//! ## Use
+//!
+//!     extern crate lv2rs_atom as atom;
+//!     extern crate lv2rs_patch as patch;
+//!     extern crate lv2rs_urid as urid;
+//!
+//!     use atom::prelude::*;
+//!     use atom::ports::*;
+//!     use atom::sequence::{TimeStamp, TimeUnit};
+//!     use patch::parameters::*;
+//!     use patch::{PatchMessage, PatchType, PatchWritingFrame};
+//!     use urid::debug::DebugMap;
+//!     use std::ffi::CStr;
+//!
+//!     let mut debug_map = DebugMap::new();
+//!     let mut urids = unsafe { debug_map.create_cached_map() };
+//!
+//!     // Declaring the parameters.
+//!     let gain_uri = CStr::from_bytes_with_nul(b"https://example.org#gain\0").unwrap();
+//!     let mut parameters = Parameters::new();
+//!     let gain = parameters.add(Parameter::float(gain_uri, "Gain", 1.0, 0.0, 2.0), &mut urids);
+//!
+//!     // Creating the atom spaces and writing a `patch:Set`, just like a host would.
+//!     let mut in_space = vec![0u8; 256];
+//!     let in_atom = unsafe { (in_space.as_mut_ptr() as *mut Atom).as_mut() }.unwrap();
+//!     *(in_atom.mut_size()) = 256 - 8;
+//!     {
+//!         let mut host_port: AtomOutputPort<Sequence> = AtomOutputPort::new();
+//!         host_port.connect_port(in_atom);
+//!         let mut sequence = unsafe {
+//!             host_port.write_atom_body(&TimeUnit::Frames, &mut urids)
+//!         }.unwrap();
+//!         let set_urid = PatchType::Set.into_urid(&mut urids);
+//!         let mut object = sequence
+//!             .push_event::<Object>(TimeStamp::Frames(0), &(0, set_urid), &mut urids)
+//!             .unwrap();
+//!         object.write_set::<f32>(None, gain, &0.5, &mut urids).unwrap();
+//!     }
+//!     let mut out_space = vec![0u8; 256];
+//!     let out_atom = unsafe { (out_space.as_mut_ptr() as *mut Atom).as_mut() }.unwrap();
+//!     *(out_atom.mut_size()) = 256 - 8;
+//!
+//!     // Connecting the ports and handling the input, just like a plugin's `run` would.
+//!     let mut in_port: AtomInputPort<Sequence> = AtomInputPort::new();
+//!     in_port.connect_port(in_atom as &Atom);
+//!     let mut out_port: AtomOutputPort<Sequence> = AtomOutputPort::new();
+//!     out_port.connect_port(out_atom);
+//!     {
+//!         let input = unsafe { in_port.get_atom_body(&mut urids) }.unwrap();
+//!         let mut output = unsafe {
+//!             out_port.write_atom_body(&TimeUnit::Frames, &mut urids)
+//!         }.unwrap();
+//!         parameters.handle_input(input, &mut output, &mut urids).unwrap();
+//!     }
+//!     assert_eq!(Some(0.5), parameters.get_float(gain));
+//!
+//!     // The change was announced.
+//!     let sequence = out_atom.get_body::<Sequence>(&mut urids).unwrap();
+//!     let (_, event) = sequence.iter(&mut urids).next().unwrap();
+//!     match PatchMessage::try_from_atom(event, &mut urids).unwrap() {
+//!         PatchMessage::Set { property, .. } => assert_eq!(gain, property),
+//!         _ => panic!("Wrong message type!"),
+//!     }
+use crate::frame::PatchWritingFrame;
+use crate::message::*;
+use lv2rs_atom::frame::{WritingFrame, WritingFrameExt};
+use lv2rs_atom::object::{Object, ObjectWritingFrame};
+use lv2rs_atom::prelude::*;
+use lv2rs_atom::sequence::{TimeStamp, TimeUnit};
+use lv2rs_core::presets::{Preset, StateValue};
+use lv2rs_core::ttl;
+use lv2rs_urid::{CachedMap, URID};
+use std::ffi::{CStr, CString};
+use std::path::Path;
+
+/// The value of a parameter.
+///
+/// Strings and paths are stored as C strings, since they are sent as null-terminated atoms.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ParameterValue {
+    /// A number, sent as `atom:Float`.
+    Float(f32),
+    /// An integer, sent as `atom:Int`.
+    Int(i32),
+    /// A switch, sent as `atom:Bool`.
+    Bool(bool),
+    /// A file path, sent as `atom:Path`.
+    Path(CString),
+    /// A string, sent as `atom:String`.
+    String(CString),
+}
+
+impl ParameterValue {
+    /// Return `true` if both values have the same type.
+    pub fn same_type(&self, other: &ParameterValue) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+    }
+
+    /// Return the prefixed name of the atom type of the value.
+    fn range(&self) -> &'static str {
+        match self {
+            ParameterValue::Float(_) => "atom:Float",
+            ParameterValue::Int(_) => "atom:Int",
+            ParameterValue::Bool(_) => "atom:Bool",
+            ParameterValue::Path(_) => "atom:Path",
+            ParameterValue::String(_) => "atom:String",
+        }
+    }
+
+    /// Return the value as a Turtle term.
+    fn term(&self) -> String {
+        match self {
+            ParameterValue::Float(value) => ttl::float(f64::from(*value)),
+            ParameterValue::Int(value) => ttl::integer(i64::from(*value)),
+            ParameterValue::Bool(value) => ttl::boolean(*value),
+            ParameterValue::Path(value) | ParameterValue::String(value) => {
+                ttl::string(&value.to_string_lossy())
+            }
+        }
+    }
+}
+
+/// The declaration of a parameter.
+///
+/// A parameter is identified by its URI and has a human-readable label, a default value and,
+/// if it's a number, a range. Values outside of this range are clamped.
+///
+/// Parameters are writable by default, which means that they are settings of the plugin. If a
+/// parameter is only an output of the plugin, for example a measured level, it should be declared
+/// [`read_only`](#method.read_only).
+#[derive(Clone, Debug)]
+pub struct Parameter {
+    uri: &'static CStr,
+    label: String,
+    default: ParameterValue,
+    minimum: Option<ParameterValue>,
+    maximum: Option<ParameterValue>,
+    writable: bool,
+}
+
+impl Parameter {
+    fn new(
+        uri: &'static CStr,
+        label: &str,
+        default: ParameterValue,
+        minimum: Option<ParameterValue>,
+        maximum: Option<ParameterValue>,
+    ) -> Self {
+        Self {
+            uri,
+            label: label.to_owned(),
+            default,
+            minimum,
+            maximum,
+            writable: true,
+        }
+    }
+
+    /// Declare a floating-point parameter.
+    pub fn float(
+        uri: &'static CStr,
+        label: &str,
+        default: f32,
+        minimum: f32,
+        maximum: f32,
+    ) -> Self {
+        Self::new(
+            uri,
+            label,
+            ParameterValue::Float(default),
+            Some(ParameterValue::Float(minimum)),
+            Some(ParameterValue::Float(maximum)),
+        )
+    }
+
+    /// Declare an integer parameter.
+    pub fn int(uri: &'static CStr, label: &str, default: i32, minimum: i32, maximum: i32) -> Self {
+        Self::new(
+            uri,
+            label,
+            ParameterValue::Int(default),
+            Some(ParameterValue::Int(minimum)),
+            Some(ParameterValue::Int(maximum)),
+        )
+    }
+
+    /// Declare a boolean parameter.
+    pub fn bool(uri: &'static CStr, label: &str, default: bool) -> Self {
+        Self::new(uri, label, ParameterValue::Bool(default), None, None)
+    }
+
+    /// Declare a path parameter, which is empty by default.
+    pub fn path(uri: &'static CStr, label: &str) -> Self {
+        Self::new(
+            uri,
+            label,
+            ParameterValue::Path(CString::default()),
+            None,
+            None,
+        )
+    }
+
+    /// Declare a string parameter.
+    ///
+    /// Panics if the default value contains a null byte.
+    pub fn string(uri: &'static CStr, label: &str, default: &str) -> Self {
+        let default = CString::new(default).unwrap();
+        Self::new(uri, label, ParameterValue::String(default), None, None)
+    }
+
+    /// Declare the parameter as read-only.
+    ///
+    /// Read-only parameters can only be changed by the plugin; Incoming `patch:Set` messages for
+    /// them are ignored.
+    pub fn read_only(mut self) -> Self {
+        self.writable = false;
+        self
+    }
+
+    /// Return the URI of the parameter.
+    pub fn uri(&self) -> &'static CStr {
+        self.uri
+    }
+
+    /// Return the label of the parameter.
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// Return the default value of the parameter.
+    pub fn default(&self) -> &ParameterValue {
+        &self.default
+    }
+
+    /// Return `true` if the parameter can be changed by the host.
+    pub fn is_writable(&self) -> bool {
+        self.writable
+    }
+
+    /// Clamp a value into the range of the parameter.
+    fn clamp(&self, value: ParameterValue) -> ParameterValue {
+        match (value, &self.minimum, &self.maximum) {
+            (
+                ParameterValue::Float(value),
+                Some(ParameterValue::Float(minimum)),
+                Some(ParameterValue::Float(maximum)),
+            ) => ParameterValue::Float(value.max(*minimum).min(*maximum)),
+            (
+                ParameterValue::Int(value),
+                Some(ParameterValue::Int(minimum)),
+                Some(ParameterValue::Int(maximum)),
+            ) => ParameterValue::Int(value.max(*minimum).min(*maximum)),
+            (value, _, _) => value,
+        }
+    }
+}
+
+/// Errors that may occur when setting a parameter or handling messages.
+#[derive(Debug, PartialEq)]
+pub enum ParameterError {
+    /// There is no parameter with this URID.
+    UnknownParameter(URID),
+    /// The value has the wrong type for this parameter.
+    WrongType(URID),
+    /// The parameter is read-only and can not be set by the host.
+    ReadOnly(URID),
+    /// The output sequence is full.
+    InsufficientSpace,
+}
+
+/// A parameter with its URID and current value.
+struct Slot {
+    urid: URID,
+    parameter: Parameter,
+    value: ParameterValue,
+    changed: bool,
+}
+
+/// A collection of parameters.
+///
+/// See the [module documentation](index.html) for more information.
+pub struct Parameters {
+    slots: Vec<Slot>,
+}
+
+impl Default for Parameters {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Parameters {
+    /// Create a new, empty collection.
+    pub fn new() -> Self {
+        Self { slots: Vec::new() }
+    }
+
+    /// Add a parameter and return its URID.
+    ///
+    /// The parameter starts with its default value. If there already is a parameter with the same
+    /// URI, it is replaced.
+    pub fn add(&mut self, parameter: Parameter, urids: &mut CachedMap) -> URID {
+        let urid = urids.map(parameter.uri);
+        let slot = Slot {
+            urid,
+            value: parameter.default.clone(),
+            parameter,
+            changed: false,
+        };
+        match self.slots.iter_mut().find(|slot| slot.urid == urid) {
+            Some(old_slot) => *old_slot = slot,
+            None => self.slots.push(slot),
+        }
+        urid
+    }
+
+    /// Iterate over the URIDs, declarations and current values of all parameters.
+    pub fn iter(&self) -> impl Iterator<Item = (URID, &Parameter, &ParameterValue)> {
+        self.slots
+            .iter()
+            .map(|slot| (slot.urid, &slot.parameter, &slot.value))
+    }
+
+    /// Return the current value of a parameter.
+    pub fn get(&self, urid: URID) -> Option<&ParameterValue> {
+        self.slot(urid).map(|slot| &slot.value)
+    }
+
+    /// Return the current value of a float parameter.
+    pub fn get_float(&self, urid: URID) -> Option<f32> {
+        match self.get(urid) {
+            Some(ParameterValue::Float(value)) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Return the current value of an integer parameter.
+    pub fn get_int(&self, urid: URID) -> Option<i32> {
+        match self.get(urid) {
+            Some(ParameterValue::Int(value)) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Return the current value of a boolean parameter.
+    pub fn get_bool(&self, urid: URID) -> Option<bool> {
+        match self.get(urid) {
+            Some(ParameterValue::Bool(value)) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Return the current value of a path or string parameter.
+    pub fn get_str(&self, urid: URID) -> Option<&str> {
+        match self.get(urid) {
+            Some(ParameterValue::Path(value)) | Some(ParameterValue::String(value)) => {
+                value.to_str().ok()
+            }
+            _ => None,
+        }
+    }
+
+    /// Set the value of a parameter.
+    ///
+    /// The value is clamped into the range of the parameter and if it differs from the current
+    /// value, a notification is sent by the next call to [`notify`](#method.notify) or
+    /// [`handle_input`](#method.handle_input).
+    ///
+    /// This method is meant to be used by the plugin and therefore, it also sets read-only
+    /// parameters.
+    pub fn set(&mut self, urid: URID, value: ParameterValue) -> Result<(), ParameterError> {
+        let slot = self
+            .slots
+            .iter_mut()
+            .find(|slot| slot.urid == urid)
+            .ok_or(ParameterError::UnknownParameter(urid))?;
+        if !slot.value.same_type(&value) {
+            return Err(ParameterError::WrongType(urid));
+        }
+        let value = slot.parameter.clamp(value);
+        if slot.value != value {
+            slot.value = value;
+            slot.changed = true;
+        }
+        Ok(())
+    }
+
+    /// Set the value of a writable parameter from an atom.
+    ///
+    /// Numbers are converted into the type of the parameter, strings and paths have to have the
+    /// right type. This is the method used to apply `patch:Set` messages and it should also be
+    /// used to restore the values saved with [`store`](#method.store).
+    ///
+    /// Setting path and string parameters allocates memory.
+    pub fn set_from_atom(
+        &mut self,
+        urid: URID,
+        atom: &Atom,
+        urids: &mut CachedMap,
+    ) -> Result<(), ParameterError> {
+        let slot = self
+            .slot(urid)
+            .ok_or(ParameterError::UnknownParameter(urid))?;
+        if !slot.parameter.writable {
+            return Err(ParameterError::ReadOnly(urid));
+        }
+        let value = match slot.value {
+            ParameterValue::Float(_) => {
+                read_number(atom, urids).map(|n| ParameterValue::Float(n as f32))
+            }
+            ParameterValue::Int(_) => {
+                read_number(atom, urids).map(|n| ParameterValue::Int(n as i32))
+            }
+            ParameterValue::Bool(_) => match atom.get_body::<bool>(urids) {
+                Ok(value) => Some(ParameterValue::Bool(*value)),
+                Err(_) => read_number(atom, urids).map(|n| ParameterValue::Bool(n != 0.0)),
+            },
+            ParameterValue::Path(_) => atom
+                .get_body::<AtomPath>(urids)
+                .ok()
+                .and_then(|path| path.as_cstr().ok())
+                .map(|path| ParameterValue::Path(path.to_owned())),
+            ParameterValue::String(_) => atom
+                .get_body::<AtomString>(urids)
+                .ok()
+                .and_then(|string| string.as_cstr().ok())
+                .map(|string| ParameterValue::String(string.to_owned())),
+        }
+        .ok_or(ParameterError::WrongType(urid))?;
+        self.set(urid, value)
+    }
+
+    /// Pass the values of all writable parameters to a storing function.
+    ///
+    /// Read-only parameters are outputs of the plugin and therefore not part of its state.
+    pub fn store<F: FnMut(URID, &ParameterValue)>(&self, mut store: F) {
+        for slot in self.slots.iter().filter(|slot| slot.parameter.writable) {
+            store(slot.urid, &slot.value);
+        }
+    }
+
+    /// Add the values of all writable parameters to a preset.
+    ///
+    /// The values are added as state properties, identified by the URIs of the parameters.
+    pub fn store_preset(&self, preset: &mut Preset) {
+        for slot in self.slots.iter().filter(|slot| slot.parameter.writable) {
+            let value = match &slot.value {
+                ParameterValue::Float(value) => StateValue::Float(*value),
+                ParameterValue::Int(value) => StateValue::Int(*value),
+                ParameterValue::Bool(value) => StateValue::Bool(*value),
+                ParameterValue::Path(value) => StateValue::Path(value.to_string_lossy().into()),
+                ParameterValue::String(value) => {
+                    StateValue::String(value.to_string_lossy().into())
+                }
+            };
+            preset.set_state_value(&slot.parameter.uri.to_string_lossy(), value);
+        }
+    }
+
+    /// Apply the state properties of a preset to the writable parameters.
+    ///
+    /// Numbers are converted into the type of the parameter, just like in
+    /// [`set_from_atom`](#method.set_from_atom). State properties that aren't writable parameters
+    /// are ignored, since they belong to the rest of the plugin's state.
+    pub fn load_preset(&mut self, preset: &Preset) -> Result<(), ParameterError> {
+        for (property, value) in preset.state_values() {
+            let slot = match self.slots.iter().find(|slot| {
+                slot.parameter.writable && slot.parameter.uri.to_bytes() == property.as_bytes()
+            }) {
+                Some(slot) => slot,
+                None => continue,
+            };
+            let number = match value {
+                StateValue::Float(value) => Some(f64::from(*value)),
+                StateValue::Int(value) => Some(f64::from(*value)),
+                _ => None,
+            };
+            let value = match (&slot.value, value) {
+                (ParameterValue::Float(_), _) => number.map(|n| ParameterValue::Float(n as f32)),
+                (ParameterValue::Int(_), _) => number.map(|n| ParameterValue::Int(n as i32)),
+                (ParameterValue::Bool(_), StateValue::Bool(value)) => {
+                    Some(ParameterValue::Bool(*value))
+                }
+                (ParameterValue::Bool(_), _) => number.map(|n| ParameterValue::Bool(n != 0.0)),
+                (ParameterValue::Path(_), StateValue::Path(value)) => {
+                    CString::new(value.as_str()).ok().map(ParameterValue::Path)
+                }
+                (ParameterValue::String(_), StateValue::String(value)) => {
+                    CString::new(value.as_str()).ok().map(ParameterValue::String)
+                }
+                _ => None,
+            }
+            .ok_or(ParameterError::WrongType(slot.urid))?;
+            let urid = slot.urid;
+            self.set(urid, value)?;
+        }
+        Ok(())
+    }
+
+    /// Handle all patch messages in the input sequence.
+    ///
+    /// `patch:Get` messages are answered with a `patch:Set` message for the requested parameter
+    /// or, if no property was requested, with a `patch:Put` message containing all parameters.
+    /// `patch:Set` messages for writable parameters are applied. Messages that are malformed or
+    /// target unknown parameters are ignored, as well as all events that aren't patch messages.
+    /// Afterwards, all changed values are announced with [`notify`](#method.notify).
+    ///
+    /// The only error returned by this method is `ParameterError::InsufficientSpace`.
+    pub fn handle_input<'a, W>(
+        &mut self,
+        input: &Sequence,
+        output: &mut W,
+        urids: &mut CachedMap,
+    ) -> Result<(), ParameterError>
+    where
+        W: WritingFrame<'a> + WritingFrameExt<'a, Sequence>,
+    {
+        let output_unit = output_unit(output, urids);
+        for (time, atom) in input.iter(urids) {
+            let message = match PatchMessage::try_from_atom(atom, urids) {
+                Ok(message) => message,
+                Err(_) => continue,
+            };
+            let time = if time.get_unit() == output_unit {
+                time
+            } else {
+                zero_stamp(&output_unit)
+            };
+            match message {
+                PatchMessage::Get {
+                    property: Some(property),
+                    ..
+                } => {
+                    if let Some(slot) = self.slot(property) {
+                        write_set(output, time, slot.urid, &slot.value, urids)?;
+                    }
+                }
+                PatchMessage::Get { property: None, .. } => self.write_put(output, time, urids)?,
+                PatchMessage::Set {
+                    property, value, ..
+                } => {
+                    let _ = self.set_from_atom(property, value, urids);
+                }
+                _ => (),
+            }
+        }
+        self.notify(output, urids)
+    }
+
+    /// Announce all changed values with `patch:Set` messages.
+    ///
+    /// Events of a sequence have to be in chronological order and therefore, the messages have the
+    /// time stamp of the last event in the output sequence, or zero if it is empty. If the output
+    /// sequence is full, the remaining changes are kept and announced by the next call.
+    pub fn notify<'a, W>(
+        &mut self,
+        output: &mut W,
+        urids: &mut CachedMap,
+    ) -> Result<(), ParameterError>
+    where
+        W: WritingFrame<'a> + WritingFrameExt<'a, Sequence>,
+    {
+        let time = last_stamp(output, urids);
+        for slot in self.slots.iter_mut().filter(|slot| slot.changed) {
+            write_set(output, time.clone(), slot.urid, &slot.value, urids)?;
+            slot.changed = false;
+        }
+        Ok(())
+    }
+
+    /// Generate the Turtle description of all parameters.
+    ///
+    /// Every parameter is described as a `lv2:Parameter` with its label, type, default value and
+    /// range. Then, the plugin is described to have these parameters as `patch:writable` or
+    /// `patch:readable` properties. The generated statements use the prefixes of
+    /// [`lv2rs_core::ttl::PREFIXES`](../../lv2rs_core/ttl/constant.PREFIXES.html).
+    pub fn to_turtle(&self, plugin_uri: &str) -> String {
+        let mut document = String::new();
+        let mut plugin = ttl::Node::new(ttl::uri(plugin_uri));
+        for slot in self.slots.iter() {
+            let parameter = &slot.parameter;
+            let uri = ttl::uri(&parameter.uri.to_string_lossy());
+
+            let mut node = ttl::Node::new(uri.as_str());
+            node.add("a", "lv2:Parameter")
+                .add("rdfs:label", ttl::string(&parameter.label))
+                .add("rdfs:range", parameter.default.range())
+                .add("lv2:default", parameter.default.term());
+            if let Some(minimum) = &parameter.minimum {
+                node.add("lv2:minimum", minimum.term());
+            }
+            if let Some(maximum) = &parameter.maximum {
+                node.add("lv2:maximum", maximum.term());
+            }
+            document.push_str(&node.to_string());
+            document.push('\n');
+
+            if parameter.writable {
+                plugin.add("patch:writable", uri);
+            } else {
+                plugin.add("patch:readable", uri);
+            }
+        }
+        if !plugin.is_empty() {
+            document.push_str(&plugin.to_string());
+        }
+        document
+    }
+
+    fn slot(&self, urid: URID) -> Option<&Slot> {
+        self.slots.iter().find(|slot| slot.urid == urid)
+    }
+
+    /// Write a `patch:Put` message with all parameters.
+    fn write_put<'a, W>(
+        &self,
+        output: &mut W,
+        time: TimeStamp,
+        urids: &mut CachedMap,
+    ) -> Result<(), ParameterError>
+    where
+        W: WritingFrame<'a> + WritingFrameExt<'a, Sequence>,
+    {
+        let put_urid = PatchType::Put.into_urid(urids);
+        let mut object = output
+            .push_event::<Object>(time, &(0, put_urid), urids)
+            .map_err(|_| ParameterError::InsufficientSpace)?;
+        let mut body = object
+            .write_put(None, urids)
+            .map_err(|_| ParameterError::InsufficientSpace)?;
+        for slot in self.slots.iter() {
+            push_value(&mut body, slot.urid, &slot.value, urids)
+                .map_err(|_| ParameterError::InsufficientSpace)?;
+        }
+        Ok(())
+    }
+}
+
+/// Return the time unit of the output sequence.
+fn output_unit<'a, W>(output: &W, urids: &mut CachedMap) -> TimeUnit
+where
+    W: WritingFrame<'a> + WritingFrameExt<'a, Sequence>,
+{
+    match unsafe { output.get_atom_body(urids) } {
+        Ok(sequence) => sequence.time_unit(urids),
+        Err(_) => TimeUnit::Frames,
+    }
+}
+
+fn zero_stamp(unit: &TimeUnit) -> TimeStamp {
+    match unit {
+        TimeUnit::Frames => TimeStamp::Frames(0),
+        TimeUnit::Beats => TimeStamp::Beats(0.0),
+    }
+}
+
+/// Return the time stamp of the last event in the output sequence, or zero if it's empty.
+fn last_stamp<'a, W>(output: &W, urids: &mut CachedMap) -> TimeStamp
+where
+    W: WritingFrame<'a> + WritingFrameExt<'a, Sequence>,
+{
+    let last = match unsafe { output.get_atom_body(urids) } {
+        Ok(sequence) => sequence.iter(urids).last().map(|(time, _)| time),
+        Err(_) => None,
+    };
+    last.unwrap_or_else(|| zero_stamp(&output_unit(output, urids)))
+}
+
+/// Write a `patch:Set` message for a parameter.
+fn write_set<'a, W>(
+    output: &mut W,
+    time: TimeStamp,
+    urid: URID,
+    value: &ParameterValue,
+    urids: &mut CachedMap,
+) -> Result<(), ParameterError>
+where
+    W: WritingFrame<'a> + WritingFrameExt<'a, Sequence>,
+{
+    let set_urid = PatchType::Set.into_urid(urids);
+    let mut object = output
+        .push_event::<Object>(time, &(0, set_urid), urids)
+        .map_err(|_| ParameterError::InsufficientSpace)?;
+    let written = match value {
+        ParameterValue::Float(value) => object.write_set::<f32>(None, urid, value, urids).map(drop),
+        ParameterValue::Int(value) => object.write_set::<i32>(None, urid, value, urids).map(drop),
+        ParameterValue::Bool(value) => object.write_set::<bool>(None, urid, value, urids).map(drop),
+        ParameterValue::Path(value) => {
+            let path = value.to_str().map_err(|_| ParameterError::WrongType(urid))?;
+            object
+                .write_set::<AtomPath>(None, urid, Path::new(path), urids)
+                .map(drop)
+        }
+        ParameterValue::String(value) => object
+            .write_set::<AtomString>(None, urid, value, urids)
+            .map(drop),
+    };
+    written.map_err(|_| ParameterError::InsufficientSpace)
+}
+
+/// Push a parameter value as a property of an object.
+fn push_value<'a, W>(
+    frame: &mut W,
+    key: URID,
+    value: &ParameterValue,
+    urids: &mut CachedMap,
+) -> Result<(), ()>
+where
+    W: WritingFrame<'a> + WritingFrameExt<'a, Object>,
+{
+    match value {
+        ParameterValue::Float(value) => frame.push_property::<f32>(key, 0, value, urids).map(drop),
+        ParameterValue::Int(value) => frame.push_property::<i32>(key, 0, value, urids).map(drop),
+        ParameterValue::Bool(value) => frame.push_property::<bool>(key, 0, value, urids).map(drop),
+        ParameterValue::Path(value) => {
+            let path = value.to_str().map_err(|_| ())?;
+            frame
+                .push_property::<AtomPath>(key, 0, Path::new(path), urids)
+                .map(drop)
+        }
+        ParameterValue::String(value) => frame
+            .push_property::<AtomString>(key, 0, value, urids)
+            .map(drop),
+    }
+}
+
+/// Read any numeric atom as a `f64`.
+fn read_number(atom: &Atom, urids: &mut CachedMap) -> Option<f64> {
+    if let Ok(value) = atom.get_body::<f32>(urids) {
+        Some(f64::from(*value))
+    } else if let Ok(value) = atom.get_body::<f64>(urids) {
+        Some(*value)
+    } else if let Ok(value) = atom.get_body::<i32>(urids) {
+        Some(f64::from(*value))
+    } else if let Ok(value) = atom.get_body::<i64>(urids) {
+        Some(*value as f64)
+    } else {
+        None
+    }
+}
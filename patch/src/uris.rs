@@ -0,0 +1,29 @@
+//! URIs of the LV2 patch library.
+pub const URI: &[u8] = b"http://lv2plug.in/ns/ext/patch\0";
+pub const ACK_URI: &[u8] = b"http://lv2plug.in/ns/ext/patch#Ack\0";
+pub const DELETE_URI: &[u8] = b"http://lv2plug.in/ns/ext/patch#Delete\0";
+pub const COPY_URI: &[u8] = b"http://lv2plug.in/ns/ext/patch#Copy\0";
+pub const ERROR_URI: &[u8] = b"http://lv2plug.in/ns/ext/patch#Error\0";
+pub const GET_URI: &[u8] = b"http://lv2plug.in/ns/ext/patch#Get\0";
+pub const MESSAGE_URI: &[u8] = b"http://lv2plug.in/ns/ext/patch#Message\0";
+pub const MOVE_URI: &[u8] = b"http://lv2plug.in/ns/ext/patch#Move\0";
+pub const PATCH_URI: &[u8] = b"http://lv2plug.in/ns/ext/patch#Patch\0";
+pub const POST_URI: &[u8] = b"http://lv2plug.in/ns/ext/patch#Post\0";
+pub const PUT_URI: &[u8] = b"http://lv2plug.in/ns/ext/patch#Put\0";
+pub const REQUEST_URI: &[u8] = b"http://lv2plug.in/ns/ext/patch#Request\0";
+pub const RESPONSE_URI: &[u8] = b"http://lv2plug.in/ns/ext/patch#Response\0";
+pub const SET_URI: &[u8] = b"http://lv2plug.in/ns/ext/patch#Set\0";
+pub const ACCEPT_URI: &[u8] = b"http://lv2plug.in/ns/ext/patch#accept\0";
+pub const ADD_URI: &[u8] = b"http://lv2plug.in/ns/ext/patch#add\0";
+pub const BODY_URI: &[u8] = b"http://lv2plug.in/ns/ext/patch#body\0";
+pub const CONTEXT_URI: &[u8] = b"http://lv2plug.in/ns/ext/patch#context\0";
+pub const DESTINATION_URI: &[u8] = b"http://lv2plug.in/ns/ext/patch#destination\0";
+pub const PROPERTY_URI: &[u8] = b"http://lv2plug.in/ns/ext/patch#property\0";
+pub const READABLE_URI: &[u8] = b"http://lv2plug.in/ns/ext/patch#readable\0";
+pub const REMOVE_URI: &[u8] = b"http://lv2plug.in/ns/ext/patch#remove\0";
+pub const REQUEST_PROPERTY_URI: &[u8] = b"http://lv2plug.in/ns/ext/patch#request\0";
+pub const SUBJECT_URI: &[u8] = b"http://lv2plug.in/ns/ext/patch#subject\0";
+pub const SEQUENCE_NUMBER_URI: &[u8] = b"http://lv2plug.in/ns/ext/patch#sequenceNumber\0";
+pub const VALUE_URI: &[u8] = b"http://lv2plug.in/ns/ext/patch#value\0";
+pub const WILDCARD_URI: &[u8] = b"http://lv2plug.in/ns/ext/patch#wildcard\0";
+pub const WRITABLE_URI: &[u8] = b"http://lv2plug.in/ns/ext/patch#writable\0";
@@ -0,0 +1,234 @@
+extern crate lv2rs_atom as atom;
+extern crate lv2rs_patch as patch;
+extern crate lv2rs_urid as urid;
+
+use atom::ports::*;
+use atom::prelude::*;
+use patch::*;
+use std::ffi::CStr;
+use urid::debug::DebugMap;
+use urid::URID;
+
+#[test]
+fn test_messages() {
+    let mut debug_map = DebugMap::new();
+    let mut urids = unsafe { debug_map.create_cached_map() };
+
+    let subject_urid =
+        urids.map(CStr::from_bytes_with_nul(b"https://example.org#subject\0").unwrap());
+    let gain_urid = urids.map(CStr::from_bytes_with_nul(b"https://example.org#gain\0").unwrap());
+    let other_urid = urids.map(CStr::from_bytes_with_nul(b"https://example.org#other\0").unwrap());
+
+    // Creating the atom space.
+    let mut atom_space = vec![0u8; 256];
+    let atom = unsafe { (atom_space.as_mut_ptr() as *mut Atom).as_mut() }.unwrap();
+    *(atom.mut_size()) = 256 - 8;
+
+    // Creating the ports and connecting them.
+    let mut out_port: AtomOutputPort<Object> = AtomOutputPort::new();
+    out_port.connect_port(atom);
+    let mut in_port: AtomInputPort<Object> = AtomInputPort::new();
+    in_port.connect_port(atom as &Atom);
+
+    // patch:Set
+    {
+        let set_urid = PatchType::Set.into_urid(&mut urids);
+        let mut frame = unsafe { out_port.write_atom_body(&(0, set_urid), &mut urids) }.unwrap();
+        frame
+            .write_set::<f32>(Some(subject_urid), gain_urid, &0.5, &mut urids)
+            .unwrap();
+    }
+    let object = unsafe { in_port.get_atom_body(&mut urids) }.unwrap();
+    match PatchMessage::try_from(object, &mut urids).unwrap() {
+        PatchMessage::Set {
+            subject,
+            property,
+            value,
+        } => {
+            assert_eq!(Some(subject_urid), subject);
+            assert_eq!(gain_urid, property);
+            assert_eq!(0.5, *value.get_body::<f32>(&mut urids).unwrap());
+        }
+        _ => panic!("Wrong message type!"),
+    }
+
+    // patch:Get without a subject.
+    *(atom.mut_size()) = 256 - 8;
+    {
+        let get_urid = PatchType::Get.into_urid(&mut urids);
+        let mut frame = unsafe { out_port.write_atom_body(&(0, get_urid), &mut urids) }.unwrap();
+        frame.write_get(None, Some(gain_urid), &mut urids).unwrap();
+        // A frame of the wrong message type refuses to write.
+        assert!(frame.write_delete(subject_urid, &mut urids).is_err());
+    }
+    let object = unsafe { in_port.get_atom_body(&mut urids) }.unwrap();
+    match PatchMessage::try_from(object, &mut urids).unwrap() {
+        PatchMessage::Get { subject, property } => {
+            assert_eq!(None, subject);
+            assert_eq!(Some(gain_urid), property);
+        }
+        _ => panic!("Wrong message type!"),
+    }
+
+    // patch:Put
+    *(atom.mut_size()) = 256 - 8;
+    {
+        let put_urid = PatchType::Put.into_urid(&mut urids);
+        let mut frame = unsafe { out_port.write_atom_body(&(0, put_urid), &mut urids) }.unwrap();
+        let mut body = frame.write_put(Some(subject_urid), &mut urids).unwrap();
+        body.push_property::<f32>(gain_urid, 0, &0.25, &mut urids)
+            .unwrap();
+    }
+    let object = unsafe { in_port.get_atom_body(&mut urids) }.unwrap();
+    match PatchMessage::try_from(object, &mut urids).unwrap() {
+        PatchMessage::Put { subject, body } => {
+            assert_eq!(Some(subject_urid), subject);
+            let (header, value) = body.iter().next().unwrap();
+            assert_eq!(gain_urid, header.key);
+            assert_eq!(0.25, *value.get_body::<f32>(&mut urids).unwrap());
+        }
+        _ => panic!("Wrong message type!"),
+    }
+
+    // patch:Patch
+    *(atom.mut_size()) = 256 - 8;
+    {
+        let patch_urid = PatchType::Patch.into_urid(&mut urids);
+        let mut frame = unsafe { out_port.write_atom_body(&(0, patch_urid), &mut urids) }.unwrap();
+        frame.write_patch(subject_urid, &mut urids).unwrap();
+        frame
+            .push_patch_remove(&mut urids)
+            .unwrap()
+            .push_property::<f32>(other_urid, 0, &0.0, &mut urids)
+            .unwrap();
+        frame
+            .push_patch_add(&mut urids)
+            .unwrap()
+            .push_property::<f32>(gain_urid, 0, &1.0, &mut urids)
+            .unwrap();
+    }
+    let object = unsafe { in_port.get_atom_body(&mut urids) }.unwrap();
+    match PatchMessage::try_from(object, &mut urids).unwrap() {
+        PatchMessage::Patch {
+            subject,
+            remove,
+            add,
+        } => {
+            assert_eq!(subject_urid, subject);
+            assert_eq!(other_urid, remove.iter().next().unwrap().0.key);
+            assert_eq!(gain_urid, add.iter().next().unwrap().0.key);
+        }
+        _ => panic!("Wrong message type!"),
+    }
+
+    // patch:Delete, patch:Copy and patch:Move
+    *(atom.mut_size()) = 256 - 8;
+    {
+        let delete_urid = PatchType::Delete.into_urid(&mut urids);
+        let mut frame = unsafe { out_port.write_atom_body(&(0, delete_urid), &mut urids) }.unwrap();
+        frame.write_delete(subject_urid, &mut urids).unwrap();
+    }
+    let object = unsafe { in_port.get_atom_body(&mut urids) }.unwrap();
+    match PatchMessage::try_from(object, &mut urids).unwrap() {
+        PatchMessage::Delete { subject } => assert_eq!(subject_urid, subject),
+        _ => panic!("Wrong message type!"),
+    }
+
+    *(atom.mut_size()) = 256 - 8;
+    {
+        let copy_urid = PatchType::Copy.into_urid(&mut urids);
+        let mut frame = unsafe { out_port.write_atom_body(&(0, copy_urid), &mut urids) }.unwrap();
+        frame
+            .write_copy(subject_urid, other_urid, &mut urids)
+            .unwrap();
+    }
+    let object = unsafe { in_port.get_atom_body(&mut urids) }.unwrap();
+    match PatchMessage::try_from(object, &mut urids).unwrap() {
+        PatchMessage::Copy {
+            subject,
+            destination,
+        } => {
+            assert_eq!(subject_urid, subject);
+            assert_eq!(other_urid, destination);
+        }
+        _ => panic!("Wrong message type!"),
+    }
+
+    *(atom.mut_size()) = 256 - 8;
+    {
+        let move_urid = PatchType::Move.into_urid(&mut urids);
+        let mut frame = unsafe { out_port.write_atom_body(&(0, move_urid), &mut urids) }.unwrap();
+        frame
+            .write_move(subject_urid, other_urid, &mut urids)
+            .unwrap();
+    }
+    let object = unsafe { in_port.get_atom_body(&mut urids) }.unwrap();
+    assert_eq!(
+        PatchType::Move,
+        PatchMessage::try_from(object, &mut urids)
+            .unwrap()
+            .patch_type()
+    );
+}
+
+#[test]
+fn test_errors() {
+    let mut debug_map = DebugMap::new();
+    let mut urids = unsafe { debug_map.create_cached_map() };
+
+    let gain_urid = urids.map(CStr::from_bytes_with_nul(b"https://example.org#gain\0").unwrap());
+
+    // Creating the atom space.
+    let mut atom_space = vec![0u8; 256];
+    let atom = unsafe { (atom_space.as_mut_ptr() as *mut Atom).as_mut() }.unwrap();
+    *(atom.mut_size()) = 256 - 8;
+
+    // Creating the ports and connecting them.
+    let mut out_port: AtomOutputPort<Object> = AtomOutputPort::new();
+    out_port.connect_port(atom);
+    let mut in_port: AtomInputPort<Object> = AtomInputPort::new();
+    in_port.connect_port(atom as &Atom);
+
+    // A patch:Set without a value.
+    {
+        let set_urid = PatchType::Set.into_urid(&mut urids);
+        let property_urid = PatchProperty::Property.into_urid(&mut urids);
+        let mut frame = unsafe { out_port.write_atom_body(&(0, set_urid), &mut urids) }.unwrap();
+        frame
+            .push_property::<URID>(property_urid, 0, &gain_urid, &mut urids)
+            .unwrap();
+    }
+    let object = unsafe { in_port.get_atom_body(&mut urids) }.unwrap();
+    match PatchMessage::try_from(object, &mut urids) {
+        Err(err) => assert_eq!(
+            PatchError::MissingProperty(PatchType::Set, PatchProperty::Value),
+            err
+        ),
+        Ok(_) => panic!("Incomplete message was accepted!"),
+    }
+
+    // A patch:Get with a property that isn't a URID.
+    *(atom.mut_size()) = 256 - 8;
+    {
+        let get_urid = PatchType::Get.into_urid(&mut urids);
+        let property_urid = PatchProperty::Property.into_urid(&mut urids);
+        let mut frame = unsafe { out_port.write_atom_body(&(0, get_urid), &mut urids) }.unwrap();
+        frame
+            .push_property::<f32>(property_urid, 0, &1.0, &mut urids)
+            .unwrap();
+    }
+    let object = unsafe { in_port.get_atom_body(&mut urids) }.unwrap();
+    match PatchMessage::try_from(object, &mut urids) {
+        Err(err) => assert_eq!(PatchError::MalformedProperty(PatchProperty::Property), err),
+        Ok(_) => panic!("Malformed message was accepted!"),
+    }
+
+    // An object that isn't a message.
+    *(atom.mut_size()) = 256 - 8;
+    unsafe { out_port.write_atom_body(&(0, gain_urid), &mut urids) }.unwrap();
+    let object = unsafe { in_port.get_atom_body(&mut urids) }.unwrap();
+    match PatchMessage::try_from(object, &mut urids) {
+        Err(err) => assert_eq!(PatchError::UnknownMessageType(gain_urid), err),
+        Ok(_) => panic!("Unknown message was accepted!"),
+    }
+}
@@ -0,0 +1,379 @@
+extern crate lv2rs_atom as atom;
+extern crate lv2rs_core as core;
+extern crate lv2rs_patch as patch;
+extern crate lv2rs_urid as urid;
+
+use atom::ports::*;
+use atom::prelude::*;
+use atom::sequence::{TimeStamp, TimeUnit};
+use core::presets::*;
+use patch::parameters::*;
+use patch::*;
+use std::ffi::{CStr, CString};
+use urid::debug::DebugMap;
+
+#[test]
+fn test_parameters() {
+    let mut debug_map = DebugMap::new();
+    let mut urids = unsafe { debug_map.create_cached_map() };
+
+    let mut parameters = Parameters::new();
+    let gain = parameters.add(
+        Parameter::float(
+            CStr::from_bytes_with_nul(b"https://example.org#gain\0").unwrap(),
+            "Gain",
+            1.0,
+            0.0,
+            2.0,
+        ),
+        &mut urids,
+    );
+    let voices = parameters.add(
+        Parameter::int(
+            CStr::from_bytes_with_nul(b"https://example.org#voices\0").unwrap(),
+            "Voices",
+            4,
+            1,
+            16,
+        ),
+        &mut urids,
+    );
+    let sample = parameters.add(
+        Parameter::path(
+            CStr::from_bytes_with_nul(b"https://example.org#sample\0").unwrap(),
+            "Sample",
+        ),
+        &mut urids,
+    );
+    let level = parameters.add(
+        Parameter::float(
+            CStr::from_bytes_with_nul(b"https://example.org#level\0").unwrap(),
+            "Level",
+            0.0,
+            0.0,
+            1.0,
+        )
+        .read_only(),
+        &mut urids,
+    );
+
+    // Setting values, including clamping and type checks.
+    parameters.set(gain, ParameterValue::Float(3.0)).unwrap();
+    assert_eq!(Some(2.0), parameters.get_float(gain));
+    assert_eq!(
+        Err(ParameterError::WrongType(gain)),
+        parameters.set(gain, ParameterValue::Int(1))
+    );
+    parameters.set(level, ParameterValue::Float(0.5)).unwrap();
+
+    // Creating the atom spaces and ports.
+    let mut in_space = vec![0u8; 512];
+    let in_atom = unsafe { (in_space.as_mut_ptr() as *mut Atom).as_mut() }.unwrap();
+    *(in_atom.mut_size()) = 512 - 8;
+    let mut out_space = vec![0u8; 512];
+    let out_atom = unsafe { (out_space.as_mut_ptr() as *mut Atom).as_mut() }.unwrap();
+    *(out_atom.mut_size()) = 512 - 8;
+
+    let mut host_port: AtomOutputPort<Sequence> = AtomOutputPort::new();
+    host_port.connect_port(in_atom);
+    let mut in_port: AtomInputPort<Sequence> = AtomInputPort::new();
+    in_port.connect_port(in_atom as &Atom);
+    let mut out_port: AtomOutputPort<Sequence> = AtomOutputPort::new();
+    out_port.connect_port(out_atom);
+
+    // Writing the input: A float Set for the int voices, a Set for the read-only level and a Get.
+    {
+        let set_urid = PatchType::Set.into_urid(&mut urids);
+        let get_urid = PatchType::Get.into_urid(&mut urids);
+        let mut sequence =
+            unsafe { host_port.write_atom_body(&TimeUnit::Frames, &mut urids) }.unwrap();
+        {
+            let mut object = sequence
+                .push_event::<Object>(TimeStamp::Frames(0), &(0, set_urid), &mut urids)
+                .unwrap();
+            object
+                .write_set::<f64>(None, voices, &8.0, &mut urids)
+                .unwrap();
+        }
+        {
+            let mut object = sequence
+                .push_event::<Object>(TimeStamp::Frames(1), &(0, set_urid), &mut urids)
+                .unwrap();
+            object
+                .write_set::<f32>(None, level, &1.0, &mut urids)
+                .unwrap();
+        }
+        {
+            let mut object = sequence
+                .push_event::<Object>(TimeStamp::Frames(2), &(0, get_urid), &mut urids)
+                .unwrap();
+            object.write_get(None, Some(gain), &mut urids).unwrap();
+        }
+    }
+
+    // Handling the input.
+    {
+        let input = unsafe { in_port.get_atom_body(&mut urids) }.unwrap();
+        let mut output =
+            unsafe { out_port.write_atom_body(&TimeUnit::Frames, &mut urids) }.unwrap();
+        parameters
+            .handle_input(input, &mut output, &mut urids)
+            .unwrap();
+    }
+    assert_eq!(Some(8), parameters.get_int(voices));
+    assert_eq!(Some(0.5), parameters.get_float(level));
+
+    // Reading the output: The answer to the Get and then the notifications of gain, voices and
+    // level, in the order of declaration. The notifications follow the answer in time.
+    {
+        let sequence = out_atom.get_body::<Sequence>(&mut urids).unwrap();
+        let mut properties = Vec::new();
+        for (time, event) in sequence.iter(&mut urids) {
+            match PatchMessage::try_from_atom(event, &mut urids).unwrap() {
+                PatchMessage::Set {
+                    property, value, ..
+                } => {
+                    if property == gain {
+                        assert_eq!(2.0, *value.get_body::<f32>(&mut urids).unwrap());
+                    }
+                    properties.push((time, property));
+                }
+                _ => panic!("Wrong message type!"),
+            }
+        }
+        assert_eq!(
+            vec![
+                (TimeStamp::Frames(2), gain),
+                (TimeStamp::Frames(2), gain),
+                (TimeStamp::Frames(2), voices),
+                (TimeStamp::Frames(2), level),
+            ],
+            properties
+        );
+    }
+
+    // Storing and restoring the state via a path atom.
+    let mut stored = Vec::new();
+    parameters.store(|urid, value| stored.push((urid, value.clone())));
+    assert_eq!(3, stored.len());
+    assert!(stored.iter().all(|(urid, _)| *urid != level));
+
+    *(in_atom.mut_size()) = 512 - 8;
+    {
+        let set_urid = PatchType::Set.into_urid(&mut urids);
+        let mut sequence =
+            unsafe { host_port.write_atom_body(&TimeUnit::Frames, &mut urids) }.unwrap();
+        let mut object = sequence
+            .push_event::<Object>(TimeStamp::Frames(0), &(0, set_urid), &mut urids)
+            .unwrap();
+        object
+            .write_set::<AtomString>(
+                None,
+                sample,
+                CStr::from_bytes_with_nul(b"/tmp/kick.wav\0").unwrap(),
+                &mut urids,
+            )
+            .unwrap();
+    }
+    {
+        // A string atom is refused by a path parameter.
+        let input = unsafe { in_port.get_atom_body(&mut urids) }.unwrap();
+        let (_, event) = input.iter(&mut urids).next().unwrap();
+        match PatchMessage::try_from_atom(event, &mut urids).unwrap() {
+            PatchMessage::Set { value, .. } => assert_eq!(
+                Err(ParameterError::WrongType(sample)),
+                parameters.set_from_atom(sample, value, &mut urids)
+            ),
+            _ => panic!("Wrong message type!"),
+        }
+    }
+    parameters
+        .set(
+            sample,
+            ParameterValue::Path(CString::new("/tmp/kick.wav").unwrap()),
+        )
+        .unwrap();
+
+    // A Get without a property is answered with a Put of all parameters, including the path.
+    *(in_atom.mut_size()) = 512 - 8;
+    *(out_atom.mut_size()) = 512 - 8;
+    {
+        let get_urid = PatchType::Get.into_urid(&mut urids);
+        let mut sequence =
+            unsafe { host_port.write_atom_body(&TimeUnit::Frames, &mut urids) }.unwrap();
+        let mut object = sequence
+            .push_event::<Object>(TimeStamp::Frames(0), &(0, get_urid), &mut urids)
+            .unwrap();
+        object.write_get(None, None, &mut urids).unwrap();
+    }
+    {
+        let input = unsafe { in_port.get_atom_body(&mut urids) }.unwrap();
+        let mut output =
+            unsafe { out_port.write_atom_body(&TimeUnit::Frames, &mut urids) }.unwrap();
+        parameters
+            .handle_input(input, &mut output, &mut urids)
+            .unwrap();
+    }
+    let mut restored = Parameters::new();
+    restored.add(
+        Parameter::path(
+            CStr::from_bytes_with_nul(b"https://example.org#sample\0").unwrap(),
+            "Sample",
+        ),
+        &mut urids,
+    );
+    {
+        let sequence = out_atom.get_body::<Sequence>(&mut urids).unwrap();
+        let mut events = sequence.iter(&mut urids);
+        let (_, event) = events.next().unwrap();
+        match PatchMessage::try_from_atom(event, &mut urids).unwrap() {
+            PatchMessage::Put { body, .. } => {
+                assert_eq!(4, body.iter().count());
+                let (_, value) = body
+                    .iter()
+                    .find(|(header, _)| header.key == sample)
+                    .unwrap();
+                restored.set_from_atom(sample, value, &mut urids).unwrap();
+            }
+            _ => panic!("Wrong message type!"),
+        }
+        // The notification of the new path.
+        assert!(events.next().is_some());
+    }
+    assert_eq!(Some("/tmp/kick.wav"), restored.get_str(sample));
+}
+
+#[test]
+fn test_turtle() {
+    let mut debug_map = DebugMap::new();
+    let mut urids = unsafe { debug_map.create_cached_map() };
+
+    let mut parameters = Parameters::new();
+    parameters.add(
+        Parameter::float(
+            CStr::from_bytes_with_nul(b"https://example.org#gain\0").unwrap(),
+            "Gain",
+            1.0,
+            0.0,
+            2.0,
+        ),
+        &mut urids,
+    );
+    parameters.add(
+        Parameter::string(
+            CStr::from_bytes_with_nul(b"https://example.org#name\0").unwrap(),
+            "Name",
+            "\"Untitled\"",
+        )
+        .read_only(),
+        &mut urids,
+    );
+
+    assert_eq!(
+        "<https://example.org#gain>
+    a lv2:Parameter ;
+    rdfs:label \"Gain\" ;
+    rdfs:range atom:Float ;
+    lv2:default 1.0 ;
+    lv2:minimum 0.0 ;
+    lv2:maximum 2.0 .
+
+<https://example.org#name>
+    a lv2:Parameter ;
+    rdfs:label \"Name\" ;
+    rdfs:range atom:String ;
+    lv2:default \"\\\"Untitled\\\"\" .
+
+<https://example.org#plugin>
+    patch:writable <https://example.org#gain> ;
+    patch:readable <https://example.org#name> .
+",
+        parameters.to_turtle("https://example.org#plugin")
+    );
+}
+
+#[test]
+fn test_presets() {
+    let mut debug_map = DebugMap::new();
+    let mut urids = unsafe { debug_map.create_cached_map() };
+
+    let mut parameters = Parameters::new();
+    let gain = parameters.add(
+        Parameter::float(
+            CStr::from_bytes_with_nul(b"https://example.org#gain\0").unwrap(),
+            "Gain",
+            1.0,
+            0.0,
+            2.0,
+        ),
+        &mut urids,
+    );
+    let sample = parameters.add(
+        Parameter::path(
+            CStr::from_bytes_with_nul(b"https://example.org#sample\0").unwrap(),
+            "Sample",
+        ),
+        &mut urids,
+    );
+    let level = parameters.add(
+        Parameter::float(
+            CStr::from_bytes_with_nul(b"https://example.org#level\0").unwrap(),
+            "Level",
+            0.0,
+            0.0,
+            1.0,
+        )
+        .read_only(),
+        &mut urids,
+    );
+    parameters.set(gain, ParameterValue::Float(0.5)).unwrap();
+    parameters
+        .set(
+            sample,
+            ParameterValue::Path(CString::new("/tmp/kick.wav").unwrap()),
+        )
+        .unwrap();
+
+    // Only writable parameters are stored.
+    let mut preset = Preset::new(
+        "https://example.org/presets#soft",
+        "https://example.org#plugin",
+    );
+    parameters.store_preset(&mut preset);
+    assert_eq!(
+        vec![
+            ("https://example.org#gain", &StateValue::Float(0.5)),
+            (
+                "https://example.org#sample",
+                &StateValue::Path("/tmp/kick.wav".to_owned())
+            ),
+        ],
+        preset.state_values().collect::<Vec<_>>()
+    );
+
+    // Loading a preset converts numbers and ignores other properties.
+    let mut preset = Preset::from_turtle(&preset.to_turtle())
+        .unwrap()
+        .pop()
+        .unwrap();
+    preset.set_state_value("https://example.org#gain", StateValue::Int(3));
+    preset.set_state_value("https://example.org#other", StateValue::Bool(true));
+    let mut restored = Parameters::new();
+    for (_, parameter, _) in parameters.iter() {
+        restored.add(parameter.clone(), &mut urids);
+    }
+    restored.load_preset(&preset).unwrap();
+    assert_eq!(Some(2.0), restored.get_float(gain));
+    assert_eq!(Some("/tmp/kick.wav"), restored.get_str(sample));
+    assert_eq!(Some(0.0), restored.get_float(level));
+
+    // Values of the wrong type are refused.
+    preset.set_state_value(
+        "https://example.org#sample",
+        StateValue::String("/tmp/snare.wav".to_owned()),
+    );
+    assert_eq!(
+        Err(ParameterError::WrongType(sample)),
+        restored.load_preset(&preset)
+    );
+}
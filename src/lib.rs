@@ -10,16 +10,22 @@
 //! The core of the library is formed by the [`core`](https://docs.rs/lv2rs-core) crate, which
 //! contains a trait and a macro that makes the creation of plugins easy. Then, there are the
 //! [`atom`](https://docs.rs/lv2rs-atom) and the [`midi`](https://docs.rs/lv2rs-midi) crates, which
-//! provide general data exchange and MIDI messages.
+//! provide general data exchange and MIDI messages. The [`patch`](https://docs.rs/lv2rs-patch)
+//! crate provides messages to access the properties of a plugin and the
+//! [`time`](https://docs.rs/lv2rs-time) crate provides the transport position of the host.
+//! User interfaces for plugins are created with the [`ui`](https://docs.rs/lv2rs-ui) crate.
 //!
 //! ## What is supported, what isn't?
 //!
-//! Currently 4 out of 22 [official and stable LV2 specifications](http://lv2plug.in/ns/) are
+//! Currently 7 out of 22 [official and stable LV2 specifications](http://lv2plug.in/ns/) are
 //! supported. These are:
 //! 
 //! * Atom
 //! * LV2
 //! * MIDI
+//! * Patch
+//! * Time
+//! * UI
 //! * URID
 //! 
 //! This is a frozen prototype and therefore, development of this crate will not continue here. Further
@@ -28,6 +34,9 @@
 pub extern crate lv2rs_atom as atom;
 pub extern crate lv2rs_core as core;
 pub extern crate lv2rs_midi as midi;
+pub extern crate lv2rs_patch as patch;
+pub extern crate lv2rs_time as time;
+pub extern crate lv2rs_ui as ui;
 pub extern crate lv2rs_urid as urid;
 
 /// Re-export module intended for wildcard use
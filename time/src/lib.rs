@@ -0,0 +1,87 @@
+//! A Rust adaptation of the [LV2 time library](http://lv2plug.in/ns/ext/time/time.html).
+//!
+//! Plugins that need to be synchronized to the host's transport, for example tempo-synced delays
+//! or arpeggiators, receive the transport position as `time:Position` objects in their control
+//! sequence. This crate provides the [`Position`](struct.Position.html) struct, which keeps track
+//! of the transport state and is updated with these objects.
+//!
+//! This is a frozen prototype and therefore, development of this crate will not continue here. Further
+//! development continues as [rust-lv2](https://github.com/rust-dsp/rust-lv2).
+//!
+//! ## Use
+//!
+//! A `Position` is stored in the plugin struct and updated with every event of the control
+//! sequence. Events that aren't position objects are simply rejected by the position:
+//!
+//!     extern crate lv2rs_atom as atom;
+//!     extern crate lv2rs_time as time;
+//!     extern crate lv2rs_urid as urid;
+//!
+//!     use atom::prelude::*;
+//!     use atom::ports::*;
+//!     use atom::sequence::{TimeStamp, TimeUnit};
+//!     use time::Position;
+//!     use urid::{CachedMap, debug::DebugMap};
+//!     use std::ffi::CStr;
+//!
+//!     pub struct Plugin {
+//!         control_port: AtomInputPort<Sequence>,
+//!         position: Position,
+//!         urids: CachedMap,
+//!     }
+//!
+//!     impl Plugin {
+//!         /// Simulated `run` method.
+//!         fn run(&mut self) {
+//!             let sequence = unsafe { self.control_port.get_atom_body(&mut self.urids) }.unwrap();
+//!             for (_, event) in sequence.iter(&mut self.urids) {
+//!                 self.position.update_from_atom(event, &mut self.urids).ok();
+//!             }
+//!         }
+//!     }
+//!
+//!     // Getting a debug URID map.
+//!     let mut debug_map = DebugMap::new();
+//!     let mut urids = unsafe { debug_map.create_cached_map() };
+//!
+//!     // Creating the atom space and writing a position object, just like a host would.
+//!     let mut atom_space = vec![0u8; 256];
+//!     let atom = unsafe { (atom_space.as_mut_ptr() as *mut Atom).as_mut() }.unwrap();
+//!     *(atom.mut_size()) = 256 - 8;
+//!     {
+//!         let position_urid = urids.map(CStr::from_bytes_with_nul(time::uris::POSITION_URI).unwrap());
+//!         let bpm_urid =
+//!             urids.map(CStr::from_bytes_with_nul(time::uris::BEATS_PER_MINUTE_URI).unwrap());
+//!         let speed_urid = urids.map(CStr::from_bytes_with_nul(time::uris::SPEED_URI).unwrap());
+//!
+//!         let mut host_port: AtomOutputPort<Sequence> = AtomOutputPort::new();
+//!         host_port.connect_port(atom);
+//!         let mut sequence =
+//!             unsafe { host_port.write_atom_body(&TimeUnit::Frames, &mut urids) }.unwrap();
+//!         let mut object = sequence
+//!             .push_event::<Object>(TimeStamp::Frames(0), &(0, position_urid), &mut urids)
+//!             .unwrap();
+//!         object.push_property::<f64>(bpm_urid, 0, &120.0, &mut urids).unwrap();
+//!         object.push_property::<f32>(speed_urid, 0, &1.0, &mut urids).unwrap();
+//!     }
+//!
+//!     // Creating the plugin and connecting the port.
+//!     let mut plugin = Plugin {
+//!         control_port: AtomInputPort::new(),
+//!         position: Position::new(),
+//!         urids: urids,
+//!     };
+//!     plugin.control_port.connect_port(atom as &Atom);
+//!
+//!     // Calling `run`.
+//!     plugin.run();
+//!     assert_eq!(Some(120.0), plugin.position.beats_per_minute);
+//!     assert!(plugin.position.is_rolling());
+//!     assert_eq!(Some(22050.0), plugin.position.frames_per_beat(44100.0));
+extern crate lv2rs_atom;
+extern crate lv2rs_urid;
+
+mod position;
+pub mod uris;
+
+pub use position::*;
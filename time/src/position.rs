@@ -0,0 +1,195 @@
+//! Transport position of the host.
+use crate::uris;
+use lv2rs_atom::object::Object;
+use lv2rs_atom::prelude::*;
+use lv2rs_urid::{CachedMap, URID};
+use std::ffi::CStr;
+
+/// The transport position of the host.
+///
+/// A host sends its transport position as an [`Object`](../lv2rs_atom/object/type.Object.html) of
+/// the type `time:Position`, usually as an event in the control sequence of the plugin. However,
+/// the host does not need to send all properties every time; It may only send the ones that
+/// changed. Therefore, every field of this struct is optional and a `Position` is meant to be kept
+/// in the plugin struct and to be [updated](#method.update) with every incoming position object.
+/// Properties that aren't contained in an object keep their previous value.
+///
+/// Hosts don't agree on the numeric atom types they use for the properties. Therefore, every
+/// property is read from `Int`, `Long`, `Float` and `Double` atoms alike. Fractional numbers are
+/// truncated when they are read into an integer field.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Position {
+    /// The number of the current frame since the start of the timeline.
+    pub frame: Option<i64>,
+    /// The transport speed, where `1.0` is normal playback and `0.0` is stopped.
+    pub speed: Option<f64>,
+    /// The number of the current bar, starting with zero.
+    pub bar: Option<i64>,
+    /// The beat within the current bar, starting with zero.
+    pub bar_beat: Option<f64>,
+    /// The number of beats since the start of the timeline.
+    pub beat: Option<f64>,
+    /// The note value that counts as one beat, for example `4` for a quarter note.
+    pub beat_unit: Option<i64>,
+    /// The number of beats in one bar.
+    pub beats_per_bar: Option<f64>,
+    /// The tempo in beats per minute.
+    pub beats_per_minute: Option<f64>,
+}
+
+/// Errors that may occur when updating a [`Position`](struct.Position.html).
+#[derive(Debug)]
+pub enum PositionError {
+    /// The atom is not an object.
+    NotAnObject,
+    /// The object is not of the type `time:Position`.
+    WrongObjectType,
+    /// The property with the contained key is known, but its body is not a number.
+    NotANumber(URID),
+}
+
+/// A number read from any of the numeric atom types.
+enum Number {
+    Integer(i64),
+    Float(f64),
+}
+
+impl Number {
+    /// Try to read a number from an atom.
+    ///
+    /// `None` is returned if the atom is neither an `Int`, a `Long`, a `Float` or a `Double`.
+    fn from_atom(atom: &Atom, urids: &mut CachedMap) -> Option<Self> {
+        if let Ok(value) = atom.get_body::<i32>(urids) {
+            Some(Number::Integer(*value as i64))
+        } else if let Ok(value) = atom.get_body::<i64>(urids) {
+            Some(Number::Integer(*value))
+        } else if let Ok(value) = atom.get_body::<f32>(urids) {
+            Some(Number::Float(*value as f64))
+        } else if let Ok(value) = atom.get_body::<f64>(urids) {
+            Some(Number::Float(*value))
+        } else {
+            None
+        }
+    }
+
+    fn as_f64(&self) -> f64 {
+        match self {
+            Number::Integer(value) => *value as f64,
+            Number::Float(value) => *value,
+        }
+    }
+
+    fn as_i64(&self) -> i64 {
+        match self {
+            Number::Integer(value) => *value,
+            Number::Float(value) => *value as i64,
+        }
+    }
+}
+
+impl Position {
+    /// Create a new position where no property is known.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Update the position with the properties of a `time:Position` object.
+    ///
+    /// Every property in the object overwrites the corresponding field, all other fields are left
+    /// untouched. Unknown properties are ignored.
+    ///
+    /// If the object is not a `time:Position` or if a known property does not contain a number,
+    /// an `Err` is returned and the position is not changed at all.
+    pub fn update(&mut self, object: &Object, urids: &mut CachedMap) -> Result<(), PositionError> {
+        if object.header.otype != urids.map(unsafe { uri(uris::POSITION_URI) }) {
+            return Err(PositionError::WrongObjectType);
+        }
+
+        let frame_urid = urids.map(unsafe { uri(uris::FRAME_URI) });
+        let speed_urid = urids.map(unsafe { uri(uris::SPEED_URI) });
+        let bar_urid = urids.map(unsafe { uri(uris::BAR_URI) });
+        let bar_beat_urid = urids.map(unsafe { uri(uris::BAR_BEAT_URI) });
+        let beat_urid = urids.map(unsafe { uri(uris::BEAT_URI) });
+        let beat_unit_urid = urids.map(unsafe { uri(uris::BEAT_UNIT_URI) });
+        let beats_per_bar_urid = urids.map(unsafe { uri(uris::BEATS_PER_BAR_URI) });
+        let beats_per_minute_urid = urids.map(unsafe { uri(uris::BEATS_PER_MINUTE_URI) });
+
+        // The new values are collected in a copy, so that an error leaves `self` untouched.
+        let mut position = self.clone();
+        for (header, atom) in object.iter() {
+            let key = header.key;
+            if key == frame_urid {
+                position.frame = Some(read_integer(atom, key, urids)?);
+            } else if key == speed_urid {
+                position.speed = Some(read_float(atom, key, urids)?);
+            } else if key == bar_urid {
+                position.bar = Some(read_integer(atom, key, urids)?);
+            } else if key == bar_beat_urid {
+                position.bar_beat = Some(read_float(atom, key, urids)?);
+            } else if key == beat_urid {
+                position.beat = Some(read_float(atom, key, urids)?);
+            } else if key == beat_unit_urid {
+                position.beat_unit = Some(read_integer(atom, key, urids)?);
+            } else if key == beats_per_bar_urid {
+                position.beats_per_bar = Some(read_float(atom, key, urids)?);
+            } else if key == beats_per_minute_urid {
+                position.beats_per_minute = Some(read_float(atom, key, urids)?);
+            }
+        }
+
+        *self = position;
+        Ok(())
+    }
+
+    /// Update the position with an atom that may be a `time:Position` object.
+    ///
+    /// This is a shortcut for events of a sequence: If the atom is an object, it is passed to
+    /// [`update`](#method.update). Otherwise, `PositionError::NotAnObject` is returned and the
+    /// position is not changed.
+    pub fn update_from_atom(
+        &mut self,
+        atom: &Atom,
+        urids: &mut CachedMap,
+    ) -> Result<(), PositionError> {
+        let object = atom
+            .get_body::<Object>(urids)
+            .map_err(|_| PositionError::NotAnObject)?;
+        self.update(object, urids)
+    }
+
+    /// Return `true` if the transport is known to be rolling.
+    pub fn is_rolling(&self) -> bool {
+        match self.speed {
+            Some(speed) => speed != 0.0,
+            None => false,
+        }
+    }
+
+    /// Calculate the length of a beat in frames, if the tempo is known.
+    pub fn frames_per_beat(&self, rate: f64) -> Option<f64> {
+        let beats_per_minute = self.beats_per_minute?;
+        if beats_per_minute > 0.0 {
+            Some(rate * 60.0 / beats_per_minute)
+        } else {
+            None
+        }
+    }
+}
+
+/// Read an integer property from any numeric atom.
+fn read_integer(atom: &Atom, key: URID, urids: &mut CachedMap) -> Result<i64, PositionError> {
+    Number::from_atom(atom, urids)
+        .map(|number| number.as_i64())
+        .ok_or(PositionError::NotANumber(key))
+}
+
+/// Read a fractional property from any numeric atom.
+fn read_float(atom: &Atom, key: URID, urids: &mut CachedMap) -> Result<f64, PositionError> {
+    Number::from_atom(atom, urids)
+        .map(|number| number.as_f64())
+        .ok_or(PositionError::NotANumber(key))
+}
+
+unsafe fn uri(uri: &'static [u8]) -> &'static CStr {
+    CStr::from_bytes_with_nul_unchecked(uri)
+}
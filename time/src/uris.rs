@@ -0,0 +1,15 @@
+//! URIs of the LV2 time library.
+pub const URI: &[u8] = b"http://lv2plug.in/ns/ext/time\0";
+pub const TIME_URI: &[u8] = b"http://lv2plug.in/ns/ext/time#Time\0";
+pub const POSITION_URI: &[u8] = b"http://lv2plug.in/ns/ext/time#Position\0";
+pub const RATE_URI: &[u8] = b"http://lv2plug.in/ns/ext/time#Rate\0";
+pub const LOWER_POSITION_URI: &[u8] = b"http://lv2plug.in/ns/ext/time#position\0";
+pub const BAR_BEAT_URI: &[u8] = b"http://lv2plug.in/ns/ext/time#barBeat\0";
+pub const BAR_URI: &[u8] = b"http://lv2plug.in/ns/ext/time#bar\0";
+pub const BEAT_URI: &[u8] = b"http://lv2plug.in/ns/ext/time#beat\0";
+pub const BEAT_UNIT_URI: &[u8] = b"http://lv2plug.in/ns/ext/time#beatUnit\0";
+pub const BEATS_PER_BAR_URI: &[u8] = b"http://lv2plug.in/ns/ext/time#beatsPerBar\0";
+pub const BEATS_PER_MINUTE_URI: &[u8] = b"http://lv2plug.in/ns/ext/time#beatsPerMinute\0";
+pub const FRAME_URI: &[u8] = b"http://lv2plug.in/ns/ext/time#frame\0";
+pub const FRAMES_PER_SECOND_URI: &[u8] = b"http://lv2plug.in/ns/ext/time#framesPerSecond\0";
+pub const SPEED_URI: &[u8] = b"http://lv2plug.in/ns/ext/time#speed\0";
@@ -0,0 +1,124 @@
+extern crate lv2rs_atom as atom;
+extern crate lv2rs_time as time;
+extern crate lv2rs_urid as urid;
+
+use atom::ports::*;
+use atom::prelude::*;
+use std::ffi::CStr;
+use time::*;
+use urid::debug::DebugMap;
+
+fn map(urids: &mut urid::CachedMap, uri: &'static [u8]) -> urid::URID {
+    urids.map(CStr::from_bytes_with_nul(uri).unwrap())
+}
+
+#[test]
+fn test_position() {
+    let mut debug_map = DebugMap::new();
+    let mut urids = unsafe { debug_map.create_cached_map() };
+
+    let position_urid = map(&mut urids, uris::POSITION_URI);
+    let frame_urid = map(&mut urids, uris::FRAME_URI);
+    let speed_urid = map(&mut urids, uris::SPEED_URI);
+    let bar_urid = map(&mut urids, uris::BAR_URI);
+    let bar_beat_urid = map(&mut urids, uris::BAR_BEAT_URI);
+    let bpm_urid = map(&mut urids, uris::BEATS_PER_MINUTE_URI);
+
+    // Creating the atom space.
+    let mut atom_space = vec![0u8; 256];
+    let atom = unsafe { (atom_space.as_mut_ptr() as *mut Atom).as_mut() }.unwrap();
+    *(atom.mut_size()) = 256 - 8;
+
+    // Creating the ports and connecting them.
+    let mut out_port: AtomOutputPort<Object> = AtomOutputPort::new();
+    out_port.connect_port(atom);
+    let mut in_port: AtomInputPort<Object> = AtomInputPort::new();
+    in_port.connect_port(atom as &Atom);
+
+    let mut position = Position::new();
+    assert!(!position.is_rolling());
+    assert_eq!(None, position.frames_per_beat(44100.0));
+
+    // A complete update, using a different numeric type for every property.
+    {
+        let mut frame =
+            unsafe { out_port.write_atom_body(&(0, position_urid), &mut urids) }.unwrap();
+        frame
+            .push_property::<i64>(frame_urid, 0, &48000, &mut urids)
+            .unwrap();
+        frame
+            .push_property::<f32>(speed_urid, 0, &1.0, &mut urids)
+            .unwrap();
+        frame
+            .push_property::<i32>(bar_urid, 0, &3, &mut urids)
+            .unwrap();
+        frame
+            .push_property::<f64>(bar_beat_urid, 0, &1.5, &mut urids)
+            .unwrap();
+        frame
+            .push_property::<i32>(bpm_urid, 0, &90, &mut urids)
+            .unwrap();
+    }
+    let object = unsafe { in_port.get_atom_body(&mut urids) }.unwrap();
+    position.update(object, &mut urids).unwrap();
+    assert_eq!(Some(48000), position.frame);
+    assert_eq!(Some(1.0), position.speed);
+    assert_eq!(Some(3), position.bar);
+    assert_eq!(Some(1.5), position.bar_beat);
+    assert_eq!(Some(90.0), position.beats_per_minute);
+    assert_eq!(None, position.beat);
+    assert!(position.is_rolling());
+
+    // Resetting the atom space, just like a host would, and sending a partial update, which only
+    // changes the contained properties.
+    *(atom.mut_size()) = 256 - 8;
+    {
+        let mut frame =
+            unsafe { out_port.write_atom_body(&(0, position_urid), &mut urids) }.unwrap();
+        frame
+            .push_property::<f64>(speed_urid, 0, &0.0, &mut urids)
+            .unwrap();
+    }
+    let object = unsafe { in_port.get_atom_body(&mut urids) }.unwrap();
+    position.update(object, &mut urids).unwrap();
+    assert_eq!(Some(0.0), position.speed);
+    assert_eq!(Some(48000), position.frame);
+    assert_eq!(Some(90.0), position.beats_per_minute);
+    assert!(!position.is_rolling());
+
+    // A property with a non-numeric body is rejected and leaves the position untouched.
+    *(atom.mut_size()) = 256 - 8;
+    {
+        let mut frame =
+            unsafe { out_port.write_atom_body(&(0, position_urid), &mut urids) }.unwrap();
+        frame
+            .push_property::<f64>(speed_urid, 0, &1.0, &mut urids)
+            .unwrap();
+        frame
+            .push_property::<Literal>(bar_urid, 0, &0, &mut urids)
+            .unwrap()
+            .append_string("three")
+            .unwrap();
+    }
+    let object = unsafe { in_port.get_atom_body(&mut urids) }.unwrap();
+    match position.update(object, &mut urids) {
+        Err(PositionError::NotANumber(key)) => assert_eq!(bar_urid, key),
+        _ => panic!("Non-numeric property was accepted!"),
+    }
+    assert_eq!(Some(0.0), position.speed);
+
+    // Objects of another type are rejected.
+    *(atom.mut_size()) = 256 - 8;
+    {
+        let mut frame = unsafe { out_port.write_atom_body(&(0, speed_urid), &mut urids) }.unwrap();
+        frame
+            .push_property::<f64>(speed_urid, 0, &1.0, &mut urids)
+            .unwrap();
+    }
+    let object = unsafe { in_port.get_atom_body(&mut urids) }.unwrap();
+    match position.update(object, &mut urids) {
+        Err(PositionError::WrongObjectType) => (),
+        _ => panic!("Object of a wrong type was accepted!"),
+    }
+    assert_eq!(Some(0.0), position.speed);
+}
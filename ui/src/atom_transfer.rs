@@ -0,0 +1,203 @@
+//! Sending and receiving atoms.
+//!
+//! Atoms are the common language of plugins and their UIs: A UI sends atoms to the plugin's atom
+//! input ports with the [`Controller`](struct.Controller.html) and the host notifies the UI about
+//! the atoms written by the plugin to its atom output ports. Since the plugin reads and writes these
+//! atoms with the usual [ports](../lv2rs_atom/ports/index.html), all message types, for example
+//! patch messages, work in both directions.
+//!
+//! On the UI side, an atom is written to an owned [`AtomBuffer`](struct.AtomBuffer.html), just like
+//! it would be written to an output port, and sent with
+//! [`Controller::write_atom`](struct.Controller.html#method.write_atom). Incoming port events are
+//! read with [`read_port_event`](fn.read_port_event.html).
+use crate::plugin_ui::Controller;
+use lv2rs_atom::frame::RootFrame;
+use lv2rs_atom::prelude::*;
+use lv2rs_atom::uris;
+use lv2rs_atom::GetBodyError;
+use lv2rs_urid::{CachedMap, URID};
+use std::ffi::CStr;
+
+/// The protocols to transfer atoms between plugins and UIs.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum TransferProtocol {
+    /// `atom:atomTransfer`: The atom is the new value of the port.
+    ///
+    /// When sent to the plugin, the host writes the atom to the port's buffer. When received by
+    /// the UI, the atom is the value the plugin has written to the port.
+    Atom,
+    /// `atom:eventTransfer`: The atom is an event.
+    ///
+    /// When sent to the plugin, the host appends the atom to the sequence of the port. When
+    /// received by the UI, the atom is one of the events the plugin has written to the port.
+    Event,
+}
+
+impl TransferProtocol {
+    /// Return the URI of the protocol.
+    pub fn get_uri(&self) -> &'static CStr {
+        let uri = match self {
+            TransferProtocol::Atom => uris::ATOM_TRANSFER_URI,
+            TransferProtocol::Event => uris::EVENT_TRANSFER_URI,
+        };
+        unsafe { CStr::from_bytes_with_nul_unchecked(uri) }
+    }
+
+    /// Try to get a `TransferProtocol` value from a URID.
+    ///
+    /// `None` is returned if the URID isn't the URID of an atom transfer protocol, for example if
+    /// it's 0, the protocol of control ports.
+    pub fn from_urid(urid: URID, urids: &mut CachedMap) -> Option<TransferProtocol> {
+        [TransferProtocol::Atom, TransferProtocol::Event]
+            .iter()
+            .find(|protocol| protocol.into_urid(urids) == urid)
+            .cloned()
+    }
+
+    /// Return the corresponding URID of the protocol.
+    pub fn into_urid(&self, urids: &mut CachedMap) -> URID {
+        urids.map(self.get_uri())
+    }
+}
+
+/// An owned, aligned buffer for a single atom.
+///
+/// This is the UI's counterpart to the [`AtomOutputPort`](../lv2rs_atom/ports/struct.AtomOutputPort.html):
+/// An atom is written to the buffer with [`write`](#method.write) and the buffer is then sent to
+/// the plugin with [`Controller::write_atom`](struct.Controller.html#method.write_atom).
+///
+///     extern crate lv2rs_atom as atom;
+///     extern crate lv2rs_ui as ui;
+///     extern crate lv2rs_urid as urid;
+///
+///     use atom::prelude::*;
+///     use ui::AtomBuffer;
+///     use urid::debug::DebugMap;
+///
+///     let mut debug_map = DebugMap::new();
+///     let mut urids = unsafe { debug_map.create_cached_map() };
+///
+///     let mut buffer = AtomBuffer::new(256);
+///     buffer.write::<f32>(&42.0, &mut urids).unwrap();
+///     assert_eq!(42.0, *buffer.atom().get_body::<f32>(&mut urids).unwrap());
+pub struct AtomBuffer {
+    data: Vec<u64>,
+}
+
+impl AtomBuffer {
+    /// Create a new buffer with the given capacity in bytes, including the header of the atom.
+    pub fn new(capacity: usize) -> Self {
+        let len = (capacity + 7) / 8;
+        Self {
+            data: vec![0; len.max(1)],
+        }
+    }
+
+    /// Return the capacity of the buffer in bytes.
+    pub fn capacity(&self) -> usize {
+        self.data.len() * 8
+    }
+
+    /// Write a new atom to the buffer.
+    ///
+    /// The previous atom is overwritten. The returned frame works just like the frame returned by
+    /// an [`AtomOutputPort`](../lv2rs_atom/ports/struct.AtomOutputPort.html) and can be used to
+    /// extend the atom.
+    pub fn write<'a, A: AtomBody + ?Sized>(
+        &'a mut self,
+        parameter: &A::InitializationParameter,
+        urids: &mut CachedMap,
+    ) -> Result<RootFrame<'a, A>, ()> {
+        let capacity = self.capacity();
+        let space =
+            unsafe { std::slice::from_raw_parts_mut(self.data.as_mut_ptr() as *mut u8, capacity) };
+        let mut frame = RootFrame::new(space, urids)?;
+        unsafe { A::initialize_body(&mut frame, parameter, urids) }?;
+        Ok(frame)
+    }
+
+    /// Return a reference to the atom in the buffer.
+    ///
+    /// If nothing was written yet, this atom has the type 0 and is empty.
+    pub fn atom(&self) -> &Atom {
+        unsafe { (self.data.as_ptr() as *const Atom).as_ref() }.unwrap()
+    }
+
+    /// Return the atom, including its header, as bytes.
+    pub fn as_bytes(&self) -> &[u8] {
+        let size = std::mem::size_of::<Atom>() + self.atom().size();
+        unsafe { std::slice::from_raw_parts(self.data.as_ptr() as *const u8, size) }
+    }
+}
+
+impl Controller {
+    /// Send the atom in the buffer to a port of the plugin.
+    pub fn write_atom(
+        &self,
+        port_index: u32,
+        protocol: TransferProtocol,
+        buffer: &AtomBuffer,
+        urids: &mut CachedMap,
+    ) {
+        self.write(port_index, protocol.into_urid(urids), buffer.as_bytes());
+    }
+}
+
+/// Errors that may occur when calling [`read_port_event`](fn.read_port_event.html).
+#[derive(Debug, PartialEq)]
+pub enum ReadAtomError {
+    /// The protocol of the port event is not an atom transfer protocol.
+    UnknownProtocol(URID),
+    /// The buffer is too small, misaligned or its size doesn't match the size of the atom.
+    MalformedBuffer,
+    /// The atom has another type than the requested one.
+    WrongType(URID),
+    /// The body of the atom is malformed.
+    MalformedAtom,
+}
+
+/// Read the atom of a port event.
+///
+/// This function checks that the event was sent with one of the atom
+/// [transfer protocols](enum.TransferProtocol.html) and that the buffer contains a well-formed atom
+/// of type `A`. If so, a reference to the body of the atom is returned. It is meant to be used
+/// in [`PluginUI::port_event`](trait.PluginUI.html#method.port_event):
+///
+///     # extern crate lv2rs_atom as atom;
+///     # extern crate lv2rs_ui as ui;
+///     # extern crate lv2rs_urid as urid;
+///     # use atom::prelude::*;
+///     # use ui::*;
+///     # use urid::debug::DebugMap;
+///     # let mut debug_map = DebugMap::new();
+///     # let mut urids = unsafe { debug_map.create_cached_map() };
+///     # let mut message = AtomBuffer::new(64);
+///     # message.write::<i32>(&17, &mut urids).unwrap();
+///     # let protocol = TransferProtocol::Event.into_urid(&mut urids);
+///     # let buffer = message.as_bytes();
+///     // In `port_event(&mut self, port_index: u32, protocol: u32, buffer: &[u8])`:
+///     match read_port_event::<i32>(protocol, buffer, &mut urids) {
+///         Ok(value) => assert_eq!(17, *value),
+///         Err(_) => panic!("Not an integer!"),
+///     }
+pub fn read_port_event<'a, A: AtomBody + ?Sized>(
+    protocol: URID,
+    buffer: &'a [u8],
+    urids: &mut CachedMap,
+) -> Result<&'a A, ReadAtomError> {
+    TransferProtocol::from_urid(protocol, urids).ok_or(ReadAtomError::UnknownProtocol(protocol))?;
+
+    let header_size = std::mem::size_of::<Atom>();
+    if buffer.len() < header_size || (buffer.as_ptr() as usize) % 8 != 0 {
+        return Err(ReadAtomError::MalformedBuffer);
+    }
+    let atom = unsafe { (buffer.as_ptr() as *const Atom).as_ref() }.unwrap();
+    if buffer.len() < header_size + atom.size() {
+        return Err(ReadAtomError::MalformedBuffer);
+    }
+
+    atom.get_body::<A>(urids).map_err(|err| match err {
+        GetBodyError::WrongURID => ReadAtomError::WrongType(atom.atom_type()),
+        GetBodyError::MalformedAtom => ReadAtomError::MalformedAtom,
+    })
+}
@@ -0,0 +1,140 @@
+//! Wrappers for the instance-access and data-access features.
+//!
+//! Both features are only provided if the UI runs in the same process as the plugin. Please read
+//! the [threading rules](../lv2rs_core/instance_access/index.html#threading-rules) before using
+//! them.
+use lv2rs_core::instance_access::{SharedData, DATA_ACCESS_URI, INSTANCE_ACCESS_URI};
+use lv2rs_core::{ExtensionData, Feature, FeaturesList};
+use std::ffi::CStr;
+use std::os::raw::*;
+use std::sync::Arc;
+
+/// Typed access to the plugin instance controlled by the UI.
+///
+/// The UI gets this handle from the `instance-access` feature, which contains a pointer to the
+/// plugin instance. Since this pointer is untyped, the URI of the plugin the UI was created for is
+/// compared to the [`URI`](../lv2rs_core/instance_access/trait.SharedData.html#associatedconstant.URI)
+/// of `P` first. A UI should get the shared data of the plugin in its `instantiate` method:
+///
+///     # extern crate lv2rs_core as core;
+///     # extern crate lv2rs_ui as ui;
+///     # use core::instance_access::SharedData;
+///     # use std::ffi::CStr;
+///     # use std::sync::atomic::AtomicU32;
+///     # use std::sync::Arc;
+///     # struct Meter {
+///     #     peak: Arc<AtomicU32>,
+///     # }
+///     # impl core::Plugin for Meter {
+///     #     fn instantiate(
+///     #         _: &core::Descriptor,
+///     #         _: f64,
+///     #         _: &CStr,
+///     #         _: Option<&core::FeaturesList>,
+///     #     ) -> Option<Self> {
+///     #         None
+///     #     }
+///     #     fn connect_port(&mut self, _port: u32, _data: *mut ()) {}
+///     #     fn run(&mut self, _n_samples: u32) {}
+///     # }
+///     # impl SharedData for Meter {
+///     #     const URI: &'static [u8] = b"http://example.org/Meter\0";
+///     #     type Data = AtomicU32;
+///     #     fn shared_data(&self) -> Arc<AtomicU32> {
+///     #         self.peak.clone()
+///     #     }
+///     # }
+///     use ui::InstanceAccess;
+///
+///     /// Called by `PluginUI::instantiate`.
+///     fn get_peak(features: &core::FeaturesList, plugin_uri: &CStr) -> Option<Arc<AtomicU32>> {
+///         let access = InstanceAccess::<Meter>::try_from_features(features, plugin_uri)?;
+///         Some(unsafe { access.shared_data() })
+///     }
+///
+///     let plugin_uri = CStr::from_bytes_with_nul(Meter::URI).unwrap();
+///     assert!(get_peak(&[], plugin_uri).is_none());
+pub struct InstanceAccess<P: SharedData> {
+    instance: *const P,
+}
+
+impl<P: SharedData> InstanceAccess<P> {
+    /// Try to get the plugin instance from the features list.
+    ///
+    /// `None` is returned if the host doesn't provide the feature or if the UI was created for
+    /// another plugin than `P`.
+    pub fn try_from_features(features: &FeaturesList, plugin_uri: &CStr) -> Option<Self> {
+        if plugin_uri.to_bytes_with_nul() != P::URI {
+            return None;
+        }
+        let instance = Feature::get_feature_raw(features, unsafe {
+            CStr::from_bytes_with_nul_unchecked(INSTANCE_ACCESS_URI)
+        })?;
+        if instance.is_null() {
+            None
+        } else {
+            Some(Self {
+                instance: instance as *const P,
+            })
+        }
+    }
+
+    /// Return a reference to the plugin instance.
+    ///
+    /// # Safety
+    ///
+    /// The host may run the plugin in the audio thread while the reference is alive, which is a
+    /// data race. Therefore, the reference may only be used while the plugin isn't running and
+    /// only the parts of the plugin that are `Sync` may be accessed.
+    pub unsafe fn instance(&self) -> &P {
+        &*self.instance
+    }
+
+    /// Return the data the plugin shares with the UI.
+    ///
+    /// # Safety
+    ///
+    /// This method calls [`SharedData::shared_data`](../lv2rs_core/instance_access/trait.SharedData.html#tymethod.shared_data),
+    /// which has the same requirements as [`instance`](#method.instance). It should be called once
+    /// in the UI's `instantiate` method.
+    pub unsafe fn shared_data(&self) -> Arc<P::Data> {
+        self.instance().shared_data()
+    }
+}
+
+/// The `data-access` feature.
+///
+/// The host passes the plugin's `extension_data` function to the UI, which can then access the
+/// same interfaces as the host. Unlike the instance, the returned data is static and can be used
+/// from every thread, as long as its type allows it.
+#[repr(C)]
+pub struct DataAccess {
+    /// The `extension_data` function of the plugin.
+    pub data_access: unsafe extern "C" fn(uri: *const c_char) -> *const c_void,
+}
+
+impl DataAccess {
+    /// Try to find the data-access feature in the features list.
+    ///
+    /// This function returns `None` if the host doesn't provide the feature.
+    pub fn try_from_features(features: &FeaturesList) -> Option<&'static Self> {
+        unsafe {
+            Feature::get_feature::<Self>(
+                features,
+                CStr::from_bytes_with_nul_unchecked(DATA_ACCESS_URI),
+            )
+        }
+        .map(|data_access| &*data_access)
+    }
+
+    /// Get the extension data of the plugin for the given URI.
+    ///
+    /// `None` is returned if the plugin doesn't provide data for this URI.
+    ///
+    /// # Safety
+    ///
+    /// `T` has to be the type of the extension data defined for this URI.
+    pub unsafe fn extension_data<T: ExtensionData>(&self, uri: &CStr) -> Option<&'static T> {
+        ((self.data_access)(uri.as_ptr()) as *const T).as_ref()
+    }
+}
@@ -0,0 +1,221 @@
+//! Wrappers for the idle, show and resize interfaces.
+use crate::plugin_ui::*;
+use crate::uris;
+use lv2rs_core::{ExtensionData, Feature, FeaturesList};
+use std::ffi::CStr;
+use std::os::raw::*;
+
+/// UI extension for getting regular idle calls from the host.
+///
+/// UIs that don't use a toolkit with its own event loop, or that only implement the
+/// [`ShowInterface`](trait.ShowInterface.html), need to be called regularly by the host in order
+/// to process events and redraw. Such UIs implement this trait and return the
+/// [`IdleInterfaceData`](struct.IdleInterfaceData.html) in their `extension_data` method.
+pub trait IdleInterface: PluginUI {
+    /// Run a single iteration of the UI's idle loop.
+    ///
+    /// Return `false` if the UI was closed, in which case the host should stop calling this
+    /// method and may destroy the UI.
+    fn idle(&mut self) -> bool;
+}
+
+/// Raw representation of the idle interface.
+///
+/// A UI returns a static reference to this struct when the host asks for the
+/// [`IDLE_INTERFACE_URI`](uris/constant.IDLE_INTERFACE_URI.html):
+///
+///     extern crate lv2rs_core as core;
+///     extern crate lv2rs_ui as ui;
+///
+///     use std::ffi::CStr;
+///     use ui::*;
+///
+///     struct MyUI {}
+///
+///     impl PluginUI for MyUI {
+///         fn instantiate(
+///             _descriptor: &UIDescriptor,
+///             _plugin_uri: &CStr,
+///             _bundle_path: &CStr,
+///             _controller: Controller,
+///             _features: Option<&core::FeaturesList>,
+///         ) -> Option<Self> {
+///             Some(Self {})
+///         }
+///
+///         fn extension_data(uri: &CStr) -> Option<&'static dyn core::ExtensionData> {
+///             static IDLE: IdleInterfaceData = IdleInterfaceData::new::<MyUI>();
+///             if uri.to_bytes_with_nul() == ui::uris::IDLE_INTERFACE_URI {
+///                 Some(&IDLE)
+///             } else {
+///                 None
+///             }
+///         }
+///     }
+///
+///     impl IdleInterface for MyUI {
+///         fn idle(&mut self) -> bool {
+///             true
+///         }
+///     }
+#[repr(C)]
+pub struct IdleInterfaceData {
+    /// Run a single iteration of the UI's idle loop. Returns non-zero if the UI was closed.
+    pub idle: unsafe extern "C" fn(ui: UIHandle) -> c_int,
+}
+
+impl IdleInterfaceData {
+    /// Create the interface for the given UI type.
+    pub const fn new<U: IdleInterface>() -> Self {
+        Self { idle: idle::<U> }
+    }
+}
+
+impl ExtensionData for IdleInterfaceData {}
+
+unsafe extern "C" fn idle<U: IdleInterface>(ui: UIHandle) -> c_int {
+    let ui = (ui as *mut U).as_mut().unwrap();
+    if ui.idle() {
+        0
+    } else {
+        1
+    }
+}
+
+/// UI extension for showing and hiding a window.
+///
+/// UIs implementing this trait manage their own window, which the host can show and hide on
+/// request. This is used by UIs that can't be embedded into the host's windows. Since the host
+/// still has to drive the event loop of the UI, these UIs also have to implement the
+/// [`IdleInterface`](trait.IdleInterface.html).
+pub trait ShowInterface: IdleInterface {
+    /// Show a window for this UI.
+    ///
+    /// Return `true` if the window is visible afterwards.
+    fn show(&mut self) -> bool;
+
+    /// Hide the window for this UI.
+    ///
+    /// Return `true` if the window is hidden afterwards.
+    fn hide(&mut self) -> bool;
+}
+
+/// Raw representation of the show interface.
+///
+/// It is used just like the [`IdleInterfaceData`](struct.IdleInterfaceData.html) and returned for
+/// the [`SHOW_INTERFACE_URI`](uris/constant.SHOW_INTERFACE_URI.html).
+#[repr(C)]
+pub struct ShowInterfaceData {
+    /// Show a window for this UI. Returns zero on success.
+    pub show: unsafe extern "C" fn(ui: UIHandle) -> c_int,
+    /// Hide the window for this UI. Returns zero on success.
+    pub hide: unsafe extern "C" fn(ui: UIHandle) -> c_int,
+}
+
+impl ShowInterfaceData {
+    /// Create the interface for the given UI type.
+    pub const fn new<U: ShowInterface>() -> Self {
+        Self {
+            show: show::<U>,
+            hide: hide::<U>,
+        }
+    }
+}
+
+impl ExtensionData for ShowInterfaceData {}
+
+unsafe extern "C" fn show<U: ShowInterface>(ui: UIHandle) -> c_int {
+    let ui = (ui as *mut U).as_mut().unwrap();
+    if ui.show() {
+        0
+    } else {
+        1
+    }
+}
+
+unsafe extern "C" fn hide<U: ShowInterface>(ui: UIHandle) -> c_int {
+    let ui = (ui as *mut U).as_mut().unwrap();
+    if ui.hide() {
+        0
+    } else {
+        1
+    }
+}
+
+/// Handle for resizing a UI.
+///
+/// The `ui:resize` feature goes both ways: When provided by the host, the UI can use it to request
+/// a new size for its widget. When provided by the UI as extension data, the host can use it to
+/// notify the UI about a new size of its widget. For the latter, the UI implements the
+/// [`ResizeInterface`](trait.ResizeInterface.html) and returns the interface created by
+/// [`Resize::new`](#method.new).
+#[repr(C)]
+pub struct Resize {
+    /// Pointer to opaque data which must be passed to `ui_resize`.
+    pub handle: *mut c_void,
+    /// Request or advertise a size change. Returns zero on success.
+    pub ui_resize: unsafe extern "C" fn(handle: *mut c_void, width: c_int, height: c_int) -> c_int,
+}
+
+// The handle of the host's feature is only used by the host and the UI's interface doesn't have
+// one at all. Therefore, the struct can be stored in a static.
+unsafe impl Sync for Resize {}
+
+impl Resize {
+    /// Create the interface for the given UI type.
+    ///
+    /// The handle of the interface is null, since the host passes the UI instance handle instead.
+    pub const fn new<U: ResizeInterface>() -> Self {
+        Self {
+            handle: std::ptr::null_mut(),
+            ui_resize: resize::<U>,
+        }
+    }
+
+    /// Try to find the resize feature of the host in the features list.
+    ///
+    /// This function returns `None` if the host does not support resizing.
+    pub fn try_from_features(features: &FeaturesList) -> Option<&'static mut Self> {
+        unsafe {
+            Feature::get_feature::<Self>(
+                features,
+                CStr::from_bytes_with_nul_unchecked(uris::RESIZE_URI),
+            )
+        }
+    }
+
+    /// Request the host to resize the UI's widget.
+    ///
+    /// The host may refuse the request, in which case an `Err` is returned.
+    pub fn resize(&mut self, width: i32, height: i32) -> Result<(), ()> {
+        match unsafe { (self.ui_resize)(self.handle, width, height) } {
+            0 => Ok(()),
+            _ => Err(()),
+        }
+    }
+}
+
+impl ExtensionData for Resize {}
+
+/// UI extension for being notified about a new size of the widget.
+///
+/// See [`Resize`](struct.Resize.html) for more information.
+pub trait ResizeInterface: PluginUI {
+    /// Adapt the UI to the new size of its widget.
+    ///
+    /// Return `true` if the new size is accepted.
+    fn resize(&mut self, width: i32, height: i32) -> bool;
+}
+
+unsafe extern "C" fn resize<U: ResizeInterface>(
+    ui: *mut c_void,
+    width: c_int,
+    height: c_int,
+) -> c_int {
+    let ui = (ui as *mut U).as_mut().unwrap();
+    if ui.resize(width, height) {
+        0
+    } else {
+        1
+    }
+}
@@ -0,0 +1,138 @@
+//! A Rust adaptation of the [LV2 UI library](http://lv2plug.in/ns/extensions/ui/ui.html).
+//!
+//! LV2 plugin UIs are separate objects, often in separate libraries, which communicate with their
+//! plugin only via the host: The UI sends data to the plugin's input ports with a write function
+//! and the host notifies the UI about changes of the plugin's ports. This way, UIs can even run
+//! in another process or on another machine.
+//!
+//! UIs implement the [`PluginUI`](trait.PluginUI.html) trait and are exported with the
+//! [`lv2ui_main`](macro.lv2ui_main.html) macro. Additionally, this crate contains wrappers for
+//! the idle, show and resize interfaces, which are needed by most UIs, and means to exchange atoms
+//! with the plugin. UIs running in the same process as their plugin may also access the plugin
+//! directly with the [`InstanceAccess`](struct.InstanceAccess.html) and
+//! [`DataAccess`](struct.DataAccess.html) features. Ports should be looked up by their symbols
+//! with the [`PortMap`](struct.PortMap.html) feature, since their indices may change.
+//!
+//! This is a frozen prototype and therefore, development of this crate will not continue here. Further
+//! development continues as [rust-lv2](https://github.com/rust-dsp/rust-lv2).
+extern crate lv2rs_atom;
+extern crate lv2rs_core;
+extern crate lv2rs_urid;
+
+mod atom_transfer;
+mod instance_access;
+mod interfaces;
+mod plugin_ui;
+mod port_features;
+mod request_value;
+pub mod uris;
+
+pub use atom_transfer::*;
+pub use instance_access::*;
+pub use interfaces::*;
+pub use lv2rs_core::{ExtensionData, Feature, FeaturesList};
+pub use plugin_ui::*;
+pub use port_features::*;
+pub use request_value::*;
+
+/// Create LV2 UI export functions.
+///
+/// This macro takes a struct that implements [`PluginUI`](trait.PluginUI.html) and creates the
+/// required functions a UI library needs to export in order to be found and used by hosts.
+///
+/// Just like [`lv2_main`](../lv2rs_core/macro.lv2_main.html), it needs three arguments:
+/// * The namespace of the `lv2rs-ui` crate.
+/// * The struct type that should be used as the UI implementation.
+/// * The URI of the UI, as a null-terminated bytes-array.
+///
+///       extern crate lv2rs_core as core;
+///       extern crate lv2rs_ui as ui;
+///       use std::ffi::CStr;
+///
+///       struct MyUI {
+///           controller: ui::Controller,
+///       }
+///
+///       impl ui::PluginUI for MyUI {
+///           fn instantiate(
+///               _descriptor: &ui::UIDescriptor,
+///               _plugin_uri: &CStr,
+///               _bundle_path: &CStr,
+///               controller: ui::Controller,
+///               _features: Option<&core::FeaturesList>,
+///           ) -> Option<Self> {
+///               Some(Self { controller })
+///           }
+///
+///           fn port_event(&mut self, port_index: u32, _protocol: u32, _buffer: &[u8]) {
+///               // Reset the gain parameter whenever the plugin changes it.
+///               if port_index == 2 {
+///                   self.controller.write_float(2, 1.0);
+///               }
+///           }
+///       }
+///
+///       ui::lv2ui_main!(ui, MyUI, b"http://example.org/Dummy#ui\0");
+///
+#[macro_export]
+macro_rules! lv2ui_main {
+    ($c:ident, $s:ty, $u:expr) => {
+        const UI_URI: &'static [u8] = $u;
+        const UI_DESCRIPTOR: $c::UIDescriptor = $c::UIDescriptor {
+            uri: UI_URI.as_ptr() as *const std::os::raw::c_char,
+            instantiate: ui_instantiate,
+            cleanup: ui_cleanup,
+            port_event: ui_port_event,
+            extension_data: ui_extension_data,
+        };
+
+        unsafe extern "C" fn ui_instantiate(
+            descriptor: *const $c::UIDescriptor,
+            plugin_uri: *const std::os::raw::c_char,
+            bundle_path: *const std::os::raw::c_char,
+            write_function: $c::WriteFunction,
+            controller: $c::RawController,
+            widget: *mut $c::Widget,
+            features: *const *const $c::Feature,
+        ) -> $c::UIHandle {
+            $c::instantiate::<$s>(
+                descriptor,
+                plugin_uri,
+                bundle_path,
+                write_function,
+                controller,
+                widget,
+                features,
+            )
+        }
+
+        unsafe extern "C" fn ui_cleanup(instance: $c::UIHandle) {
+            $c::cleanup::<$s>(instance);
+        }
+
+        unsafe extern "C" fn ui_port_event(
+            instance: $c::UIHandle,
+            port_index: u32,
+            buffer_size: u32,
+            format: u32,
+            buffer: *const std::os::raw::c_void,
+        ) {
+            $c::port_event::<$s>(instance, port_index, buffer_size, format, buffer);
+        }
+
+        unsafe extern "C" fn ui_extension_data(
+            uri: *const std::os::raw::c_char,
+        ) -> *const std::os::raw::c_void {
+            $c::extension_data::<$s>(uri)
+        }
+
+        #[no_mangle]
+        pub unsafe extern "C" fn lv2ui_descriptor(index: u32) -> *const $c::UIDescriptor {
+            if index == 0 {
+                &UI_DESCRIPTOR
+            } else {
+                std::ptr::null()
+            }
+        }
+    };
+}
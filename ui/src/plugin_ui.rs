@@ -0,0 +1,340 @@
+//! General UI-related traits and functions.
+use lv2rs_core::{ExtensionData, Feature, FeaturesList};
+use std::ffi::CStr;
+use std::os::raw::*;
+
+/**
+   A pointer to some widget or other type of UI handle.
+
+   The actual type is defined by the type of the UI.
+*/
+pub type Widget = *mut c_void;
+
+/**
+   UI Instance Handle.
+*/
+pub type UIHandle = *mut c_void;
+
+/**
+   A pointer to a controller provided by the host, which is passed back to the write function.
+*/
+pub type RawController = *mut c_void;
+
+/**
+   A host-provided function that sends data to a plugin's input ports.
+
+   controller: The opaque controller pointer passed to instantiate().
+
+   port_index: The index of the port to write to.
+
+   buffer_size: The size of the data pointed to by `buffer`, in bytes.
+
+   port_protocol: Either 0 or the URID of a ui:PortProtocol. If 0, the protocol is implicitly
+   ui:floatProtocol, the port MUST be an lv2:ControlPort input, `buffer` MUST point to a single
+   float value, and `buffer_size` MUST be `size_of::<f32>()`.
+
+   buffer: Buffer containing the data to send.
+*/
+pub type WriteFunction = unsafe extern "C" fn(
+    controller: RawController,
+    port_index: u32,
+    buffer_size: u32,
+    port_protocol: u32,
+    buffer: *const c_void,
+);
+
+/**
+   UI Descriptor.
+
+   This structure provides the functions necessary to instantiate and use a UI.
+*/
+#[repr(C)]
+pub struct UIDescriptor {
+    /**
+       The URI for this UI (not for the plugin it controls).
+    */
+    pub uri: *const c_char,
+
+    /**
+       Create a new UI and return a handle to it.
+
+       descriptor: The descriptor for the UI to instantiate.
+
+       plugin_uri: The URI of the plugin that this UI will control.
+
+       bundle_path: The path to the bundle containing this UI, including the trailing directory
+       separator.
+
+       write_function: A function that the UI can use to send data to the plugin's input ports.
+
+       controller: A handle for the plugin instance to be passed as the first parameter of
+       write_function.
+
+       widget: A pointer to an LV2UI_Widget. The UI will write a widget pointer to this location
+       (what type of widget depends on the RDF class of the UI) that will be the main UI widget.
+
+       features: An array of LV2_Feature pointers. The host must pass all feature URIs that it
+       and the UI supports and any additional data, as in the LV2 core. This array must always
+       exist; if a host has no features, it should pass a single element array containing NULL.
+
+       return value: A pointer to the UI instance or NULL if instantiation failed.
+    */
+    pub instantiate: unsafe extern "C" fn(
+        descriptor: *const UIDescriptor,
+        plugin_uri: *const c_char,
+        bundle_path: *const c_char,
+        write_function: WriteFunction,
+        controller: RawController,
+        widget: *mut Widget,
+        features: *const *const Feature,
+    ) -> UIHandle,
+
+    /**
+       Destroy the UI. The host must not try to access the widget after calling this function.
+    */
+    pub cleanup: unsafe extern "C" fn(ui: UIHandle),
+
+    /**
+       Tell the UI that something interesting has happened at a plugin port.
+
+       What is "interesting" and how it is written to `buffer` is defined by `format`, which has
+       the same meaning as in write_function(). Format 0 is a special case for lv2:ControlPort,
+       where this function should be called when the port value changes (but not necessarily for
+       every change), `buffer_size` must be `size_of::<f32>()`, and `buffer` points to a single
+       IEEE-754 float.
+
+       This member may be NULL if the UI is not interested in any port events.
+    */
+    pub port_event: unsafe extern "C" fn(
+        ui: UIHandle,
+        port_index: u32,
+        buffer_size: u32,
+        format: u32,
+        buffer: *const c_void,
+    ),
+
+    /**
+       Return a data structure associated with an extension URI, typically an interface struct
+       with additional function pointers.
+
+       This member may be set to NULL if the UI is not interested in supporting any extensions.
+    */
+    pub extension_data: unsafe extern "C" fn(uri: *const c_char) -> *const c_void,
+}
+
+/// Safe handle for the host's write function.
+///
+/// The controller is passed to a UI when it's instantiated and it's the UI's only way to send
+/// data to the plugin: Either values for control ports or buffers with a certain protocol, for
+/// example atoms.
+///
+/// The controller is only valid as long as the UI instance exists.
+#[derive(Clone, Copy)]
+pub struct Controller {
+    write_function: WriteFunction,
+    controller: RawController,
+}
+
+impl Controller {
+    /// Create a new controller from the raw parts passed by the host.
+    pub fn new(write_function: WriteFunction, controller: RawController) -> Self {
+        Self {
+            write_function,
+            controller,
+        }
+    }
+
+    /// Write a buffer to a port of the plugin, using the given protocol.
+    ///
+    /// The protocol is the URID of a port protocol, or 0 for `ui:floatProtocol`. In the latter
+    /// case, you should use [`write_float`](#method.write_float) instead.
+    pub fn write(&self, port_index: u32, protocol: u32, buffer: &[u8]) {
+        unsafe {
+            (self.write_function)(
+                self.controller,
+                port_index,
+                buffer.len() as u32,
+                protocol,
+                buffer.as_ptr() as *const c_void,
+            )
+        }
+    }
+
+    /// Set the value of a control port of the plugin.
+    pub fn write_float(&self, port_index: u32, value: f32) {
+        unsafe {
+            (self.write_function)(
+                self.controller,
+                port_index,
+                std::mem::size_of::<f32>() as u32,
+                0,
+                &value as *const f32 as *const c_void,
+            )
+        }
+    }
+}
+
+/// LV2 UI trait.
+///
+/// This trait is the counterpart of the [`Plugin`](../lv2rs_core/trait.Plugin.html) trait for
+/// user interfaces. UIs implementing this trait can be exported with the
+/// [`lv2ui_main`](macro.lv2ui_main.html) macro.
+///
+/// Just like plugin calls, all UI calls from the host are checked and "safed" before these trait
+/// functions are called.
+pub trait PluginUI {
+    /// Create a new instance of the UI.
+    ///
+    /// You get the descriptor of the UI, the URI of the plugin it controls, the path of the bundle
+    /// and the features supported by the host. The controller is used to send data to the plugin
+    /// and should be stored by the UI. If the UI can't be created, you can return `None`.
+    fn instantiate(
+        descriptor: &UIDescriptor,
+        plugin_uri: &CStr,
+        bundle_path: &CStr,
+        controller: Controller,
+        features: Option<&FeaturesList>,
+    ) -> Option<Self>
+    where
+        Self: Sized;
+
+    /// Return the main widget of the UI.
+    ///
+    /// This is called once after the UI was instantiated and the returned pointer is passed to the
+    /// host. The type of the widget is defined by the type of the UI, for example a X11 window id
+    /// for `ui:X11UI`. UIs that only implement the [show interface](trait.ShowInterface.html)
+    /// don't have a widget and therefore, the default implementation returns a null pointer.
+    fn widget(&self) -> Widget {
+        std::ptr::null_mut()
+    }
+
+    /// Handle an update of a plugin port.
+    ///
+    /// The protocol is either 0, in which case the buffer contains a single float value of a
+    /// control port, or the URID of the protocol used to write the buffer.
+    ///
+    /// The default implementation does nothing.
+    fn port_event(&mut self, _port_index: u32, _protocol: u32, _buffer: &[u8]) {}
+
+    /// Clean up the UI.
+    ///
+    /// This is called right before the instance is dropped and the host must not use the widget
+    /// afterwards.
+    ///
+    /// The default implementation does nothing.
+    fn cleanup(&mut self) {}
+
+    /// Return extension specific data to the host.
+    ///
+    /// This works just like the [`extension_data`](../lv2rs_core/trait.Plugin.html#method.extension_data)
+    /// method of plugins and is used to provide interfaces like the
+    /// [idle interface](struct.IdleInterfaceData.html) to the host.
+    fn extension_data(_uri: &CStr) -> Option<&'static dyn ExtensionData> {
+        None
+    }
+}
+
+/// Helper function for the `instantiate` UI call.
+///
+/// This function takes the raw parameters provided by the C API and turns them into safe Rust data
+/// types.
+///
+/// # Safety
+///
+/// Only functions generated by the `lv2ui_main` should call the function any other should not.
+pub unsafe fn instantiate<U: PluginUI>(
+    descriptor: *const UIDescriptor,
+    plugin_uri: *const c_char,
+    bundle_path: *const c_char,
+    write_function: WriteFunction,
+    controller: RawController,
+    widget: *mut Widget,
+    features: *const *const Feature,
+) -> UIHandle {
+    let descriptor = match descriptor.as_ref() {
+        Some(desc) => desc,
+        None => return std::ptr::null_mut(),
+    };
+    if plugin_uri.is_null() || bundle_path.is_null() {
+        return std::ptr::null_mut();
+    }
+    let plugin_uri = CStr::from_ptr(plugin_uri);
+    let bundle_path = CStr::from_ptr(bundle_path);
+
+    let features = if features.is_null() {
+        None
+    } else {
+        let mut length = 0;
+        while !(*features.add(length)).is_null() {
+            length += 1;
+        }
+        Some(std::slice::from_raw_parts(
+            features as *const &'static Feature,
+            length,
+        ))
+    };
+
+    let controller = Controller::new(write_function, controller);
+    match U::instantiate(descriptor, plugin_uri, bundle_path, controller, features) {
+        Some(instance) => {
+            if let Some(widget) = widget.as_mut() {
+                *widget = instance.widget();
+            }
+            let instance = Box::new(instance);
+            Box::leak(instance) as *const U as UIHandle
+        }
+        None => std::ptr::null_mut(),
+    }
+}
+
+/// Helper function for the `cleanup` UI call.
+///
+/// This function calls the trait's `cleanup` function and drops the instance.
+///
+/// # Safety
+///
+/// Only functions generated by the `lv2ui_main` should call this function, any other must not.
+pub unsafe fn cleanup<U: PluginUI>(instance: UIHandle) {
+    let mut instance = Box::from_raw(instance as *mut U);
+    instance.cleanup();
+}
+
+/// Helper function for the `port_event` UI call.
+///
+/// This function takes the raw parameters provided by the C API, turns them into safe Rust data
+/// types, and calls the trait's function.
+///
+/// # Safety
+///
+/// Only functions generated by the `lv2ui_main` should call this function, any other must not.
+pub unsafe fn port_event<U: PluginUI>(
+    instance: UIHandle,
+    port_index: u32,
+    buffer_size: u32,
+    format: u32,
+    buffer: *const c_void,
+) {
+    let instance = (instance as *mut U).as_mut().unwrap();
+    let buffer = if buffer.is_null() {
+        &[]
+    } else {
+        std::slice::from_raw_parts(buffer as *const u8, buffer_size as usize)
+    };
+    instance.port_event(port_index, format, buffer);
+}
+
+/// Helper function for the `extension_data` UI call.
+///
+/// This function takes the raw parameters provided by the C API, turns them into safe Rust data
+/// types, and calls the trait's function.
+///
+/// # Safety
+///
+/// Only functions generated by the `lv2ui_main` should call this function, any other must not.
+pub unsafe fn extension_data<U: PluginUI>(uri: *const c_char) -> *const c_void {
+    let uri = CStr::from_ptr(uri);
+    match U::extension_data(uri) {
+        Some(ext_data) => ext_data as *const dyn ExtensionData as *const c_void,
+        None => std::ptr::null(),
+    }
+}
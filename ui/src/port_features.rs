@@ -0,0 +1,246 @@
+//! Wrappers for the port map, port subscribe and touch features.
+use crate::uris;
+use lv2rs_core::{Feature, FeaturesList};
+use lv2rs_urid::URID;
+use std::ffi::{CStr, CString};
+use std::os::raw::*;
+
+/// The index the port map returns for unknown symbols.
+pub const INVALID_PORT_INDEX: u32 = u32::MAX;
+
+/// The `ui:portMap` feature.
+///
+/// The indices of a plugin's ports may change between versions, while their symbols don't.
+/// Therefore, UIs should look up the indices of their ports by symbol, preferably when they are
+/// instantiated with [`PortIndices`](struct.PortIndices.html).
+#[repr(C)]
+pub struct PortMap {
+    /// Opaque data of the host, which must be passed to `port_index`.
+    pub handle: *mut c_void,
+    /// Return the index of the port with the given symbol or
+    /// [`INVALID_PORT_INDEX`](constant.INVALID_PORT_INDEX.html).
+    pub port_index: unsafe extern "C" fn(handle: *mut c_void, symbol: *const c_char) -> u32,
+}
+
+impl PortMap {
+    /// Try to find the port map feature of the host in the features list.
+    ///
+    /// This function returns `None` if the host does not support the feature.
+    pub fn try_from_features(features: &FeaturesList) -> Option<&'static mut Self> {
+        unsafe {
+            Feature::get_feature::<Self>(
+                features,
+                CStr::from_bytes_with_nul_unchecked(uris::PORT_MAP_URI),
+            )
+        }
+    }
+
+    /// Return the index of the port with the given symbol, if there is one.
+    pub fn port_index(&mut self, symbol: &CStr) -> Option<u32> {
+        match unsafe { (self.port_index)(self.handle, symbol.as_ptr()) } {
+            INVALID_PORT_INDEX => None,
+            index => Some(index),
+        }
+    }
+}
+
+/// Errors that may occur when resolving ports with
+/// [`PortIndices::resolve`](struct.PortIndices.html#method.resolve).
+#[derive(Clone, Debug, PartialEq)]
+pub enum ResolvePortError {
+    /// The host doesn't know a port with the given symbol.
+    UnknownSymbol(String),
+    /// The given symbol contains a null byte and can't be passed to the host.
+    InvalidSymbol(String),
+}
+
+/// The indices of a set of ports, resolved by their symbols.
+///
+///     extern crate lv2rs_core as core;
+///     extern crate lv2rs_ui as ui;
+///     use ui::{PortIndices, PortMap};
+///
+///     /// Called by the UI's `instantiate` function.
+///     fn resolve_ports(features: &core::FeaturesList) -> Option<PortIndices> {
+///         let port_map = PortMap::try_from_features(features)?;
+///         PortIndices::resolve(port_map, &["gain", "control", "notify"]).ok()
+///     }
+///
+///     assert!(resolve_ports(&[]).is_none());
+#[derive(Clone, Debug, PartialEq)]
+pub struct PortIndices {
+    ports: Vec<(String, u32)>,
+}
+
+impl PortIndices {
+    /// Look up the indices of the ports with the given symbols.
+    ///
+    /// If one of the symbols can't be resolved, an error naming it is returned.
+    pub fn resolve(port_map: &mut PortMap, symbols: &[&str]) -> Result<Self, ResolvePortError> {
+        let ports = symbols
+            .iter()
+            .map(|symbol| {
+                let c_symbol = CString::new(*symbol)
+                    .map_err(|_| ResolvePortError::InvalidSymbol(String::from(*symbol)))?;
+                let index = port_map
+                    .port_index(&c_symbol)
+                    .ok_or_else(|| ResolvePortError::UnknownSymbol(String::from(*symbol)))?;
+                Ok((String::from(*symbol), index))
+            })
+            .collect::<Result<Vec<(String, u32)>, ResolvePortError>>()?;
+        Ok(Self { ports })
+    }
+
+    /// Return the index of the port with the given symbol, if it was resolved.
+    pub fn index(&self, symbol: &str) -> Option<u32> {
+        self.ports
+            .iter()
+            .find(|(other, _)| other == symbol)
+            .map(|(_, index)| *index)
+    }
+
+    /// Return the symbol of the port with the given index, if it was resolved.
+    pub fn symbol(&self, index: u32) -> Option<&str> {
+        self.ports
+            .iter()
+            .find(|(_, other)| *other == index)
+            .map(|(symbol, _)| symbol.as_str())
+    }
+}
+
+/// Create a null-terminated array of feature pointers from the features list.
+///
+/// The array has to be kept alive as long as the host may use it.
+pub(crate) fn raw_features(features: Option<&FeaturesList>) -> Option<Vec<*const Feature>> {
+    features.map(|features| {
+        features
+            .iter()
+            .map(|feature| *feature as *const Feature)
+            .chain(std::iter::once(std::ptr::null()))
+            .collect()
+    })
+}
+
+/// Call a subscription function with the given features.
+fn call_subscription(
+    function: unsafe extern "C" fn(*mut c_void, u32, u32, *const *const Feature) -> u32,
+    handle: *mut c_void,
+    port_index: u32,
+    protocol: URID,
+    features: Option<&FeaturesList>,
+) -> bool {
+    let features = raw_features(features);
+    let features = match &features {
+        Some(features) => features.as_ptr(),
+        None => std::ptr::null(),
+    };
+    unsafe { function(handle, port_index, protocol, features) == 0 }
+}
+
+/// The `ui:portSubscribe` feature.
+///
+/// By default, the host only notifies the UI about the ports listed in the UI's description.
+/// With this feature, the UI may subscribe to and unsubscribe from other ports at runtime.
+#[repr(C)]
+pub struct PortSubscribe {
+    /// Opaque data of the host, which must be passed to `subscribe` and `unsubscribe`.
+    pub handle: *mut c_void,
+    /// Subscribe to a port with the given protocol. Returns zero on success.
+    pub subscribe: unsafe extern "C" fn(
+        handle: *mut c_void,
+        port_index: u32,
+        protocol: u32,
+        features: *const *const Feature,
+    ) -> u32,
+    /// Unsubscribe from a port with the given protocol. Returns zero on success.
+    pub unsubscribe: unsafe extern "C" fn(
+        handle: *mut c_void,
+        port_index: u32,
+        protocol: u32,
+        features: *const *const Feature,
+    ) -> u32,
+}
+
+impl PortSubscribe {
+    /// Try to find the port subscribe feature of the host in the features list.
+    ///
+    /// This function returns `None` if the host does not support the feature.
+    pub fn try_from_features(features: &FeaturesList) -> Option<&'static mut Self> {
+        unsafe {
+            Feature::get_feature::<Self>(
+                features,
+                CStr::from_bytes_with_nul_unchecked(uris::PORT_SUBSCRIBE_URI),
+            )
+        }
+    }
+
+    /// Subscribe to the port, in order to receive `port_event` calls for it.
+    ///
+    /// The protocol is the URID of a port protocol, like `ui:floatProtocol` or
+    /// `atom:eventTransfer`. Additional features may be passed to the host. Return `true` if the
+    /// host accepted the subscription.
+    pub fn subscribe(
+        &mut self,
+        port_index: u32,
+        protocol: URID,
+        features: Option<&FeaturesList>,
+    ) -> bool {
+        call_subscription(self.subscribe, self.handle, port_index, protocol, features)
+    }
+
+    /// Unsubscribe from the port.
+    ///
+    /// The protocol and the features should be the same that were used to subscribe. Return
+    /// `true` if the host removed the subscription.
+    pub fn unsubscribe(
+        &mut self,
+        port_index: u32,
+        protocol: URID,
+        features: Option<&FeaturesList>,
+    ) -> bool {
+        call_subscription(
+            self.unsubscribe,
+            self.handle,
+            port_index,
+            protocol,
+            features,
+        )
+    }
+}
+
+/// The `ui:touch` feature.
+///
+/// Hosts that record automation need to know when the user starts and stops changing a
+/// parameter, for example when a slider is grabbed and released, in order to not overwrite the
+/// user's changes with existing automation.
+#[repr(C)]
+pub struct Touch {
+    /// Opaque data of the host, which must be passed to `touch`.
+    pub handle: *mut c_void,
+    /// Notify the host that the control of a port was grabbed or released.
+    pub touch: unsafe extern "C" fn(handle: *mut c_void, port_index: u32, grabbed: bool),
+}
+
+impl Touch {
+    /// Try to find the touch feature of the host in the features list.
+    ///
+    /// This function returns `None` if the host does not support the feature.
+    pub fn try_from_features(features: &FeaturesList) -> Option<&'static mut Self> {
+        unsafe {
+            Feature::get_feature::<Self>(
+                features,
+                CStr::from_bytes_with_nul_unchecked(uris::TOUCH_URI),
+            )
+        }
+    }
+
+    /// Notify the host that the user started changing the port's value.
+    pub fn grab(&mut self, port_index: u32) {
+        unsafe { (self.touch)(self.handle, port_index, true) }
+    }
+
+    /// Notify the host that the user stopped changing the port's value.
+    pub fn release(&mut self, port_index: u32) {
+        unsafe { (self.touch)(self.handle, port_index, false) }
+    }
+}
@@ -0,0 +1,132 @@
+//! Wrapper for the request value feature.
+use crate::port_features::raw_features;
+use crate::uris;
+use lv2rs_core::{Feature, FeaturesList};
+use lv2rs_urid::URID;
+use std::ffi::CStr;
+use std::os::raw::*;
+
+/// The host is showing a dialog for the value.
+pub const REQUEST_VALUE_SUCCESS: c_int = 0;
+/// The host is already showing a dialog for another request.
+pub const REQUEST_VALUE_BUSY: c_int = 1;
+/// The request failed for an unknown reason.
+pub const REQUEST_VALUE_ERR_UNKNOWN: c_int = 2;
+/// The host can't ask for a value of the given type.
+pub const REQUEST_VALUE_UNSUPPORTED: c_int = 3;
+
+/// The result of a value request.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RequestValueStatus {
+    /// The host is showing a dialog for the value. The new value is sent to the plugin, not to the
+    /// UI, which is notified about it like about every other parameter change.
+    Success,
+    /// The host is already showing a dialog for another request.
+    Busy,
+    /// The host can't ask for a value of the given type.
+    Unsupported,
+    /// The request failed for an unknown reason.
+    Unknown,
+}
+
+impl RequestValueStatus {
+    /// Interpret the raw status returned by the host.
+    ///
+    /// Unknown values are interpreted as [`Unknown`](#variant.Unknown).
+    pub fn from_raw(status: c_int) -> Self {
+        match status {
+            REQUEST_VALUE_SUCCESS => RequestValueStatus::Success,
+            REQUEST_VALUE_BUSY => RequestValueStatus::Busy,
+            REQUEST_VALUE_UNSUPPORTED => RequestValueStatus::Unsupported,
+            _ => RequestValueStatus::Unknown,
+        }
+    }
+
+    /// Return the raw representation of the status.
+    pub fn into_raw(self) -> c_int {
+        match self {
+            RequestValueStatus::Success => REQUEST_VALUE_SUCCESS,
+            RequestValueStatus::Busy => REQUEST_VALUE_BUSY,
+            RequestValueStatus::Unsupported => REQUEST_VALUE_UNSUPPORTED,
+            RequestValueStatus::Unknown => REQUEST_VALUE_ERR_UNKNOWN,
+        }
+    }
+}
+
+/// The `ui:requestValue` feature.
+///
+/// With this feature, a UI asks the host to show a dialog for a parameter of the plugin, for
+/// example the host's native file chooser for a parameter of the type `atom:Path`. This way, UIs
+/// don't need to implement their own file choosers:
+///
+///     extern crate lv2rs_core as core;
+///     extern crate lv2rs_ui as ui;
+///     extern crate lv2rs_urid as urid;
+///     use std::ffi::CStr;
+///     use ui::{RequestValue, RequestValueStatus};
+///
+///     /// Ask the host for a new sample file. Return `false` if the UI has to show its own dialog.
+///     fn choose_sample(features: &core::FeaturesList, urids: &mut urid::CachedMap) -> bool {
+///         let request = match RequestValue::try_from_features(features) {
+///             Some(request) => request,
+///             None => return false,
+///         };
+///         let sample = CStr::from_bytes_with_nul(b"http://example.org/sample\0").unwrap();
+///         let path = CStr::from_bytes_with_nul(b"http://lv2plug.in/ns/ext/atom#Path\0").unwrap();
+///         let (sample, path) = (urids.map(sample), urids.map(path));
+///         match request.request(sample, path, None) {
+///             RequestValueStatus::Unsupported | RequestValueStatus::Unknown => false,
+///             _ => true,
+///         }
+///     }
+///
+///     let mut debug_map = urid::debug::DebugMap::new();
+///     let mut urids = unsafe { debug_map.create_cached_map() };
+///     assert!(!choose_sample(&[], &mut urids));
+#[repr(C)]
+pub struct RequestValue {
+    /// Opaque data of the host, which must be passed to `request`.
+    pub handle: *mut c_void,
+    /// Ask the host to show a dialog for the parameter with the given key and type.
+    pub request: unsafe extern "C" fn(
+        handle: *mut c_void,
+        key: URID,
+        value_type: URID,
+        features: *const *const Feature,
+    ) -> c_int,
+}
+
+impl RequestValue {
+    /// Try to find the request value feature of the host in the features list.
+    ///
+    /// This function returns `None` if the host does not support the feature.
+    pub fn try_from_features(features: &FeaturesList) -> Option<&'static mut Self> {
+        unsafe {
+            Feature::get_feature::<Self>(
+                features,
+                CStr::from_bytes_with_nul_unchecked(uris::REQUEST_VALUE_URI),
+            )
+        }
+    }
+
+    /// Ask the host to show a dialog for the parameter with the given key.
+    ///
+    /// The key is the URID of the parameter and the type is the URID of its value type, like
+    /// `atom:Path`. If the type is zero, the host uses the `rdfs:range` of the parameter.
+    /// Additional information, like a file filter, may be passed to the host as features.
+    pub fn request(
+        &mut self,
+        key: URID,
+        value_type: URID,
+        features: Option<&FeaturesList>,
+    ) -> RequestValueStatus {
+        let features = raw_features(features);
+        let features = match &features {
+            Some(features) => features.as_ptr(),
+            None => std::ptr::null(),
+        };
+        RequestValueStatus::from_raw(unsafe {
+            (self.request)(self.handle, key, value_type, features)
+        })
+    }
+}
@@ -0,0 +1,39 @@
+//! LV2 UI URIs
+
+pub const URI: &[u8] = b"http://lv2plug.in/ns/extensions/ui\0";
+pub const COCOA_UI_URI: &[u8] = b"http://lv2plug.in/ns/extensions/ui#CocoaUI\0";
+pub const GTK3_UI_URI: &[u8] = b"http://lv2plug.in/ns/extensions/ui#Gtk3UI\0";
+pub const GTK_UI_URI: &[u8] = b"http://lv2plug.in/ns/extensions/ui#GtkUI\0";
+pub const PORT_NOTIFICATION_URI: &[u8] = b"http://lv2plug.in/ns/extensions/ui#PortNotification\0";
+pub const PORT_PROTOCOL_URI: &[u8] = b"http://lv2plug.in/ns/extensions/ui#PortProtocol\0";
+pub const QT4_UI_URI: &[u8] = b"http://lv2plug.in/ns/extensions/ui#Qt4UI\0";
+pub const QT5_UI_URI: &[u8] = b"http://lv2plug.in/ns/extensions/ui#Qt5UI\0";
+pub const UI_URI: &[u8] = b"http://lv2plug.in/ns/extensions/ui#UI\0";
+pub const WINDOWS_UI_URI: &[u8] = b"http://lv2plug.in/ns/extensions/ui#WindowsUI\0";
+pub const X11_UI_URI: &[u8] = b"http://lv2plug.in/ns/extensions/ui#X11UI\0";
+pub const BINARY_URI: &[u8] = b"http://lv2plug.in/ns/extensions/ui#binary\0";
+pub const FIXED_SIZE_URI: &[u8] = b"http://lv2plug.in/ns/extensions/ui#fixedSize\0";
+pub const FLOAT_PROTOCOL_URI: &[u8] = b"http://lv2plug.in/ns/extensions/ui#floatProtocol\0";
+pub const IDLE_INTERFACE_URI: &[u8] = b"http://lv2plug.in/ns/extensions/ui#idleInterface\0";
+pub const MAKE_RESIDENT_URI: &[u8] = b"http://lv2plug.in/ns/extensions/ui#makeResident\0";
+pub const MAKE_SO_NAME_RESIDENT_URI: &[u8] =
+    b"http://lv2plug.in/ns/extensions/ui#makeSONameResident\0";
+pub const NO_USER_RESIZE_URI: &[u8] = b"http://lv2plug.in/ns/extensions/ui#noUserResize\0";
+pub const NOTIFY_TYPE_URI: &[u8] = b"http://lv2plug.in/ns/extensions/ui#notifyType\0";
+pub const PARENT_URI: &[u8] = b"http://lv2plug.in/ns/extensions/ui#parent\0";
+pub const PEAK_PROTOCOL_URI: &[u8] = b"http://lv2plug.in/ns/extensions/ui#peakProtocol\0";
+pub const PLUGIN_URI: &[u8] = b"http://lv2plug.in/ns/extensions/ui#plugin\0";
+pub const PORT_INDEX_URI: &[u8] = b"http://lv2plug.in/ns/extensions/ui#portIndex\0";
+pub const PORT_MAP_URI: &[u8] = b"http://lv2plug.in/ns/extensions/ui#portMap\0";
+pub const PORT_NOTIFICATION_PROPERTY_URI: &[u8] =
+    b"http://lv2plug.in/ns/extensions/ui#portNotification\0";
+pub const PORT_SUBSCRIBE_URI: &[u8] = b"http://lv2plug.in/ns/extensions/ui#portSubscribe\0";
+pub const PROTOCOL_URI: &[u8] = b"http://lv2plug.in/ns/extensions/ui#protocol\0";
+pub const REQUEST_VALUE_URI: &[u8] = b"http://lv2plug.in/ns/extensions/ui#requestValue\0";
+pub const RESIZE_URI: &[u8] = b"http://lv2plug.in/ns/extensions/ui#resize\0";
+pub const SHOW_INTERFACE_URI: &[u8] = b"http://lv2plug.in/ns/extensions/ui#showInterface\0";
+pub const TOUCH_URI: &[u8] = b"http://lv2plug.in/ns/extensions/ui#touch\0";
+pub const UPDATE_RATE_URI: &[u8] = b"http://lv2plug.in/ns/extensions/ui#updateRate\0";
+pub const SCALE_FACTOR_URI: &[u8] = b"http://lv2plug.in/ns/extensions/ui#scaleFactor\0";
+pub const FOREGROUND_COLOR_URI: &[u8] = b"http://lv2plug.in/ns/extensions/ui#foregroundColor\0";
+pub const BACKGROUND_COLOR_URI: &[u8] = b"http://lv2plug.in/ns/extensions/ui#backgroundColor\0";
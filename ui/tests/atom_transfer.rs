@@ -0,0 +1,104 @@
+extern crate lv2rs_atom as atom;
+extern crate lv2rs_ui as ui;
+extern crate lv2rs_urid as urid;
+
+use atom::ports::*;
+use atom::prelude::*;
+use std::ffi::CStr;
+use std::os::raw::*;
+use ui::*;
+use urid::debug::DebugMap;
+
+/// A stand-in host that records the calls of the write function.
+struct TestHost {
+    writes: Vec<(u32, u32, Vec<u8>)>,
+}
+
+unsafe extern "C" fn write_function(
+    controller: RawController,
+    port_index: u32,
+    buffer_size: u32,
+    port_protocol: u32,
+    buffer: *const c_void,
+) {
+    let host = (controller as *mut TestHost).as_mut().unwrap();
+    let buffer = std::slice::from_raw_parts(buffer as *const u8, buffer_size as usize);
+    host.writes
+        .push((port_index, port_protocol, buffer.to_owned()));
+}
+
+#[test]
+fn test_atom_transfer() {
+    let mut debug_map = DebugMap::new();
+    let mut urids = unsafe { debug_map.create_cached_map() };
+    let gain_urid = urids.map(CStr::from_bytes_with_nul(b"https://example.org#gain\0").unwrap());
+    let event_urid = TransferProtocol::Event.into_urid(&mut urids);
+
+    let mut host = TestHost { writes: Vec::new() };
+    let controller = Controller::new(write_function, &mut host as *mut TestHost as RawController);
+
+    // UI to plugin: An object is written to a buffer and sent via the controller.
+    let mut buffer = AtomBuffer::new(256);
+    {
+        let mut frame = buffer.write::<Object>(&(0, gain_urid), &mut urids).unwrap();
+        frame
+            .push_property::<f32>(gain_urid, 0, &0.5, &mut urids)
+            .unwrap();
+    }
+    controller.write_atom(3, TransferProtocol::Event, &buffer, &mut urids);
+    assert_eq!(1, host.writes.len());
+    let (port_index, protocol, data) = host.writes.pop().unwrap();
+    assert_eq!(3, port_index);
+    assert_eq!(event_urid, protocol);
+    assert_eq!(buffer.as_bytes(), data.as_slice());
+
+    // The plugin reads the atom with an input port, after the host has copied it.
+    let mut port_space = vec![0u64; 32];
+    let port_bytes =
+        unsafe { std::slice::from_raw_parts_mut(port_space.as_mut_ptr() as *mut u8, 256) };
+    port_bytes[..data.len()].copy_from_slice(&data);
+    let atom = unsafe { (port_space.as_mut_ptr() as *mut Atom).as_mut() }.unwrap();
+    {
+        let mut in_port: AtomInputPort<Object> = AtomInputPort::new();
+        in_port.connect_port(atom as &Atom);
+        let object = unsafe { in_port.get_atom_body(&mut urids) }.unwrap();
+        assert_eq!(gain_urid, object.header.otype);
+        let (header, value) = object.iter().next().unwrap();
+        assert_eq!(gain_urid, header.key);
+        assert_eq!(0.5, *value.get_body::<f32>(&mut urids).unwrap());
+    }
+
+    // Plugin to UI: The plugin writes an atom to an output port and the host passes it to the
+    // UI's port_event.
+    *(atom.mut_size()) = 256 - 8;
+    {
+        let mut out_port: AtomOutputPort<i32> = AtomOutputPort::new();
+        out_port.connect_port(atom);
+        unsafe { out_port.write_atom_body(&17, &mut urids) }.unwrap();
+    }
+    let event_size = 8 + atom.size();
+    let event = &port_bytes[..event_size];
+    assert_eq!(
+        Ok(&17),
+        read_port_event::<i32>(event_urid, event, &mut urids)
+    );
+
+    // Wrong protocols, types and buffers are refused.
+    assert_eq!(
+        Err(ReadAtomError::UnknownProtocol(0)),
+        read_port_event::<i32>(0, event, &mut urids)
+    );
+    let int_urid = urids.map(<i32 as AtomBody>::get_uri());
+    assert_eq!(
+        Err(ReadAtomError::WrongType(int_urid)),
+        read_port_event::<f32>(event_urid, event, &mut urids)
+    );
+    assert_eq!(
+        Err(ReadAtomError::MalformedBuffer),
+        read_port_event::<i32>(event_urid, &event[..event_size - 1], &mut urids)
+    );
+
+    // A buffer that is too small can't hold the atom.
+    let mut small_buffer = AtomBuffer::new(8);
+    assert!(small_buffer.write::<f64>(&1.0, &mut urids).is_err());
+}
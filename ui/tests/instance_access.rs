@@ -0,0 +1,131 @@
+extern crate lv2rs_core as core;
+extern crate lv2rs_ui as ui;
+
+use core::instance_access::*;
+use std::ffi::CStr;
+use std::os::raw::*;
+use std::ptr::{null, null_mut};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use ui::*;
+
+/// A plugin that shares the peak of its input with the UI.
+struct Meter {
+    peak: Arc<AtomicU32>,
+}
+
+impl core::Plugin for Meter {
+    fn instantiate(
+        _descriptor: &core::Descriptor,
+        _rate: f64,
+        _bundle_path: &CStr,
+        _features: Option<&FeaturesList>,
+    ) -> Option<Self> {
+        Some(Self {
+            peak: Arc::new(AtomicU32::new(0)),
+        })
+    }
+
+    fn connect_port(&mut self, _port: u32, _data: *mut ()) {}
+
+    fn run(&mut self, _n_samples: u32) {
+        self.peak.store(0.5f32.to_bits(), Ordering::Relaxed);
+    }
+
+    fn extension_data(uri: &CStr) -> Option<&'static dyn ExtensionData> {
+        static RANGE: MeterRange = MeterRange {
+            minimum: -60.0,
+            maximum: 6.0,
+        };
+        if uri.to_bytes_with_nul() == METER_RANGE_URI {
+            Some(&RANGE)
+        } else {
+            None
+        }
+    }
+}
+
+impl SharedData for Meter {
+    const URI: &'static [u8] = b"http://example.org/Meter\0";
+    type Data = AtomicU32;
+
+    fn shared_data(&self) -> Arc<AtomicU32> {
+        self.peak.clone()
+    }
+}
+
+const METER_RANGE_URI: &[u8] = b"http://example.org/Meter#range\0";
+
+/// Extension data of the meter.
+#[repr(C)]
+struct MeterRange {
+    minimum: f32,
+    maximum: f32,
+}
+
+impl ExtensionData for MeterRange {}
+
+core::lv2_main!(core, Meter, b"http://example.org/Meter\0");
+
+#[test]
+fn test_instance_access() {
+    // Instantiating and running the plugin, just like a host would.
+    let descriptor = unsafe { lv2_descriptor(0).as_ref() }.unwrap();
+    let empty_features: [*const Feature; 1] = [null()];
+    let instance = unsafe {
+        (descriptor.instantiate)(
+            descriptor,
+            44100.0,
+            b"/\0".as_ptr() as *const c_char,
+            empty_features.as_ptr(),
+        )
+    };
+    assert_ne!(null_mut(), instance);
+    unsafe { (descriptor.run)(instance, 64) };
+
+    let instance_feature = Feature::new(
+        CStr::from_bytes_with_nul(INSTANCE_ACCESS_URI).unwrap(),
+        instance,
+    );
+    let mut data_access = DataAccess {
+        data_access: descriptor.extension_data,
+    };
+    let data_feature = Feature::new(
+        CStr::from_bytes_with_nul(DATA_ACCESS_URI).unwrap(),
+        &mut data_access as *mut DataAccess as *mut c_void,
+    );
+    let instance_feature: &'static Feature = unsafe { &*(&instance_feature as *const Feature) };
+    let data_feature: &'static Feature = unsafe { &*(&data_feature as *const Feature) };
+    let features = [instance_feature, data_feature];
+
+    // The UI is created for another plugin or the host doesn't support the feature.
+    let other_uri = CStr::from_bytes_with_nul(b"http://example.org/Other\0").unwrap();
+    let meter_uri = CStr::from_bytes_with_nul(Meter::URI).unwrap();
+    assert!(InstanceAccess::<Meter>::try_from_features(&features, other_uri).is_none());
+    assert!(InstanceAccess::<Meter>::try_from_features(&[data_feature], meter_uri).is_none());
+
+    // Getting the shared data.
+    let access = InstanceAccess::<Meter>::try_from_features(&features, meter_uri).unwrap();
+    let peak = unsafe { access.shared_data() };
+    assert_eq!(0.5, f32::from_bits(peak.load(Ordering::Relaxed)));
+
+    // Getting the extension data.
+    let data_access = DataAccess::try_from_features(&features).unwrap();
+    let range = unsafe {
+        data_access
+            .extension_data::<MeterRange>(CStr::from_bytes_with_nul(METER_RANGE_URI).unwrap())
+    }
+    .unwrap();
+    assert_eq!((-60.0, 6.0), (range.minimum, range.maximum));
+    assert!(unsafe { data_access.extension_data::<MeterRange>(other_uri) }.is_none());
+    assert!(DataAccess::try_from_features(&[instance_feature]).is_none());
+
+    // The shared data outlives the instance.
+    unsafe {
+        core::cleanup::<Meter>(instance);
+        drop(Box::from_raw(
+            instance as *mut std::mem::ManuallyDrop<Meter>,
+        ));
+    }
+    assert_eq!(1, Arc::strong_count(&peak));
+}
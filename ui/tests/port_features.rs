@@ -0,0 +1,157 @@
+extern crate lv2rs_core as core;
+extern crate lv2rs_ui as ui;
+
+use std::ffi::CStr;
+use std::os::raw::*;
+use ui::*;
+
+/// The host side of the features, which records the calls of the UI.
+struct Host {
+    symbols: Vec<&'static str>,
+    calls: Vec<String>,
+}
+
+unsafe extern "C" fn port_index(handle: *mut c_void, symbol: *const c_char) -> u32 {
+    let host = (handle as *mut Host).as_mut().unwrap();
+    let symbol = CStr::from_ptr(symbol).to_str().unwrap();
+    match host.symbols.iter().position(|other| *other == symbol) {
+        Some(index) => index as u32,
+        None => INVALID_PORT_INDEX,
+    }
+}
+
+/// Count the features in a null-terminated features array.
+unsafe fn count_features(features: *const *const Feature) -> Option<usize> {
+    if features.is_null() {
+        return None;
+    }
+    let mut length = 0;
+    while !(*features.add(length)).is_null() {
+        length += 1;
+    }
+    Some(length)
+}
+
+unsafe extern "C" fn subscribe(
+    handle: *mut c_void,
+    port_index: u32,
+    protocol: u32,
+    features: *const *const Feature,
+) -> u32 {
+    let host = (handle as *mut Host).as_mut().unwrap();
+    if port_index as usize >= host.symbols.len() {
+        return 1;
+    }
+    host.calls.push(format!(
+        "subscribe {} {} {:?}",
+        port_index,
+        protocol,
+        count_features(features)
+    ));
+    0
+}
+
+unsafe extern "C" fn unsubscribe(
+    handle: *mut c_void,
+    port_index: u32,
+    protocol: u32,
+    features: *const *const Feature,
+) -> u32 {
+    let host = (handle as *mut Host).as_mut().unwrap();
+    host.calls.push(format!(
+        "unsubscribe {} {} {:?}",
+        port_index,
+        protocol,
+        count_features(features)
+    ));
+    0
+}
+
+unsafe extern "C" fn touch(handle: *mut c_void, port_index: u32, grabbed: bool) {
+    let host = (handle as *mut Host).as_mut().unwrap();
+    host.calls.push(format!("touch {} {}", port_index, grabbed));
+}
+
+#[test]
+fn test_port_features() {
+    let mut host = Host {
+        symbols: vec!["input", "output", "gain", "notify"],
+        calls: Vec::new(),
+    };
+    let handle = &mut host as *mut Host as *mut c_void;
+
+    let mut port_map = PortMap { handle, port_index };
+    let mut port_subscribe = PortSubscribe {
+        handle,
+        subscribe,
+        unsubscribe,
+    };
+    let mut touch_data = Touch { handle, touch };
+    let port_map_feature = Feature::new(
+        CStr::from_bytes_with_nul(ui::uris::PORT_MAP_URI).unwrap(),
+        &mut port_map as *mut PortMap as *mut c_void,
+    );
+    let subscribe_feature = Feature::new(
+        CStr::from_bytes_with_nul(ui::uris::PORT_SUBSCRIBE_URI).unwrap(),
+        &mut port_subscribe as *mut PortSubscribe as *mut c_void,
+    );
+    let touch_feature = Feature::new(
+        CStr::from_bytes_with_nul(ui::uris::TOUCH_URI).unwrap(),
+        &mut touch_data as *mut Touch as *mut c_void,
+    );
+    let features: [&'static Feature; 3] = unsafe {
+        [
+            &*(&port_map_feature as *const Feature),
+            &*(&subscribe_feature as *const Feature),
+            &*(&touch_feature as *const Feature),
+        ]
+    };
+
+    // Resolving the ports like a UI's `instantiate` function.
+    let port_map = PortMap::try_from_features(&features).unwrap();
+    assert_eq!(
+        None,
+        port_map.port_index(CStr::from_bytes_with_nul(b"volume\0").unwrap())
+    );
+    assert_eq!(
+        Err(ResolvePortError::UnknownSymbol(String::from("volume"))),
+        PortIndices::resolve(port_map, &["gain", "volume"])
+    );
+    assert_eq!(
+        Err(ResolvePortError::InvalidSymbol(String::from("ga\0in"))),
+        PortIndices::resolve(port_map, &["ga\0in"])
+    );
+    let ports = PortIndices::resolve(port_map, &["notify", "gain"]).unwrap();
+    assert_eq!(Some(2), ports.index("gain"));
+    assert_eq!(Some(3), ports.index("notify"));
+    assert_eq!(None, ports.index("input"));
+    assert_eq!(Some("gain"), ports.symbol(2));
+    assert_eq!(None, ports.symbol(0));
+
+    // Subscribing to the notification port.
+    let gain = ports.index("gain").unwrap();
+    let notify = ports.index("notify").unwrap();
+    let port_subscribe = PortSubscribe::try_from_features(&features).unwrap();
+    assert!(port_subscribe.subscribe(notify, 7, None));
+    assert!(!port_subscribe.subscribe(12, 7, None));
+    assert!(port_subscribe.unsubscribe(notify, 7, Some(&features[..1])));
+
+    // Automating the gain.
+    let touch = Touch::try_from_features(&features).unwrap();
+    touch.grab(gain);
+    touch.release(gain);
+
+    assert!(PortMap::try_from_features(&[]).is_none());
+    assert!(PortSubscribe::try_from_features(&features[..1]).is_none());
+    assert!(Touch::try_from_features(&features[..2]).is_none());
+
+    assert_eq!(
+        vec![
+            "subscribe 3 7 None",
+            "unsubscribe 3 7 Some(1)",
+            "touch 2 true",
+            "touch 2 false",
+        ],
+        host.calls
+    );
+}
@@ -0,0 +1,98 @@
+extern crate lv2rs_core as core;
+extern crate lv2rs_ui as ui;
+
+use std::ffi::CStr;
+use std::os::raw::*;
+use ui::*;
+
+/// A stand-in host that records requests and only shows file choosers.
+struct Host {
+    path_type: u32,
+    dialog_open: bool,
+    requests: Vec<(u32, u32, Option<usize>)>,
+}
+
+unsafe extern "C" fn request(
+    handle: *mut c_void,
+    key: u32,
+    value_type: u32,
+    features: *const *const Feature,
+) -> c_int {
+    let host = (handle as *mut Host).as_mut().unwrap();
+    let n_features = if features.is_null() {
+        None
+    } else {
+        let mut length = 0;
+        while !(*features.add(length)).is_null() {
+            length += 1;
+        }
+        Some(length)
+    };
+    host.requests.push((key, value_type, n_features));
+
+    if value_type != host.path_type {
+        REQUEST_VALUE_UNSUPPORTED
+    } else if host.dialog_open {
+        REQUEST_VALUE_BUSY
+    } else {
+        host.dialog_open = true;
+        REQUEST_VALUE_SUCCESS
+    }
+}
+
+#[test]
+fn test_request_value() {
+    let mut host = Host {
+        path_type: 5,
+        dialog_open: false,
+        requests: Vec::new(),
+    };
+    let mut request_value = RequestValue {
+        handle: &mut host as *mut Host as *mut c_void,
+        request,
+    };
+    let feature = Feature::new(
+        CStr::from_bytes_with_nul(ui::uris::REQUEST_VALUE_URI).unwrap(),
+        &mut request_value as *mut RequestValue as *mut c_void,
+    );
+    let features = [unsafe { &*(&feature as *const Feature) }];
+
+    assert!(RequestValue::try_from_features(&[]).is_none());
+    let request_value = RequestValue::try_from_features(&features).unwrap();
+
+    assert_eq!(
+        RequestValueStatus::Success,
+        request_value.request(10, 5, None)
+    );
+    assert_eq!(
+        RequestValueStatus::Busy,
+        request_value.request(10, 5, Some(&features))
+    );
+    assert_eq!(
+        RequestValueStatus::Unsupported,
+        request_value.request(11, 6, Some(&[]))
+    );
+    assert_eq!(
+        vec![(10, 5, None), (10, 5, Some(1)), (11, 6, Some(0))],
+        host.requests
+    );
+
+    for status in [
+        RequestValueStatus::Success,
+        RequestValueStatus::Busy,
+        RequestValueStatus::Unsupported,
+        RequestValueStatus::Unknown,
+    ]
+    .iter()
+    {
+        assert_eq!(*status, RequestValueStatus::from_raw(status.into_raw()));
+    }
+    assert_eq!(
+        RequestValueStatus::Unknown,
+        RequestValueStatus::from_raw(REQUEST_VALUE_ERR_UNKNOWN)
+    );
+    assert_eq!(
+        RequestValueStatus::Unknown,
+        RequestValueStatus::from_raw(42)
+    );
+}
@@ -0,0 +1,250 @@
+extern crate lv2rs_ui as ui;
+
+use std::ffi::CStr;
+use std::os::raw::*;
+use std::ptr::{null, null_mut};
+use ui::*;
+
+/// A simple test UI.
+///
+/// It mirrors every value of the first control port to the second one and echoes all other port
+/// events. Its "widget" is its size, it can be shown and hidden, and it reports itself as closed
+/// when it's hidden.
+struct TestUI {
+    controller: Controller,
+    visible: bool,
+    size: Box<(i32, i32)>,
+}
+
+impl PluginUI for TestUI {
+    fn instantiate(
+        _descriptor: &UIDescriptor,
+        plugin_uri: &CStr,
+        _bundle_path: &CStr,
+        controller: Controller,
+        features: Option<&FeaturesList>,
+    ) -> Option<Self> {
+        if plugin_uri.to_bytes() != b"http://example.org/TestPlugin" {
+            return None;
+        }
+
+        // Request a size from the host.
+        if let Some(resize) = features.and_then(Resize::try_from_features) {
+            resize.resize(400, 300).ok()?;
+        }
+
+        Some(Self {
+            controller,
+            visible: false,
+            size: Box::new((0, 0)),
+        })
+    }
+
+    fn widget(&self) -> Widget {
+        self.size.as_ref() as *const (i32, i32) as Widget
+    }
+
+    fn port_event(&mut self, port_index: u32, protocol: u32, buffer: &[u8]) {
+        if port_index == 0 && protocol == 0 && buffer.len() == 4 {
+            let value = unsafe { *(buffer.as_ptr() as *const f32) };
+            self.controller.write_float(1, value);
+        } else {
+            self.controller.write(port_index, protocol, buffer);
+        }
+    }
+
+    fn extension_data(uri: &CStr) -> Option<&'static dyn ExtensionData> {
+        static IDLE: IdleInterfaceData = IdleInterfaceData::new::<TestUI>();
+        static SHOW: ShowInterfaceData = ShowInterfaceData::new::<TestUI>();
+        static RESIZE: Resize = Resize::new::<TestUI>();
+        match uri.to_bytes_with_nul() {
+            ui::uris::IDLE_INTERFACE_URI => Some(&IDLE),
+            ui::uris::SHOW_INTERFACE_URI => Some(&SHOW),
+            ui::uris::RESIZE_URI => Some(&RESIZE),
+            _ => None,
+        }
+    }
+}
+
+impl IdleInterface for TestUI {
+    fn idle(&mut self) -> bool {
+        self.visible
+    }
+}
+
+impl ShowInterface for TestUI {
+    fn show(&mut self) -> bool {
+        self.visible = true;
+        true
+    }
+
+    fn hide(&mut self) -> bool {
+        self.visible = false;
+        true
+    }
+}
+
+impl ResizeInterface for TestUI {
+    fn resize(&mut self, width: i32, height: i32) -> bool {
+        *self.size = (width, height);
+        width > 0 && height > 0
+    }
+}
+
+ui::lv2ui_main!(ui, TestUI, b"http://example.org/TestPlugin#ui\0");
+
+/// A stand-in host that records the calls of the write function and the resize requests.
+struct TestHost {
+    writes: Vec<(u32, u32, Vec<u8>)>,
+    requested_size: Option<(i32, i32)>,
+}
+
+unsafe extern "C" fn write_function(
+    controller: RawController,
+    port_index: u32,
+    buffer_size: u32,
+    port_protocol: u32,
+    buffer: *const c_void,
+) {
+    let host = (controller as *mut TestHost).as_mut().unwrap();
+    let buffer = std::slice::from_raw_parts(buffer as *const u8, buffer_size as usize);
+    host.writes
+        .push((port_index, port_protocol, buffer.to_owned()));
+}
+
+unsafe extern "C" fn host_resize(handle: *mut c_void, width: c_int, height: c_int) -> c_int {
+    let host = (handle as *mut TestHost).as_mut().unwrap();
+    host.requested_size = Some((width, height));
+    0
+}
+
+const PLUGIN_URI: &[u8] = b"http://example.org/TestPlugin\0";
+const BUNDLE_PATH: &[u8] = b"/\0";
+
+#[test]
+fn test_ui() {
+    let mut host = TestHost {
+        writes: Vec::new(),
+        requested_size: None,
+    };
+    let host_ptr = &mut host as *mut TestHost as *mut c_void;
+
+    let mut resize = Resize {
+        handle: host_ptr,
+        ui_resize: host_resize,
+    };
+    let resize_feature = Feature::new(
+        CStr::from_bytes_with_nul(ui::uris::RESIZE_URI).unwrap(),
+        &mut resize as *mut Resize as *mut c_void,
+    );
+    let features: [*const Feature; 2] = [&resize_feature, null()];
+
+    let descriptor = unsafe { lv2ui_descriptor(0) };
+    let descriptor_ref = unsafe { descriptor.as_ref() }.unwrap();
+    assert_eq!(
+        b"http://example.org/TestPlugin#ui",
+        unsafe { CStr::from_ptr(descriptor_ref.uri) }.to_bytes()
+    );
+    assert_eq!(null(), unsafe { lv2ui_descriptor(1) });
+
+    // Instantiating with invalid parameters or for the wrong plugin.
+    let mut widget: Widget = null_mut();
+    unsafe {
+        assert_eq!(
+            null_mut(),
+            (descriptor_ref.instantiate)(
+                null(),
+                PLUGIN_URI.as_ptr() as *const c_char,
+                BUNDLE_PATH.as_ptr() as *const c_char,
+                write_function,
+                host_ptr,
+                &mut widget,
+                features.as_ptr(),
+            )
+        );
+        assert_eq!(
+            null_mut(),
+            (descriptor_ref.instantiate)(
+                descriptor,
+                BUNDLE_PATH.as_ptr() as *const c_char,
+                BUNDLE_PATH.as_ptr() as *const c_char,
+                write_function,
+                host_ptr,
+                &mut widget,
+                features.as_ptr(),
+            )
+        );
+    }
+
+    // Instantiating the UI.
+    let handle = unsafe {
+        (descriptor_ref.instantiate)(
+            descriptor,
+            PLUGIN_URI.as_ptr() as *const c_char,
+            BUNDLE_PATH.as_ptr() as *const c_char,
+            write_function,
+            host_ptr,
+            &mut widget,
+            features.as_ptr(),
+        )
+    };
+    assert_ne!(null_mut(), handle);
+    assert_ne!(null_mut(), widget);
+    assert_eq!(Some((400, 300)), host.requested_size);
+
+    // Port events are answered with writes.
+    let value: f32 = 0.5;
+    let atom_data: [u8; 4] = [1, 2, 3, 4];
+    unsafe {
+        (descriptor_ref.port_event)(handle, 0, 4, 0, &value as *const f32 as *const c_void);
+        (descriptor_ref.port_event)(handle, 2, 4, 17, atom_data.as_ptr() as *const c_void);
+    }
+    assert_eq!(
+        vec![
+            (1, 0, value.to_ne_bytes().to_vec()),
+            (2, 17, atom_data.to_vec())
+        ],
+        host.writes
+    );
+
+    // Getting the interfaces.
+    let idle_interface = unsafe {
+        ((descriptor_ref.extension_data)(ui::uris::IDLE_INTERFACE_URI.as_ptr() as *const c_char)
+            as *const IdleInterfaceData)
+            .as_ref()
+    }
+    .unwrap();
+    let show_interface = unsafe {
+        ((descriptor_ref.extension_data)(ui::uris::SHOW_INTERFACE_URI.as_ptr() as *const c_char)
+            as *const ShowInterfaceData)
+            .as_ref()
+    }
+    .unwrap();
+    let resize_interface = unsafe {
+        ((descriptor_ref.extension_data)(ui::uris::RESIZE_URI.as_ptr() as *const c_char)
+            as *const Resize)
+            .as_ref()
+    }
+    .unwrap();
+    assert_eq!(null(), unsafe {
+        (descriptor_ref.extension_data)(PLUGIN_URI.as_ptr() as *const c_char)
+    });
+
+    // Showing, idling and hiding.
+    unsafe {
+        assert_eq!(0, (show_interface.show)(handle));
+        assert_eq!(0, (idle_interface.idle)(handle));
+        assert_eq!(0, (show_interface.hide)(handle));
+        assert_ne!(0, (idle_interface.idle)(handle));
+    }
+
+    // Resizing the widget.
+    unsafe {
+        assert_eq!(0, (resize_interface.ui_resize)(handle, 800, 600));
+        assert_ne!(0, (resize_interface.ui_resize)(handle, 0, 600));
+        assert_eq!((0, 600), *(widget as *const (i32, i32)));
+    }
+
+    // Cleanup.
+    unsafe { (descriptor_ref.cleanup)(handle) };
+}
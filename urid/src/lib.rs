@@ -49,6 +49,7 @@
 extern crate lv2rs_core as core;
 
 pub mod debug;
+pub mod uri_map;
 pub mod uris;
 
 use std::collections::HashMap;
@@ -138,19 +139,31 @@ impl Unmap {
 
 /// Cached version of [Map](struct.Map.html)
 pub struct CachedMap {
-    raw: &'static mut Map,
+    raw: Map,
     cache: HashMap<&'static CStr, URID>,
 }
 
 impl CachedMap {
     /// Create a new cached map from a mutable map reference.
     pub fn new(raw: &'static mut Map) -> CachedMap {
+        Self::from_map(Map {
+            handle: raw.handle,
+            map: raw.map,
+        })
+    }
+
+    fn from_map(raw: Map) -> CachedMap {
         Self {
             raw: raw,
             cache: HashMap::new(),
         }
     }
 
+    /// Create a new cached map that is backed by the deprecated uri-map feature.
+    pub fn from_uri_map(uri_map: &'static mut uri_map::UriMap) -> CachedMap {
+        Self::from_map(uri_map.to_map())
+    }
+
     /// Try to find the mapping feature in the features map.
     ///
     /// If this function returns `None` if the host does not support mapping.
@@ -159,6 +172,18 @@ impl CachedMap {
         Some(Self::new(raw_map))
     }
 
+    /// Try to find a mapping feature in the features map, including deprecated ones.
+    ///
+    /// The `urid:map` feature is preferred, but if the host only supports the deprecated
+    /// [uri-map](uri_map/index.html) feature, this one is used instead. `None` is returned if the
+    /// host supports neither.
+    pub fn try_from_legacy_features(features: &core::FeaturesList) -> Option<Self> {
+        match Map::try_from_features(features) {
+            Some(raw_map) => Some(Self::new(raw_map)),
+            None => Some(Self::from_uri_map(uri_map::UriMap::try_from_features(features)?)),
+        }
+    }
+
     /// Return a reference to the cache.
     pub fn cache(&self) -> &HashMap<&'static CStr, URID> {
         &self.cache
@@ -0,0 +1,79 @@
+//! Compatibility layer for the deprecated uri-map feature.
+//!
+//! Before the URID extension was introduced, URIs were mapped to integers with the
+//! [uri-map extension](http://lv2plug.in/ns/ext/uri-map). Some older hosts still only provide
+//! this feature. Its mapping function has an additional "map" parameter, the URI of the extension
+//! the mapped URI is used with; The `uri-map` IDs of event types, for example, are mapped with the
+//! URI of the event extension.
+//!
+//! A [`UriMap`](struct.UriMap.html) can be turned into a [`Map`](../struct.Map.html) that maps
+//! URIs without the extension context. This way, the rest of the library works with hosts that
+//! only support `uri-map`:
+//!
+//!     extern crate lv2rs_core as core;
+//!     extern crate lv2rs_urid as urid;
+//!
+//!     /// Called by the plugin's `instantiate` function.
+//!     fn get_urids(features: &core::FeaturesList) -> Option<urid::CachedMap> {
+//!         // Use `urid:map` if the host supports it and `uri-map` otherwise.
+//!         urid::CachedMap::try_from_legacy_features(features)
+//!     }
+//!
+//!     assert!(get_urids(&[]).is_none());
+use crate::{Map, MapHandle, URID};
+use std::ffi::CStr;
+use std::os::raw::*;
+
+pub const URI: &[u8] = b"http://lv2plug.in/ns/ext/uri-map\0";
+
+/// Type to describe pointers to uri-map callback data.
+pub type UriMapCallbackData = *mut c_void;
+
+/// The uri-map feature.
+#[repr(C)]
+pub struct UriMap {
+    /// Pointer to host-specific data, which has to be passed to `uri_to_id`.
+    pub callback_data: UriMapCallbackData,
+    /// Map a URI to an ID in the context of the given extension URI, which may be null.
+    ///
+    /// The ID zero is returned if the URI couldn't be mapped.
+    pub uri_to_id: extern "C" fn(
+        callback_data: UriMapCallbackData,
+        map: *const c_char,
+        uri: *const c_char,
+    ) -> u32,
+}
+
+impl UriMap {
+    /// Try to find the uri-map feature in the features list.
+    ///
+    /// This function returns `None` if the host does not support the feature.
+    pub fn try_from_features(features: &core::FeaturesList) -> Option<&'static mut Self> {
+        unsafe {
+            core::Feature::get_feature::<Self>(features, CStr::from_bytes_with_nul_unchecked(URI))
+        }
+    }
+
+    /// Map a URI to an ID in the context of the given extension.
+    ///
+    /// If `map` is `None`, the URI is mapped without a context, just like a URID.
+    pub fn uri_to_id(&mut self, map: Option<&CStr>, uri: &CStr) -> u32 {
+        let map = map.map(CStr::as_ptr).unwrap_or(std::ptr::null());
+        (self.uri_to_id)(self.callback_data, map, uri.as_ptr())
+    }
+
+    /// Create a URID map that is backed by this feature.
+    ///
+    /// The map maps URIs without an extension context.
+    pub fn to_map(&'static mut self) -> Map {
+        Map {
+            handle: self as *mut Self as MapHandle,
+            map: map_with_uri_map,
+        }
+    }
+}
+
+extern "C" fn map_with_uri_map(handle: MapHandle, uri: *const c_char) -> URID {
+    let uri_map = unsafe { (handle as *mut UriMap).as_mut() }.unwrap();
+    (uri_map.uri_to_id)(uri_map.callback_data, std::ptr::null(), uri)
+}